@@ -0,0 +1,298 @@
+//! Async inference on top of the blocking vstream API.
+//!
+//! An `AsyncDevice` moves a `HailoDevice` onto a dedicated worker thread. Requests are queued to
+//! that thread and run one after the other, so the blocking writes and reads never stall an async
+//! executor and the outputs of one request can never be mixed up with those of another.
+//!
+//! Dropping a future or stream cancels only the wait: a request that has been queued always runs
+//! to completion so that the vstreams stay in step, and its result is discarded.
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread::JoinHandle;
+
+use futures_core::Stream;
+
+use crate::error::{HailorsError, Result};
+use crate::network::Network;
+use crate::vstream::VStreamInfo;
+use crate::HailoDevice;
+
+/// A request run on the worker thread.
+type Job = Box<dyn FnOnce(&HailoDevice) + Send>;
+
+/// A `HailoDevice` running on a dedicated worker thread, driven through futures.
+///
+/// # Example
+///
+/// ```rust
+/// use hailors::{HailoDevice, MockBackend, VStreamShape};
+///
+/// # futures::executor::block_on(async {
+/// let mock = MockBackend::new()
+///     .with_input("model/input_layer1", VStreamShape::new(2, 2, 3))
+///     .with_output("model/output_layer1", VStreamShape::new(1, 1, 4));
+/// let device = HailoDevice::with_backend(mock).into_async();
+///
+/// let outputs = device.infer_async(vec![vec![0u8; 12]]).await.unwrap();
+/// assert_eq!(outputs[0].len(), 16);
+/// # });
+/// ```
+pub struct AsyncDevice {
+    /// Queue of requests for the worker thread.
+    jobs: Option<mpsc::Sender<Job>>,
+    /// The worker thread, which owns the device and hands it back when it exits.
+    worker: Option<JoinHandle<HailoDevice>>,
+    /// Information about each input virtual stream.
+    inputs: Vec<VStreamInfo>,
+    /// Information about each output virtual stream.
+    outputs: Vec<VStreamInfo>,
+}
+
+impl AsyncDevice {
+    /// Moves a device onto a new worker thread.
+    pub fn new(device: HailoDevice) -> Self {
+        let inputs = device.inputs().to_vec();
+        let outputs = device.outputs().to_vec();
+        let (jobs, queue) = mpsc::channel::<Job>();
+        let worker = std::thread::Builder::new()
+            .name("hailors-worker".to_string())
+            .spawn(move || {
+                for job in queue {
+                    job(&device);
+                }
+                device
+            })
+            .expect("Failed to spawn the inference worker thread");
+
+        Self {
+            jobs: Some(jobs),
+            worker: Some(worker),
+            inputs,
+            outputs,
+        }
+    }
+
+    /// Returns the input virtual streams of the configured model.
+    pub fn inputs(&self) -> &[VStreamInfo] {
+        &self.inputs
+    }
+
+    /// Returns the output virtual streams of the configured model.
+    pub fn outputs(&self) -> &[VStreamInfo] {
+        &self.outputs
+    }
+
+    /// Runs a closure against the device on the worker thread.
+    ///
+    /// The closure runs after every previously queued request and is not interrupted if the
+    /// returned future is dropped.
+    pub fn run<R, F>(&self, f: F) -> InferFuture<R>
+    where
+        R: Send + 'static,
+        F: FnOnce(&HailoDevice) -> Result<R> + Send + 'static,
+    {
+        let slot = Arc::new(Mutex::new(Slot::default()));
+        let completer = Completer { slot: Some(slot.clone()) };
+        let job: Job = Box::new(move |device| completer.complete(f(device)));
+
+        // If the worker is gone the job is dropped here, which completes it with `WorkerStopped`
+        if let Some(jobs) = &self.jobs {
+            let _ = jobs.send(job);
+        }
+        InferFuture { slot }
+    }
+
+    /// Runs one inference by writing all inputs and reading all outputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `frames` - One frame per input stream, in the order of `inputs()`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a future resolving to one raw frame per output stream, in the order of `outputs()`.
+    pub fn infer_async(&self, frames: Vec<Vec<u8>>) -> InferFuture<Vec<Vec<u8>>> {
+        self.run(move |device| {
+            let frames: Vec<&[u8]> = frames.iter().map(Vec::as_slice).collect();
+            device.infer(&frames)
+        })
+    }
+
+    /// Writes a frame to the first input stream and parses the first output stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The input frame.
+    /// * `network` - The network used to parse the output.
+    pub fn infer_parsed_async<T>(&self, frame: Vec<u8>, network: Arc<T>) -> InferFuture<Vec<T::Output>>
+    where
+        T: Network + Send + Sync + 'static,
+        T::Output: Send + 'static,
+    {
+        self.run(move |device| {
+            device.write_input(&frame)?;
+            device.read_output(network.as_ref())
+        })
+    }
+
+    /// Runs an inference for every item of an input stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - A stream yielding one frame per input stream for every inference.
+    /// * `max_in_flight` - Maximum number of inferences queued to the worker at once.
+    ///
+    /// # Returns
+    ///
+    /// Returns a stream of raw outputs, in the order of the inputs.
+    pub fn infer_stream<S>(&self, inputs: S, max_in_flight: usize) -> InferStream<'_, S>
+    where
+        S: Stream<Item = Vec<Vec<u8>>> + Unpin,
+    {
+        InferStream {
+            device: self,
+            inputs: Some(inputs),
+            in_flight: VecDeque::new(),
+            max_in_flight: max_in_flight.max(1),
+        }
+    }
+
+    /// Waits for all queued requests and returns the device.
+    pub fn into_inner(mut self) -> HailoDevice {
+        self.shutdown().expect("Inference worker thread panicked")
+    }
+
+    /// Closes the request queue and joins the worker thread.
+    fn shutdown(&mut self) -> Option<HailoDevice> {
+        self.jobs.take();
+        self.worker.take().and_then(|worker| worker.join().ok())
+    }
+}
+
+impl Drop for AsyncDevice {
+    /// Waits for queued requests, then releases the device.
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+impl HailoDevice {
+    /// Moves the device onto a dedicated worker thread for async inference.
+    pub fn into_async(self) -> AsyncDevice {
+        AsyncDevice::new(self)
+    }
+}
+
+/// Result slot shared by a future and the job that completes it.
+struct Slot<R> {
+    result: Option<Result<R>>,
+    waker: Option<Waker>,
+}
+
+impl<R> Default for Slot<R> {
+    fn default() -> Self {
+        Self { result: None, waker: None }
+    }
+}
+
+/// Completes a slot exactly once, with `WorkerStopped` if the job is dropped without running.
+struct Completer<R> {
+    slot: Option<Arc<Mutex<Slot<R>>>>,
+}
+
+impl<R> Completer<R> {
+    fn complete(mut self, result: Result<R>) {
+        if let Some(slot) = self.slot.take() {
+            fill_slot(&slot, result);
+        }
+    }
+}
+
+impl<R> Drop for Completer<R> {
+    fn drop(&mut self) {
+        if let Some(slot) = self.slot.take() {
+            fill_slot(&slot, Err(HailorsError::WorkerStopped));
+        }
+    }
+}
+
+fn fill_slot<R>(slot: &Mutex<Slot<R>>, result: Result<R>) {
+    let waker = {
+        let mut slot = slot.lock().unwrap();
+        slot.result = Some(result);
+        slot.waker.take()
+    };
+    if let Some(waker) = waker {
+        waker.wake();
+    }
+}
+
+/// A future resolving to the result of a request queued on an `AsyncDevice`.
+pub struct InferFuture<R> {
+    slot: Arc<Mutex<Slot<R>>>,
+}
+
+impl<R> Future for InferFuture<R> {
+    type Output = Result<R>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = self.slot.lock().unwrap();
+        match slot.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// A stream of inference results created by `AsyncDevice::infer_stream`.
+pub struct InferStream<'a, S> {
+    device: &'a AsyncDevice,
+    /// The input stream, until it is exhausted.
+    inputs: Option<S>,
+    /// Queued inferences, oldest first.
+    in_flight: VecDeque<InferFuture<Vec<Vec<u8>>>>,
+    max_in_flight: usize,
+}
+
+impl<S> Stream for InferStream<'_, S>
+where
+    S: Stream<Item = Vec<Vec<u8>>> + Unpin,
+{
+    type Item = Result<Vec<Vec<u8>>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        // Keep the worker queue filled while inputs are ready
+        while this.in_flight.len() < this.max_in_flight {
+            let Some(inputs) = this.inputs.as_mut() else {
+                break;
+            };
+            match Pin::new(inputs).poll_next(cx) {
+                Poll::Ready(Some(frames)) => this.in_flight.push_back(this.device.infer_async(frames)),
+                Poll::Ready(None) => this.inputs = None,
+                Poll::Pending => break,
+            }
+        }
+
+        match this.in_flight.front_mut() {
+            Some(future) => match Pin::new(future).poll(cx) {
+                Poll::Ready(result) => {
+                    this.in_flight.pop_front();
+                    Poll::Ready(Some(result))
+                }
+                Poll::Pending => Poll::Pending,
+            },
+            None if this.inputs.is_none() => Poll::Ready(None),
+            None => Poll::Pending,
+        }
+    }
+}
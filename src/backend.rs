@@ -0,0 +1,159 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::Result;
+use crate::vdevice::VDevice;
+use crate::vstream::VStreamInfo;
+
+/// A trait representing an inference backend behind a `HailoDevice`.
+///
+/// A backend owns the configured model and its virtual streams. The default backend talks to
+/// HailoRT through FFI (`HailoRtBackend`), while `MockBackend` runs entirely in Rust so that
+/// applications can be tested without a Hailo accelerator.
+///
+/// Streams are addressed by their index in `inputs()` and `outputs()`. Backends must allow
+/// different streams to be used from different threads at the same time, so that inputs can be
+/// written while outputs are read.
+pub trait InferenceBackend: Send + Sync {
+    /// Returns the input virtual streams of the configured model.
+    fn inputs(&self) -> &[VStreamInfo];
+
+    /// Returns the output virtual streams of the configured model.
+    fn outputs(&self) -> &[VStreamInfo];
+
+    /// Returns the name of the configured network group, if the backend has one.
+    fn network_group(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the virtual device the model runs on, if the backend runs on Hailo hardware.
+    fn vdevice(&self) -> Option<&VDevice> {
+        None
+    }
+
+    /// Activates the network group for manual network group switching.
+    ///
+    /// Backends without network groups, or whose device schedules network groups itself,
+    /// accept this as a no-op.
+    fn activate(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Deactivates the network group activated by `activate`.
+    fn deactivate(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Writes one frame to an input virtual stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Index of the input stream.
+    /// * `frame` - A byte slice of exactly `inputs()[index].frame_size` bytes.
+    fn write_input(&self, index: usize, frame: &[u8]) -> Result<()>;
+
+    /// Blocks until every frame written to an input virtual stream has been sent to the model.
+    ///
+    /// With a batch size above one, a partial batch is only processed once it is flushed.
+    /// Backends without input queues accept this as a no-op.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Index of the input stream.
+    fn flush_input(&self, index: usize) -> Result<()> {
+        let _ = index;
+        Ok(())
+    }
+
+    /// Reads one frame from an output virtual stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Index of the output stream.
+    /// * `buffer` - A byte buffer of exactly `outputs()[index].frame_size` bytes that receives the frame.
+    fn read_output(&self, index: usize, buffer: &mut [u8]) -> Result<()>;
+
+    /// Writes one frame to an input virtual stream, giving up after `timeout`.
+    ///
+    /// On timeout the stream is aborted and resumed, which discards the frames queued in it, and
+    /// `HailorsError::Timeout` is returned. Backends that never block fall back to `write_input`.
+    fn write_input_timeout(&self, index: usize, frame: &[u8], timeout: Duration) -> Result<()> {
+        let _ = timeout;
+        self.write_input(index, frame)
+    }
+
+    /// Reads one frame from an output virtual stream, giving up after `timeout`.
+    ///
+    /// On timeout the stream is aborted and resumed, which discards the frames queued in it, and
+    /// `HailorsError::Timeout` is returned. Backends that never block fall back to `read_output`.
+    fn read_output_timeout(&self, index: usize, buffer: &mut [u8], timeout: Duration) -> Result<()> {
+        let _ = timeout;
+        self.read_output(index, buffer)
+    }
+
+    /// Aborts every virtual stream, so that blocked and later writes and reads fail with
+    /// `HailorsError::StreamAborted` until `resume` is called.
+    ///
+    /// Must not wait for a blocked write or read, since it is meant to be called from another
+    /// thread to unblock them.
+    fn abort(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Resumes the virtual streams after `abort`. Frames queued before the abort are discarded.
+    fn resume(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Aborts and resumes the streams of a `HailoDevice` from any thread.
+///
+/// The handle stays valid while the device is used elsewhere, such as inside a `Pipeline`, so a
+/// watchdog can unblock a hung write or read: `abort` makes them fail with
+/// `HailorsError::StreamAborted`, and once the pipeline has been shut down, `resume` makes the
+/// device usable again.
+///
+/// # Example
+///
+/// ```rust
+/// use hailors::{HailoDevice, HailorsError, MockBackend, VStreamShape};
+///
+/// let mock = MockBackend::new()
+///     .with_input("model/input_layer1", VStreamShape::new(2, 2, 3))
+///     .with_output("model/output_layer1", VStreamShape::new(1, 1, 4));
+/// let device = HailoDevice::with_backend(mock);
+/// let handle = device.abort_handle();
+///
+/// handle.abort().unwrap();
+/// assert!(matches!(device.read_outputs(), Err(HailorsError::StreamAborted { .. })));
+/// handle.resume().unwrap();
+/// assert!(device.read_outputs().is_ok());
+/// ```
+#[derive(Clone)]
+pub struct AbortHandle {
+    backend: Arc<dyn InferenceBackend>,
+}
+
+impl AbortHandle {
+    pub(crate) fn new(backend: Arc<dyn InferenceBackend>) -> Self {
+        Self { backend }
+    }
+
+    /// Aborts every virtual stream of the device. See `InferenceBackend::abort`.
+    pub fn abort(&self) -> Result<()> {
+        self.backend.abort()
+    }
+
+    /// Resumes the virtual streams after `abort`. See `InferenceBackend::resume`.
+    pub fn resume(&self) -> Result<()> {
+        self.backend.resume()
+    }
+}
+
+impl std::fmt::Debug for AbortHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AbortHandle")
+            .field("network_group", &self.backend.network_group())
+            .finish()
+    }
+}
@@ -0,0 +1,380 @@
+//! Throughput and latency benchmarking.
+//!
+//! A benchmark writes frames on one thread while reading outputs on another, like
+//! `HailoDevice::infer_batch`, and measures:
+//!
+//! - throughput in frames per second over the timed run,
+//! - latency from the start of a frame's write until all of its outputs have been read,
+//! - host-side preprocessing time spent producing each frame's inputs,
+//! - host-side postprocessing time spent on each frame's outputs,
+//! - queue occupancy, the number of frames written but not yet read, sampled at every write.
+//!
+//! Benchmarks run against any backend, so a `MockBackend` with a simulated latency measures the
+//! host side of an application without a Hailo device.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::buffer;
+use crate::error::{HailorsError, Result};
+use crate::HailoDevice;
+
+/// Number of frames a benchmark runs when neither a frame count nor a duration is set.
+pub const DEFAULT_FRAMES: u64 = 1000;
+
+/// Parameters of a benchmark run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Number of timed frames; the run ends at whichever of `frames` and `duration` comes first.
+    pub frames: Option<u64>,
+    /// Length of the timed run.
+    pub duration: Option<Duration>,
+    /// Frames inferred one at a time before the timed run, to settle clocks and caches.
+    pub warmup_frames: u32,
+    /// Maximum number of frames written but not yet read; at least 2, one being read while the
+    /// next is written.
+    pub queue_depth: usize,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            frames: None,
+            duration: None,
+            warmup_frames: 10,
+            queue_depth: 4,
+        }
+    }
+}
+
+impl BenchConfig {
+    /// Creates the default parameters, which run `DEFAULT_FRAMES` frames.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of timed frames.
+    pub fn with_frames(mut self, frames: u64) -> Self {
+        self.frames = Some(frames);
+        self
+    }
+
+    /// Sets the length of the timed run.
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Sets the number of warmup frames.
+    pub fn with_warmup_frames(mut self, warmup_frames: u32) -> Self {
+        self.warmup_frames = warmup_frames;
+        self
+    }
+
+    /// Sets the maximum number of frames in flight.
+    pub fn with_queue_depth(mut self, queue_depth: usize) -> Self {
+        self.queue_depth = queue_depth;
+        self
+    }
+}
+
+/// Distribution of a per-frame timing, in milliseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct TimingStats {
+    /// Shortest time.
+    pub min_ms: f64,
+    /// Mean time.
+    pub mean_ms: f64,
+    /// Median time.
+    pub p50_ms: f64,
+    /// 90th percentile.
+    pub p90_ms: f64,
+    /// 99th percentile.
+    pub p99_ms: f64,
+    /// Longest time.
+    pub max_ms: f64,
+}
+
+impl TimingStats {
+    /// Computes the distribution of a set of timings; all fields are zero if there are none.
+    pub fn from_durations(durations: &[Duration]) -> Self {
+        if durations.is_empty() {
+            return Self::default();
+        }
+        let mut millis: Vec<f64> = durations.iter().map(|duration| duration.as_secs_f64() * 1000.0).collect();
+        millis.sort_by(f64::total_cmp);
+
+        // Nearest-rank percentile
+        let percentile = |p: f64| {
+            let rank = (p / 100.0 * millis.len() as f64).ceil() as usize;
+            millis[rank.clamp(1, millis.len()) - 1]
+        };
+        Self {
+            min_ms: millis[0],
+            mean_ms: millis.iter().sum::<f64>() / millis.len() as f64,
+            p50_ms: percentile(50.0),
+            p90_ms: percentile(90.0),
+            p99_ms: percentile(99.0),
+            max_ms: millis[millis.len() - 1],
+        }
+    }
+}
+
+/// Number of frames written but not yet read, sampled at every write.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct QueueOccupancy {
+    /// Maximum number of frames in flight allowed by the benchmark.
+    pub capacity: usize,
+    /// Mean number of frames in flight.
+    pub mean: f64,
+    /// Highest number of frames in flight.
+    pub max: usize,
+}
+
+/// Results of a benchmark run.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BenchReport {
+    /// Name of the benchmarked network group, if the backend has one.
+    pub network_group: Option<String>,
+    /// Number of timed frames.
+    pub frames: u64,
+    /// Length of the timed run, in seconds.
+    pub elapsed_secs: f64,
+    /// Throughput, in frames per second.
+    pub fps: f64,
+    /// Time from the start of a frame's write until all its outputs have been read.
+    pub latency: TimingStats,
+    /// Host-side time spent producing each frame's inputs.
+    pub preprocess: TimingStats,
+    /// Host-side time spent on each frame's outputs.
+    pub postprocess: TimingStats,
+    /// Frames in flight between writing and reading.
+    pub queue: QueueOccupancy,
+}
+
+impl std::fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(network_group) = &self.network_group {
+            writeln!(f, "Network group: {}", network_group)?;
+        }
+        writeln!(f, "Frames:        {} in {:.2} s", self.frames, self.elapsed_secs)?;
+        writeln!(f, "Throughput:    {:.1} FPS", self.fps)?;
+        for (name, stats) in [
+            ("Latency:    ", &self.latency),
+            ("Preprocess: ", &self.preprocess),
+            ("Postprocess:", &self.postprocess),
+        ] {
+            writeln!(
+                f,
+                "{}   p50 {:.2} ms, p90 {:.2} ms, p99 {:.2} ms (min {:.2}, mean {:.2}, max {:.2})",
+                name, stats.p50_ms, stats.p90_ms, stats.p99_ms, stats.min_ms, stats.mean_ms, stats.max_ms
+            )?;
+        }
+        write!(
+            f,
+            "Queue:         {:.1} mean, {} max of {} frames in flight",
+            self.queue.mean, self.queue.max, self.queue.capacity
+        )
+    }
+}
+
+/// Produces the input frames of the frame with the given index, one per input stream.
+type Preprocess<'a> = Box<dyn FnMut(u64) -> Vec<Vec<u8>> + Send + 'a>;
+
+/// Processes the raw outputs of one frame.
+type Postprocess<'a> = Box<dyn FnMut(&[Vec<u8>]) + 'a>;
+
+/// A benchmark with optional host-side preprocessing and postprocessing steps.
+///
+/// Without custom steps, preprocessing allocates zero-filled input frames and postprocessing
+/// dequantizes every output to FLOAT32, as `HailoDevice::read_output` does before parsing.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use hailors::{bench::{Bench, BenchConfig}, HailoDevice, MockBackend, VStreamShape};
+///
+/// let mock = MockBackend::new()
+///     .with_input("model/input_layer1", VStreamShape::new(2, 2, 3))
+///     .with_output("model/output_layer1", VStreamShape::new(1, 1, 4))
+///     .with_latency(Duration::from_micros(100))
+///     .without_input_recording();
+/// let device = HailoDevice::with_backend(mock);
+///
+/// let report = Bench::new(BenchConfig::new().with_frames(50)).run(&device).unwrap();
+/// assert_eq!(report.frames, 50);
+/// println!("{:.1} FPS", report.fps);
+/// ```
+pub struct Bench<'a> {
+    config: BenchConfig,
+    preprocess: Option<Preprocess<'a>>,
+    postprocess: Option<Postprocess<'a>>,
+}
+
+impl<'a> Bench<'a> {
+    /// Creates a benchmark with the given parameters and the default steps.
+    pub fn new(config: BenchConfig) -> Self {
+        Self {
+            config,
+            preprocess: None,
+            postprocess: None,
+        }
+    }
+
+    /// Sets the preprocessing step, which produces the input frames for a frame index.
+    ///
+    /// It runs on the writer thread and its time is reported as `BenchReport::preprocess`.
+    pub fn with_preprocess<F>(mut self, preprocess: F) -> Self
+    where
+        F: FnMut(u64) -> Vec<Vec<u8>> + Send + 'a,
+    {
+        self.preprocess = Some(Box::new(preprocess));
+        self
+    }
+
+    /// Sets the postprocessing step, which receives the raw outputs of every frame.
+    ///
+    /// It runs on the reader thread and its time is reported as `BenchReport::postprocess`.
+    pub fn with_postprocess<F>(mut self, postprocess: F) -> Self
+    where
+        F: FnMut(&[Vec<u8>]) + 'a,
+    {
+        self.postprocess = Some(Box::new(postprocess));
+        self
+    }
+
+    /// Runs the benchmark on a device.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from writing, reading or a frame of the wrong size.
+    pub fn run(&mut self, device: &HailoDevice) -> Result<BenchReport> {
+        let input_sizes: Vec<usize> = device.inputs().iter().map(|info| info.frame_size).collect();
+        let mut default_preprocess = |_| input_sizes.iter().map(|&size| vec![0u8; size]).collect();
+        let preprocess: &mut (dyn FnMut(u64) -> Vec<Vec<u8>> + Send) = match self.preprocess.as_mut() {
+            Some(preprocess) => preprocess.as_mut(),
+            None => &mut default_preprocess,
+        };
+        let mut values = Vec::new();
+        let mut default_postprocess = |outputs: &[Vec<u8>]| {
+            for (info, frame) in device.outputs().iter().zip(outputs) {
+                buffer::dequantize_into(info, frame, &mut values);
+            }
+        };
+        let postprocess: &mut dyn FnMut(&[Vec<u8>]) = match self.postprocess.as_mut() {
+            Some(postprocess) => postprocess.as_mut(),
+            None => &mut default_postprocess,
+        };
+
+        for index in 0..self.config.warmup_frames {
+            let frames = preprocess(index as u64);
+            let frames: Vec<&[u8]> = frames.iter().map(Vec::as_slice).collect();
+            postprocess(&device.infer(&frames)?);
+        }
+
+        let frame_limit = match (self.config.frames, self.config.duration) {
+            (None, None) => Some(DEFAULT_FRAMES),
+            (frames, _) => frames,
+        };
+        let duration = self.config.duration;
+        let queue_depth = self.config.queue_depth.max(2);
+        let failed = &AtomicBool::new(false);
+        let in_flight = &AtomicUsize::new(0);
+
+        let start = Instant::now();
+        let (latencies, postprocess_times, preprocess_times, occupancy) = std::thread::scope(|scope| {
+            // The writer hands the start time of every written frame to the reader. One frame is
+            // being read and one waits to be handed over, so the channel holds the rest of the
+            // queue depth
+            let (written, to_read) = mpsc::sync_channel::<Instant>(queue_depth - 2);
+            let writer = scope.spawn(move || -> Result<(Vec<Duration>, Vec<usize>)> {
+                let mut preprocess_times = Vec::new();
+                let mut occupancy = Vec::new();
+                let mut index = 0;
+                while frame_limit.is_none_or(|limit| index < limit)
+                    && duration.is_none_or(|duration| start.elapsed() < duration)
+                    && !failed.load(Ordering::Relaxed)
+                {
+                    let preprocess_start = Instant::now();
+                    let frames = preprocess(index);
+                    preprocess_times.push(preprocess_start.elapsed());
+
+                    let frame_start = Instant::now();
+                    let frames: Vec<&[u8]> = frames.iter().map(Vec::as_slice).collect();
+                    if let Err(err) = device.write_inputs(&frames) {
+                        failed.store(true, Ordering::Relaxed);
+                        return Err(err);
+                    }
+                    occupancy.push(in_flight.fetch_add(1, Ordering::Relaxed) + 1);
+                    if written.send(frame_start).is_err() {
+                        break;
+                    }
+                    index += 1;
+                }
+                // Push out a partial batch so the reader can finish
+                if !failed.load(Ordering::Relaxed) {
+                    device.flush_inputs()?;
+                }
+                Ok((preprocess_times, occupancy))
+            });
+
+            let mut latencies = Vec::new();
+            let mut postprocess_times = Vec::new();
+            let read_result: Result<()> = to_read.iter().try_for_each(|frame_start| {
+                let outputs = device.read_outputs()?;
+                in_flight.fetch_sub(1, Ordering::Relaxed);
+                latencies.push(frame_start.elapsed());
+
+                let postprocess_start = Instant::now();
+                postprocess(&outputs);
+                postprocess_times.push(postprocess_start.elapsed());
+                Ok(())
+            });
+            if read_result.is_err() {
+                failed.store(true, Ordering::Relaxed);
+            }
+            drop(to_read);
+
+            // A read error is the cause of any write error that follows it
+            let writer_result = writer.join().expect("Benchmark writer thread panicked");
+            read_result?;
+            let (preprocess_times, occupancy) = writer_result?;
+            Ok::<_, HailorsError>((latencies, postprocess_times, preprocess_times, occupancy))
+        })?;
+        let elapsed = start.elapsed();
+
+        let frames = latencies.len() as u64;
+        Ok(BenchReport {
+            network_group: device.network_group().map(str::to_string),
+            frames,
+            elapsed_secs: elapsed.as_secs_f64(),
+            fps: frames as f64 / elapsed.as_secs_f64(),
+            latency: TimingStats::from_durations(&latencies),
+            preprocess: TimingStats::from_durations(&preprocess_times),
+            postprocess: TimingStats::from_durations(&postprocess_times),
+            queue: QueueOccupancy {
+                capacity: queue_depth,
+                mean: if occupancy.is_empty() {
+                    0.0
+                } else {
+                    occupancy.iter().sum::<usize>() as f64 / occupancy.len() as f64
+                },
+                max: occupancy.iter().copied().max().unwrap_or(0),
+            },
+        })
+    }
+}
+
+/// Runs a benchmark with the default preprocessing and postprocessing steps.
+///
+/// # Arguments
+///
+/// * `device` - The device to benchmark.
+/// * `config` - Frame count, duration, warmup and queue depth.
+pub fn run(device: &HailoDevice, config: &BenchConfig) -> Result<BenchReport> {
+    Bench::new(config.clone()).run(device)
+}
@@ -0,0 +1,222 @@
+use std::alloc::{self, Layout};
+use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
+use std::sync::{Arc, Mutex};
+
+use crate::quant;
+use crate::vstream::VStreamInfo;
+
+/// Alignment of `AlignedBuffer` in bytes, the page size.
+pub const BUFFER_ALIGNMENT: usize = 4096;
+
+/// A zero-initialized byte buffer aligned to `BUFFER_ALIGNMENT`.
+///
+/// The vstream reads and writes used by this crate copy frames through HailoRT's own pipeline
+/// buffers, so they accept any buffer. The page alignment only matters for paths that map user
+/// buffers for DMA, such as HailoRT's async stream API, which this crate does not use yet.
+pub struct AlignedBuffer {
+    ptr: NonNull<u8>,
+    len: usize,
+}
+
+// SAFETY: the buffer exclusively owns its allocation, like a `Vec<u8>`.
+unsafe impl Send for AlignedBuffer {}
+
+// SAFETY: shared access only hands out `&[u8]`.
+unsafe impl Sync for AlignedBuffer {}
+
+impl AlignedBuffer {
+    /// Allocates a zero-filled buffer of `len` bytes.
+    pub fn zeroed(len: usize) -> Self {
+        let layout = Self::layout(len);
+        // SAFETY: the layout has a non-zero size
+        let ptr = unsafe { alloc::alloc_zeroed(layout) };
+        let ptr = NonNull::new(ptr).unwrap_or_else(|| alloc::handle_alloc_error(layout));
+        Self { ptr, len }
+    }
+
+    /// Layout of a buffer of `len` bytes; empty buffers still allocate one byte so that the
+    /// pointer is always aligned and owned.
+    fn layout(len: usize) -> Layout {
+        Layout::from_size_align(len.max(1), BUFFER_ALIGNMENT).expect("Buffer size overflows the address space")
+    }
+}
+
+impl Deref for AlignedBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: the allocation holds `len` initialized bytes
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl DerefMut for AlignedBuffer {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        // SAFETY: the allocation holds `len` initialized bytes and `&mut self` is exclusive
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl Clone for AlignedBuffer {
+    fn clone(&self) -> Self {
+        let mut buffer = Self::zeroed(self.len);
+        buffer.copy_from_slice(self);
+        buffer
+    }
+}
+
+impl Drop for AlignedBuffer {
+    fn drop(&mut self) {
+        // SAFETY: the pointer was allocated in `zeroed` with the same layout
+        unsafe { alloc::dealloc(self.ptr.as_ptr(), Self::layout(self.len)) }
+    }
+}
+
+impl std::fmt::Debug for AlignedBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AlignedBuffer").field("len", &self.len).finish()
+    }
+}
+
+/// Caller-owned buffers for one frame of every output stream.
+///
+/// Each output stream gets an aligned buffer for the raw frame and a buffer for its dequantized
+/// FLOAT32 values, both sized once from the stream information. Reading into the same
+/// `OutputBuffers` frame after frame with `HailoDevice::read_output_into` therefore performs no
+/// heap allocation.
+#[derive(Debug, Clone)]
+pub struct OutputBuffers {
+    frames: Vec<AlignedBuffer>,
+    values: Vec<Vec<f32>>,
+}
+
+impl OutputBuffers {
+    /// Allocates buffers for the given output streams, usually `HailoDevice::outputs()`.
+    pub fn new(outputs: &[VStreamInfo]) -> Self {
+        Self {
+            frames: outputs.iter().map(|info| AlignedBuffer::zeroed(info.frame_size)).collect(),
+            values: outputs
+                .iter()
+                .map(|info| Vec::with_capacity(info.frame_size / info.format.format_type.element_size()))
+                .collect(),
+        }
+    }
+
+    /// Returns the number of output streams the buffers hold frames for.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns `true` if the buffers hold no output streams.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Returns the last raw frame read from the output stream at `index`.
+    pub fn frame(&self, index: usize) -> &[u8] {
+        &self.frames[index]
+    }
+
+    /// Returns the dequantized values of the last frame parsed from the output stream at `index`.
+    pub fn values(&self, index: usize) -> &[f32] {
+        &self.values[index]
+    }
+
+    /// Returns the raw frame buffer of the output stream at `index`, if there is one.
+    pub(crate) fn frame_mut(&mut self, index: usize) -> Option<&mut AlignedBuffer> {
+        self.frames.get_mut(index)
+    }
+
+    /// Dequantizes the raw frame of the output stream at `index` into its value buffer.
+    pub(crate) fn dequantize(&mut self, index: usize, info: &VStreamInfo) -> &[f32] {
+        dequantize_into(info, &self.frames[index], &mut self.values[index]);
+        &self.values[index]
+    }
+}
+
+/// A thread-safe pool of `OutputBuffers` for the same set of output streams.
+///
+/// Threads that process frames concurrently, such as a reader handing frames to a worker pool,
+/// take buffers with `acquire` and return them by dropping the guard. Once the pool holds as many
+/// buffers as are in flight at once, inference runs without allocating. Clones share the pool.
+///
+/// # Example
+///
+/// ```rust
+/// use hailors::{BufferPool, HailoDevice, MockBackend, VStreamShape};
+///
+/// let mock = MockBackend::new()
+///     .with_input("model/input_layer1", VStreamShape::new(2, 2, 3))
+///     .with_output("model/output_layer1", VStreamShape::new(1, 1, 4));
+/// let device = HailoDevice::with_backend(mock);
+///
+/// let pool = BufferPool::new(device.outputs(), 2);
+/// let mut buffers = pool.acquire();
+/// device.read_outputs_into(&mut buffers).unwrap();
+/// assert_eq!(buffers.frame(0).len(), 16);
+/// ```
+#[derive(Clone)]
+pub struct BufferPool {
+    outputs: Arc<[VStreamInfo]>,
+    free: Arc<Mutex<Vec<OutputBuffers>>>,
+}
+
+impl BufferPool {
+    /// Creates a pool for the given output streams with `capacity` preallocated buffer sets.
+    pub fn new(outputs: &[VStreamInfo], capacity: usize) -> Self {
+        let mut free = Vec::with_capacity(capacity);
+        free.extend((0..capacity).map(|_| OutputBuffers::new(outputs)));
+        Self {
+            outputs: outputs.into(),
+            free: Arc::new(Mutex::new(free)),
+        }
+    }
+
+    /// Takes a set of buffers from the pool, allocating a new set if none is free.
+    pub fn acquire(&self) -> PooledOutputBuffers {
+        let buffers = self.free.lock().unwrap_or_else(|e| e.into_inner()).pop();
+        PooledOutputBuffers {
+            buffers: Some(buffers.unwrap_or_else(|| OutputBuffers::new(&self.outputs))),
+            free: Arc::clone(&self.free),
+        }
+    }
+
+    /// Returns the number of buffer sets waiting in the pool.
+    pub fn available(&self) -> usize {
+        self.free.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+}
+
+/// A set of `OutputBuffers` borrowed from a `BufferPool`, returned to the pool when dropped.
+pub struct PooledOutputBuffers {
+    buffers: Option<OutputBuffers>,
+    free: Arc<Mutex<Vec<OutputBuffers>>>,
+}
+
+impl Deref for PooledOutputBuffers {
+    type Target = OutputBuffers;
+
+    fn deref(&self) -> &OutputBuffers {
+        self.buffers.as_ref().expect("Pooled buffers are only taken on drop")
+    }
+}
+
+impl DerefMut for PooledOutputBuffers {
+    fn deref_mut(&mut self) -> &mut OutputBuffers {
+        self.buffers.as_mut().expect("Pooled buffers are only taken on drop")
+    }
+}
+
+impl Drop for PooledOutputBuffers {
+    fn drop(&mut self) {
+        if let Some(buffers) = self.buffers.take() {
+            self.free.lock().unwrap_or_else(|e| e.into_inner()).push(buffers);
+        }
+    }
+}
+
+/// Converts a raw output frame into FLOAT32 values, replacing the contents of `values`.
+pub(crate) fn dequantize_into(info: &VStreamInfo, frame: &[u8], values: &mut Vec<f32>) {
+    quant::dequantize(info.format.format_type, info.quant, frame, values)
+}
@@ -0,0 +1,202 @@
+//! Pre-flight checks that a HEF can run on a device.
+//!
+//! HailoRT reports a HEF compiled for another architecture, or by a newer Dataflow Compiler, as a
+//! generic configure failure. Before configuring, `HailoRtBackend::configure` reads the HEF with
+//! the pure-Rust `hef` module and compares it with the devices of the virtual device, so an
+//! architecture mistake surfaces as `HailorsError::ArchitectureMismatch` naming the HEF that is
+//! needed instead.
+//!
+//! The check only rejects combinations known to fail. The pairing of Dataflow Compiler and
+//! HailoRT versions is inferred from their release numbering, so a HEF that looks too new for the
+//! installed HailoRT is only logged as a warning. A HEF the inspector cannot read, or an
+//! architecture this crate does not know, is left for HailoRT to judge. The check can be turned
+//! off with `DeviceConfig::with_compatibility_check`.
+
+use crate::device::DeviceArchitecture;
+use crate::error::{check_status, HailorsError, Operation, Result};
+use crate::ffi;
+use crate::hef::{Hef, HefArchitecture};
+use crate::vdevice::VDevice;
+
+/// Version of the HailoRT library.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HailoRtVersion {
+    /// Major version.
+    pub major: u32,
+    /// Minor version.
+    pub minor: u32,
+    /// Revision.
+    pub revision: u32,
+}
+
+impl HailoRtVersion {
+    /// Returns the newest Dataflow Compiler version, as `(major, minor)`, whose HEFs this HailoRT
+    /// version loads, or `None` if the pairing is unknown.
+    ///
+    /// HailoRT 4.x is released together with Dataflow Compiler 3.(x + 10), e.g. HailoRT 4.19 with
+    /// Dataflow Compiler 3.29.
+    pub fn newest_compiler(&self) -> Option<(u32, u32)> {
+        (self.major == 4).then_some((3, self.minor + 10))
+    }
+}
+
+impl std::fmt::Display for HailoRtVersion {
+    /// Formats the version as `major.minor.revision`, e.g. `4.19.0`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.revision)
+    }
+}
+
+/// Returns the version of the HailoRT library hailors is linked against.
+pub fn hailort_version() -> Result<HailoRtVersion> {
+    let mut version = HailoRtVersion {
+        major: 0,
+        minor: 0,
+        revision: 0,
+    };
+    let status =
+        unsafe { ffi::hailors_get_library_version(&mut version.major, &mut version.minor, &mut version.revision) };
+    check_status(status, Operation::GetLibraryVersion)?;
+    Ok(version)
+}
+
+impl HefArchitecture {
+    /// Returns whether a HEF compiled for this architecture runs on a device of the given
+    /// architecture.
+    ///
+    /// Hailo-8 devices also run HEFs compiled for Hailo-8P, Hailo-8R and, at lower throughput,
+    /// Hailo-8L, and Hailo-10H devices run Hailo-15H HEFs. Unknown architectures are assumed to
+    /// be compatible.
+    pub fn runs_on(&self, device: DeviceArchitecture) -> bool {
+        if let HefArchitecture::Unknown(_) = self {
+            return true;
+        }
+        match device {
+            DeviceArchitecture::Hailo8A0 | DeviceArchitecture::Hailo8 => matches!(
+                self,
+                HefArchitecture::Hailo8 | HefArchitecture::Hailo8P | HefArchitecture::Hailo8R | HefArchitecture::Hailo8L
+            ),
+            DeviceArchitecture::Hailo8L => *self == HefArchitecture::Hailo8L,
+            DeviceArchitecture::Hailo15H => *self == HefArchitecture::Hailo15H,
+            DeviceArchitecture::Hailo15M => *self == HefArchitecture::Hailo15M,
+            DeviceArchitecture::Hailo10H => matches!(self, HefArchitecture::Hailo10H | HefArchitecture::Hailo15H),
+            DeviceArchitecture::Hailo15L | DeviceArchitecture::Unknown(_) => true,
+        }
+    }
+
+    /// Returns the architecture to compile HEFs for to run on a device of the given architecture.
+    pub fn for_device(device: DeviceArchitecture) -> Option<Self> {
+        match device {
+            DeviceArchitecture::Hailo8A0 | DeviceArchitecture::Hailo8 => Some(HefArchitecture::Hailo8),
+            DeviceArchitecture::Hailo8L => Some(HefArchitecture::Hailo8L),
+            DeviceArchitecture::Hailo15H => Some(HefArchitecture::Hailo15H),
+            DeviceArchitecture::Hailo15M => Some(HefArchitecture::Hailo15M),
+            DeviceArchitecture::Hailo10H => Some(HefArchitecture::Hailo10H),
+            DeviceArchitecture::Hailo15L | DeviceArchitecture::Unknown(_) => None,
+        }
+    }
+}
+
+impl Hef {
+    /// Checks that the HEF runs on a device of the given architecture.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HailorsError::ArchitectureMismatch` if it does not.
+    pub fn check_architecture(&self, device_id: &str, device: DeviceArchitecture) -> Result<()> {
+        if !self.architecture.runs_on(device) {
+            return Err(HailorsError::ArchitectureMismatch {
+                hef_architecture: self.architecture,
+                device_id: device_id.to_string(),
+                device_architecture: device,
+                hef_path: None,
+            });
+        }
+        if self.architecture == HefArchitecture::Hailo8L && device != DeviceArchitecture::Hailo8L {
+            tracing::warn!(
+                device_id,
+                "HEF is compiled for HAILO8L; a HAILO8 HEF of the model runs faster on this device"
+            );
+        }
+        Ok(())
+    }
+
+    /// Checks that the given HailoRT version loads HEFs from the Dataflow Compiler that compiled
+    /// this HEF.
+    ///
+    /// HEFs without a recorded compiler version, or with a version scheme this crate does not
+    /// know, pass the check. Since `HailoRtVersion::newest_compiler` is inferred from release
+    /// numbering, `VDevice::check_hef` only logs a warning when this check fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HailorsError::UnsupportedHefVersion` if the HEF is too new for HailoRT.
+    pub fn check_hailort_version(&self, version: HailoRtVersion) -> Result<()> {
+        let compiler = self.sdk_version.as_deref().and_then(|sdk_version| {
+            let mut parts = sdk_version.split('.').map(|part| part.parse::<u32>().ok());
+            Some((parts.next()??, parts.next()??))
+        });
+        match (compiler, version.newest_compiler()) {
+            (Some((major, minor)), Some(newest)) if major == newest.0 && minor > newest.1 => {
+                Err(HailorsError::UnsupportedHefVersion {
+                    sdk_version: self.sdk_version.clone().unwrap_or_default(),
+                    hailort_version: version,
+                    hef_path: None,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl VDevice {
+    /// Checks that a HEF can run on every device of this virtual device with the installed
+    /// HailoRT.
+    ///
+    /// `HailoDevice::with_config` and `VDevice::configure` run this check before configuring,
+    /// unless it is turned off with `DeviceConfig::with_compatibility_check`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HailorsError::ArchitectureMismatch` if the HEF is compiled for another device. A
+    /// HEF that seems too new for the installed HailoRT is logged as a warning, and a HEF that
+    /// cannot be read passes, so that HailoRT reports the problem.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hailors::VDevice;
+    ///
+    /// let vdevice = VDevice::new().expect("Failed to create VDevice");
+    /// if let Err(err) = vdevice.check_hef("./hef/yolov8s_h8.hef") {
+    ///     eprintln!("{}", err);
+    /// }
+    /// ```
+    pub fn check_hef(&self, hef_path: &str) -> Result<()> {
+        let hef = match Hef::open(hef_path) {
+            Ok(hef) => hef,
+            Err(err) => {
+                tracing::debug!(hef_path, error = %err, "Skipping the compatibility check of an unreadable HEF");
+                return Ok(());
+            }
+        };
+
+        if let Ok(version) = hailort_version() {
+            if let Err(err) = hef.check_hailort_version(version) {
+                tracing::warn!(error = %err.with_hef_path(hef_path), "HEF may be too new for the installed HailoRT");
+            }
+        }
+        let devices = match self.device_info() {
+            Ok(devices) => devices,
+            Err(err) => {
+                tracing::debug!(error = %err, "Skipping the architecture check of unidentified devices");
+                return Ok(());
+            }
+        };
+        for device in devices {
+            hef.check_architecture(&device.device_id, device.architecture)
+                .map_err(|err| err.with_hef_path(hef_path))?;
+        }
+        Ok(())
+    }
+}
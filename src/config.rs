@@ -0,0 +1,184 @@
+use std::time::Duration;
+
+use crate::vdevice::VDeviceParams;
+use crate::vstream::FormatType;
+
+/// Parameters of one virtual stream, applied when the HEF is configured.
+///
+/// `None` timeouts and queue sizes select the HailoRT defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamConfig {
+    /// Data type of the host-side buffer.
+    pub format_type: FormatType,
+    /// Whether the host-side buffer stays quantized instead of being (de)quantized by HailoRT.
+    pub quantized: bool,
+    /// Timeout of a single read or write.
+    pub timeout: Option<Duration>,
+    /// Depth of the vstream pipeline queue.
+    pub queue_size: Option<u32>,
+}
+
+impl StreamConfig {
+    /// Creates stream parameters with the given format type and HailoRT defaults otherwise.
+    pub fn new(format_type: FormatType) -> Self {
+        Self {
+            format_type,
+            quantized: false,
+            timeout: None,
+            queue_size: None,
+        }
+    }
+
+    /// Sets whether the host-side buffer stays quantized.
+    pub fn with_quantized(mut self, quantized: bool) -> Self {
+        self.quantized = quantized;
+        self
+    }
+
+    /// Sets the timeout of a single read or write.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the depth of the vstream pipeline queue.
+    pub fn with_queue_size(mut self, queue_size: u32) -> Self {
+        self.queue_size = Some(queue_size);
+        self
+    }
+}
+
+/// Configuration applied when a `HailoDevice` configures its HEF.
+///
+/// The defaults match `HailoDevice::new`: inputs use the format HailoRT picks for the model and
+/// outputs are dequantized to FLOAT32. Direction-wide parameters apply to every stream unless a
+/// stream is given its own parameters by name.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use hailors::{DeviceConfig, FormatType, StreamConfig};
+///
+/// // Keep the raw feature map quantized to save bandwidth, but dequantize the NMS output.
+/// let config = DeviceConfig::new()
+///     .with_input_format(FormatType::Uint8)
+///     .with_output_format(FormatType::Uint8)
+///     .with_output_stream("yolov8s/yolov8_nms_postprocess", StreamConfig::new(FormatType::Float32))
+///     .with_timeout(Duration::from_secs(2))
+///     .with_queue_size(4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceConfig {
+    /// Parameters of every input stream without its own parameters.
+    pub input: StreamConfig,
+    /// Parameters of every output stream without its own parameters.
+    pub output: StreamConfig,
+    /// Parameters of individual input streams, by name.
+    pub input_streams: Vec<(String, StreamConfig)>,
+    /// Parameters of individual output streams, by name.
+    pub output_streams: Vec<(String, StreamConfig)>,
+    /// Number of frames the device processes per batch; `None` lets HailoRT decide.
+    pub batch_size: Option<u16>,
+    /// Network group to configure; `None` selects the first network group in the HEF.
+    pub network_group: Option<String>,
+    /// Parameters of the virtual device created for the network group.
+    pub vdevice: VDeviceParams,
+    /// Whether to check that the HEF matches the devices and HailoRT before configuring it.
+    pub check_compatibility: bool,
+}
+
+impl Default for DeviceConfig {
+    fn default() -> Self {
+        Self {
+            input: StreamConfig::new(FormatType::Auto),
+            output: StreamConfig::new(FormatType::Float32),
+            input_streams: Vec::new(),
+            output_streams: Vec::new(),
+            batch_size: None,
+            network_group: None,
+            vdevice: VDeviceParams::default(),
+            check_compatibility: true,
+        }
+    }
+}
+
+impl DeviceConfig {
+    /// Creates the default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the format type of input streams without their own parameters.
+    pub fn with_input_format(mut self, format_type: FormatType) -> Self {
+        self.input.format_type = format_type;
+        self
+    }
+
+    /// Sets the format type of output streams without their own parameters.
+    pub fn with_output_format(mut self, format_type: FormatType) -> Self {
+        self.output.format_type = format_type;
+        self
+    }
+
+    /// Sets whether output streams without their own parameters stay quantized.
+    pub fn with_quantized_outputs(mut self, quantized: bool) -> Self {
+        self.output.quantized = quantized;
+        self
+    }
+
+    /// Sets the read and write timeout of streams without their own parameters.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.input.timeout = Some(timeout);
+        self.output.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the pipeline queue depth of streams without their own parameters.
+    pub fn with_queue_size(mut self, queue_size: u32) -> Self {
+        self.input.queue_size = Some(queue_size);
+        self.output.queue_size = Some(queue_size);
+        self
+    }
+
+    /// Sets the number of frames the device processes per batch.
+    ///
+    /// Larger batches raise throughput when frames are written and read concurrently, for
+    /// example with `HailoDevice::infer_batch`.
+    pub fn with_batch_size(mut self, batch_size: u16) -> Self {
+        self.batch_size = Some(batch_size);
+        self
+    }
+
+    /// Selects the network group to configure from a HEF holding several network groups.
+    pub fn with_network_group(mut self, name: &str) -> Self {
+        self.network_group = Some(name.to_string());
+        self
+    }
+
+    /// Sets the parameters of the virtual device created for the network group, such as the
+    /// physical devices to run on.
+    pub fn with_vdevice_params(mut self, params: VDeviceParams) -> Self {
+        self.vdevice = params;
+        self
+    }
+
+    /// Sets whether to check that the HEF matches the architecture of the devices and the
+    /// installed HailoRT before configuring it. See `VDevice::check_hef`.
+    pub fn with_compatibility_check(mut self, check: bool) -> Self {
+        self.check_compatibility = check;
+        self
+    }
+
+    /// Gives the input stream with the given name its own parameters.
+    pub fn with_input_stream(mut self, name: &str, config: StreamConfig) -> Self {
+        self.input_streams.push((name.to_string(), config));
+        self
+    }
+
+    /// Gives the output stream with the given name its own parameters.
+    pub fn with_output_stream(mut self, name: &str, config: StreamConfig) -> Self {
+        self.output_streams.push((name.to_string(), config));
+        self
+    }
+}
@@ -0,0 +1,131 @@
+use std::ffi::CStr;
+
+use crate::error::{check_status, Operation, Result};
+use crate::ffi::{self, RawDeviceInfo};
+
+/// Hailo device architecture (`hailo_device_architecture_t`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceArchitecture {
+    /// Hailo-8 A0 revision.
+    Hailo8A0,
+    /// Hailo-8.
+    Hailo8,
+    /// Hailo-8L.
+    Hailo8L,
+    /// Hailo-15H.
+    Hailo15H,
+    /// Hailo-15L.
+    Hailo15L,
+    /// Hailo-15M.
+    Hailo15M,
+    /// Hailo-10H.
+    Hailo10H,
+    /// An architecture this crate does not know about.
+    Unknown(u32),
+}
+
+impl DeviceArchitecture {
+    /// Converts a raw `hailo_device_architecture_t` value into a `DeviceArchitecture`.
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            0 => DeviceArchitecture::Hailo8A0,
+            1 => DeviceArchitecture::Hailo8,
+            2 => DeviceArchitecture::Hailo8L,
+            3 => DeviceArchitecture::Hailo15H,
+            4 => DeviceArchitecture::Hailo15L,
+            5 => DeviceArchitecture::Hailo15M,
+            6 => DeviceArchitecture::Hailo10H,
+            other => DeviceArchitecture::Unknown(other),
+        }
+    }
+}
+
+impl std::fmt::Display for DeviceArchitecture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeviceArchitecture::Hailo8A0 => write!(f, "HAILO8_A0"),
+            DeviceArchitecture::Hailo8 => write!(f, "HAILO8"),
+            DeviceArchitecture::Hailo8L => write!(f, "HAILO8L"),
+            DeviceArchitecture::Hailo15H => write!(f, "HAILO15H"),
+            DeviceArchitecture::Hailo15L => write!(f, "HAILO15L"),
+            DeviceArchitecture::Hailo15M => write!(f, "HAILO15M"),
+            DeviceArchitecture::Hailo10H => write!(f, "HAILO10H"),
+            DeviceArchitecture::Unknown(value) => write!(f, "UNKNOWN({})", value),
+        }
+    }
+}
+
+/// PCIe bus address of a device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PcieAddress {
+    /// PCIe domain.
+    pub domain: u32,
+    /// PCIe bus.
+    pub bus: u32,
+    /// PCIe device.
+    pub device: u32,
+    /// PCIe function.
+    pub func: u32,
+}
+
+impl std::fmt::Display for PcieAddress {
+    /// Formats the address as `domain:bus:device.func`, e.g. `0000:01:00.0`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04x}:{:02x}:{:02x}.{:x}", self.domain, self.bus, self.device, self.func)
+    }
+}
+
+/// A physical Hailo device found by `devices()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhysicalDevice {
+    /// Device ID, as accepted by `VDeviceParams::with_device_ids`.
+    pub id: String,
+    /// PCIe bus address, for PCIe devices.
+    pub pcie: Option<PcieAddress>,
+    /// Device architecture, if the device could be opened to query it.
+    pub architecture: Option<DeviceArchitecture>,
+}
+
+/// Lists the physical Hailo devices attached to this host.
+///
+/// # Returns
+///
+/// Returns every device HailoRT can see, including devices in use by other processes.
+///
+/// # Example
+///
+/// ```no_run
+/// for device in hailors::devices().expect("Failed to scan devices") {
+///     println!("{}", device.id);
+/// }
+/// ```
+pub fn devices() -> Result<Vec<PhysicalDevice>> {
+    let mut raw_devices = vec![RawDeviceInfo::default(); 4];
+    loop {
+        let mut count: usize = 0;
+        let status = unsafe { ffi::hailors_scan_devices(raw_devices.as_mut_ptr(), raw_devices.len(), &mut count) };
+        check_status(status, Operation::ScanDevices)?;
+
+        // Retry with room for every device if more are attached than fit
+        if count > raw_devices.len() {
+            raw_devices = vec![RawDeviceInfo::default(); count];
+            continue;
+        }
+        return Ok(raw_devices[..count].iter().map(physical_device).collect());
+    }
+}
+
+/// Converts a device found by the scan into a `PhysicalDevice`.
+fn physical_device(raw: &RawDeviceInfo) -> PhysicalDevice {
+    let id = unsafe { CStr::from_ptr(raw.id.as_ptr()) };
+    PhysicalDevice {
+        id: id.to_string_lossy().into_owned(),
+        pcie: (raw.is_pcie != 0).then_some(PcieAddress {
+            domain: raw.pcie_domain,
+            bus: raw.pcie_bus,
+            device: raw.pcie_device,
+            func: raw.pcie_func,
+        }),
+        architecture: (raw.has_architecture != 0).then(|| DeviceArchitecture::from_u32(raw.architecture)),
+    }
+}
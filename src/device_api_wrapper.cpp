@@ -1,10 +1,51 @@
 #include "device_api_wrapper.hpp"
 #include <vector>
 #include <thread>
-#include <iostream>
+#include <cstring>
+#include <cstdlib>
+#include <memory>
+#include <new>
+#include <algorithm>
+#include <map>
+#include <string>
+#include <functional>
+#include <mutex>
+#include <condition_variable>
+#include <deque>
+#include <chrono>
+#include <atomic>
 
 using namespace hailort;
 
+static std::atomic<hailors_log_callback> log_callback{nullptr};
+
+extern "C" void hailors_set_log_callback(hailors_log_callback callback) {
+    log_callback.store(callback);
+}
+
+// Passes a diagnostic message to the Rust side, if it has registered a callback
+static void log_message(hailors_log_level level, const std::string& message) {
+    auto callback = log_callback.load();
+    if (callback) {
+        callback(static_cast<int32_t>(level), message.c_str());
+    }
+}
+
+extern "C" hailo_status hailors_get_library_version(uint32_t* major, uint32_t* minor, uint32_t* revision) {
+    if (!major || !minor || !revision) {
+        return HAILO_INVALID_ARGUMENT;
+    }
+    hailo_version_t version;
+    hailo_status status = hailo_get_library_version(&version);
+    if (status != HAILO_SUCCESS) {
+        return status;
+    }
+    *major = version.major;
+    *minor = version.minor;
+    *revision = version.revision;
+    return HAILO_SUCCESS;
+}
+
 extern "C" hailo_status hailors_create_vdevice(hailo_vdevice_handle* vdevice) {
     auto vdevice_result = VDevice::create();
     if (!vdevice_result) {
@@ -14,14 +55,290 @@ extern "C" hailo_status hailors_create_vdevice(hailo_vdevice_handle* vdevice) {
     return HAILO_SUCCESS;
 }
 
+extern "C" hailo_status hailors_create_vdevice_with_params(const hailors_vdevice_params* params, hailo_vdevice_handle* vdevice) {
+    if (!params || !vdevice || (params->device_id_count > 0 && !params->device_ids)) {
+        return HAILO_INVALID_ARGUMENT;
+    }
+    *vdevice = nullptr;
+
+    hailo_vdevice_params_t vdevice_params;
+    auto status = hailo_init_vdevice_params(&vdevice_params);
+    if (status != HAILO_SUCCESS) {
+        return status;
+    }
+
+    // HailoRT only borrows the IDs, so they must stay alive until the VDevice is created
+    std::vector<hailo_device_id_t> device_ids(params->device_id_count);
+    for (size_t i = 0; i < params->device_id_count; i++) {
+        if (!params->device_ids[i]) {
+            return HAILO_INVALID_ARGUMENT;
+        }
+        std::memset(device_ids[i].id, 0, sizeof(device_ids[i].id));
+        std::strncpy(device_ids[i].id, params->device_ids[i], sizeof(device_ids[i].id) - 1);
+    }
+    if (!device_ids.empty()) {
+        vdevice_params.device_ids = device_ids.data();
+        vdevice_params.device_count = static_cast<uint32_t>(device_ids.size());
+    } else if (params->device_count > 0) {
+        vdevice_params.device_count = params->device_count;
+    }
+    vdevice_params.scheduling_algorithm = static_cast<hailo_scheduling_algorithm_t>(params->scheduling_algorithm);
+    if (params->group_id) {
+        vdevice_params.group_id = params->group_id;
+    }
+
+    auto vdevice_result = VDevice::create(vdevice_params);
+    if (!vdevice_result) {
+        return vdevice_result.status();
+    }
+    *vdevice = vdevice_result.value().release();
+    return HAILO_SUCCESS;
+}
+
+extern "C" hailo_status hailors_scan_devices(hailors_device_info* devices, size_t max_count, size_t* count) {
+    if (!count || (max_count > 0 && !devices)) {
+        return HAILO_INVALID_ARGUMENT;
+    }
+    *count = 0;
+
+    auto device_ids = Device::scan();
+    if (!device_ids) {
+        return device_ids.status();
+    }
+
+    // PCIe bus addresses are matched to device IDs by their string form
+    std::vector<std::pair<std::string, hailo_pcie_device_info_t>> pcie_devices;
+    auto pcie_scan = Device::scan_pcie();
+    if (pcie_scan) {
+        for (const auto &pcie_info : pcie_scan.value()) {
+            auto pcie_id = Device::pcie_device_info_to_string(pcie_info);
+            if (pcie_id) {
+                pcie_devices.emplace_back(pcie_id.value(), pcie_info);
+            }
+        }
+    }
+
+    for (size_t i = 0; i < std::min(max_count, device_ids->size()); i++) {
+        const auto &device_id = device_ids.value()[i];
+        auto &info = devices[i];
+        std::memset(&info, 0, sizeof(info));
+        std::strncpy(info.id, device_id.c_str(), sizeof(info.id) - 1);
+
+        for (const auto &pcie_device : pcie_devices) {
+            if (pcie_device.first == device_id) {
+                info.is_pcie = 1;
+                info.pcie_domain = pcie_device.second.domain;
+                info.pcie_bus = pcie_device.second.bus;
+                info.pcie_device = pcie_device.second.device;
+                info.pcie_func = pcie_device.second.func;
+            }
+        }
+
+        // The architecture needs the device opened; a device busy in another process is still listed
+        auto device = Device::create(device_id);
+        if (device) {
+            auto architecture = device.value()->get_architecture();
+            if (architecture) {
+                info.has_architecture = 1;
+                info.architecture = static_cast<uint32_t>(architecture.value());
+            }
+        }
+    }
+    *count = device_ids->size();
+    return HAILO_SUCCESS;
+}
+
+extern "C" hailo_status hailors_open_device(const char* device_id, hailo_device_handle* device) {
+    if (!device_id || !device) {
+        return HAILO_INVALID_ARGUMENT;
+    }
+    *device = nullptr;
+
+    auto device_result = Device::create(device_id);
+    if (!device_result) {
+        return device_result.status();
+    }
+    *device = device_result.value().release();
+    return HAILO_SUCCESS;
+}
+
+extern "C" hailo_status hailors_release_device(hailo_device_handle device) {
+    delete static_cast<Device*>(device);
+    return HAILO_SUCCESS;
+}
+
+extern "C" hailo_status hailors_get_physical_devices(
+    hailo_vdevice_handle vdevice,
+    hailo_device_handle* devices,
+    size_t max_count,
+    size_t* count
+) {
+    if (!vdevice || !count || (max_count > 0 && !devices)) {
+        return HAILO_INVALID_ARGUMENT;
+    }
+    *count = 0;
+
+    auto physical_devices = static_cast<VDevice*>(vdevice)->get_physical_devices();
+    if (!physical_devices) {
+        return physical_devices.status();
+    }
+    for (size_t i = 0; i < std::min(max_count, physical_devices->size()); i++) {
+        devices[i] = &physical_devices.value()[i].get();
+    }
+    *count = physical_devices->size();
+    return HAILO_SUCCESS;
+}
+
+// Copies a string that is not necessarily null-terminated into a null-terminated buffer
+static void copy_info_string(char* destination, size_t destination_size, const char* source, size_t length) {
+    std::memset(destination, 0, destination_size);
+    std::memcpy(destination, source, std::min(length, destination_size - 1));
+}
+
+extern "C" hailo_status hailors_identify_device(hailo_device_handle device, hailors_device_identity* identity) {
+    if (!device || !identity) {
+        return HAILO_INVALID_ARGUMENT;
+    }
+    std::memset(identity, 0, sizeof(*identity));
+    auto device_ptr = static_cast<Device*>(device);
+    std::strncpy(identity->device_id, device_ptr->get_dev_id(), sizeof(identity->device_id) - 1);
+
+    auto identify_result = device_ptr->identify();
+    if (!identify_result) {
+        return identify_result.status();
+    }
+    const auto &device_identity = identify_result.value();
+    copy_info_string(identity->board_name, sizeof(identity->board_name),
+        device_identity.board_name, device_identity.board_name_length);
+    copy_info_string(identity->serial_number, sizeof(identity->serial_number),
+        device_identity.serial_number, device_identity.serial_number_length);
+    copy_info_string(identity->part_number, sizeof(identity->part_number),
+        device_identity.part_number, device_identity.part_number_length);
+    copy_info_string(identity->product_name, sizeof(identity->product_name),
+        device_identity.product_name, device_identity.product_name_length);
+    identity->fw_major = device_identity.fw_version.major;
+    identity->fw_minor = device_identity.fw_version.minor;
+    identity->fw_revision = device_identity.fw_version.revision;
+    identity->is_release = device_identity.is_release ? 1 : 0;
+    identity->protocol_version = device_identity.protocol_version;
+    identity->architecture = static_cast<uint32_t>(device_identity.device_architecture);
+
+    // Extended information is not available on every device; identity alone is still useful
+    auto extended_result = device_ptr->get_extended_device_information();
+    if (extended_result) {
+        const auto &extended_info = extended_result.value();
+        identity->has_extended_info = 1;
+        identity->nn_clock_rate = extended_info.neural_network_core_clock_rate;
+        identity->boot_source = static_cast<uint32_t>(extended_info.boot_source);
+        identity->lcs = extended_info.lcs;
+        std::memcpy(identity->soc_id, extended_info.soc_id,
+            std::min(sizeof(identity->soc_id), sizeof(extended_info.soc_id)));
+        std::memcpy(identity->eth_mac_address, extended_info.eth_mac_address,
+            std::min(sizeof(identity->eth_mac_address), sizeof(extended_info.eth_mac_address)));
+    }
+
+    return HAILO_SUCCESS;
+}
+
+extern "C" hailo_status hailors_get_device_id(hailo_device_handle device, char* device_id, size_t device_id_size) {
+    if (!device || !device_id || device_id_size == 0) {
+        return HAILO_INVALID_ARGUMENT;
+    }
+    std::memset(device_id, 0, device_id_size);
+    std::strncpy(device_id, static_cast<Device*>(device)->get_dev_id(), device_id_size - 1);
+    return HAILO_SUCCESS;
+}
+
+extern "C" hailo_status hailors_get_chip_temperature(hailo_device_handle device, hailors_temperature_info* temperature) {
+    if (!device || !temperature) {
+        return HAILO_INVALID_ARGUMENT;
+    }
+
+    auto temperature_result = static_cast<Device*>(device)->get_chip_temperature();
+    if (!temperature_result) {
+        return temperature_result.status();
+    }
+    temperature->ts0_temperature = temperature_result->ts0_temperature;
+    temperature->ts1_temperature = temperature_result->ts1_temperature;
+    temperature->sample_count = temperature_result->sample_count;
+    return HAILO_SUCCESS;
+}
+
+extern "C" hailo_status hailors_measure_power(hailo_device_handle device, float* power) {
+    if (!device || !power) {
+        return HAILO_INVALID_ARGUMENT;
+    }
+
+    auto power_result = static_cast<Device*>(device)->power_measurement(
+        HAILO_DVM_OPTIONS_AUTO, HAILO_POWER_MEASUREMENT_TYPES__POWER);
+    if (!power_result) {
+        return power_result.status();
+    }
+    *power = power_result.value();
+    return HAILO_SUCCESS;
+}
+
 extern "C" hailo_status hailors_release_vdevice(hailo_vdevice_handle vdevice) {
     delete static_cast<VDevice*>(vdevice);
     return HAILO_SUCCESS;
 }
 
+// Parameters used when hailors_configure_hef is given no parameters
+static const hailors_configure_params DEFAULT_CONFIGURE_PARAMS = {
+    {HAILO_FORMAT_TYPE_AUTO, 0, 0, 0},
+    {HAILO_FORMAT_TYPE_FLOAT32, 0, 0, 0},
+    nullptr, 0,
+    nullptr, 0,
+    HAILO_DEFAULT_BATCH_SIZE,
+    nullptr,
+};
+
+// Builds the vstream params of every stream in a direction: the direction defaults first,
+// then the per-stream overrides
+template <typename MakeParams>
+static Expected<std::map<std::string, hailo_vstream_params_t>> make_vstream_params(
+    MakeParams make_params,
+    const hailors_vstream_params &defaults,
+    const hailors_named_vstream_params *overrides,
+    size_t override_count)
+{
+    auto make = [&](const hailors_vstream_params &stream_params) {
+        return make_params(stream_params.quantized != 0, static_cast<hailo_format_type_t>(stream_params.format_type),
+            stream_params.timeout_ms ? stream_params.timeout_ms : HAILO_DEFAULT_VSTREAM_TIMEOUT_MS,
+            stream_params.queue_size ? stream_params.queue_size : HAILO_DEFAULT_VSTREAM_QUEUE_SIZE);
+    };
+
+    auto params = make(defaults);
+    if (!params) {
+        return make_unexpected(params.status());
+    }
+    auto result = std::move(params.value());
+
+    // Overrides are built through HailoRT as well, so the quantized flag and format type are
+    // resolved exactly as for the defaults
+    for (size_t i = 0; i < override_count; i++) {
+        if (!overrides || !overrides[i].name) {
+            return make_unexpected(HAILO_INVALID_ARGUMENT);
+        }
+        auto entry = result.find(overrides[i].name);
+        if (entry == result.end()) {
+            log_message(HAILORS_LOG_ERROR, std::string("No vstream named ") + overrides[i].name + " in the network group.");
+            return make_unexpected(HAILO_NOT_FOUND);
+        }
+        auto override_params = make(overrides[i].params);
+        if (!override_params) {
+            return make_unexpected(override_params.status());
+        }
+        entry->second = override_params->at(entry->first);
+    }
+
+    return result;
+}
+
 extern "C" hailo_status hailors_configure_hef(
     hailo_vdevice_handle vdevice,
     const char* hef_path,
+    const hailors_configure_params *params,  // Optional; NULL selects the default parameters
     hailo_network_group_handle* network_group,
     void ***input_vstreams,   // Pointer to an array of input vstreams
     size_t *input_count,      // Pointer to the number of input vstreams
@@ -30,9 +347,20 @@ extern "C" hailo_status hailors_configure_hef(
     size_t* input_frame_size,   // New parameter for input frame size
     size_t* output_frame_size  // New parameter for output frame size
 ) {
-    // Initialize vstreams as empty
+    if (!vdevice || !hef_path || !network_group || !input_vstreams || !input_count ||
+        !output_vstreams || !output_count || !input_frame_size || !output_frame_size) {
+        return HAILO_INVALID_ARGUMENT;
+    }
+
+    // Initialize outputs as empty so callers never see stale pointers on failure
+    *network_group = nullptr;
+    *input_vstreams = nullptr;
+    *output_vstreams = nullptr;
     *input_count = 0;
     *output_count = 0;
+    *input_frame_size = 0;
+    *output_frame_size = 0;
+
     auto vdevice_ptr = static_cast<VDevice*>(vdevice);
     auto hef_result = Hef::create(hef_path);
     if (!hef_result) {
@@ -40,25 +368,65 @@ extern "C" hailo_status hailors_configure_hef(
     }
     auto hef = std::move(hef_result.value());  // Move instead of copy
 
+    if (!params) {
+        params = &DEFAULT_CONFIGURE_PARAMS;
+    }
+
     auto configure_params = vdevice_ptr->create_configure_params(hef);
     if (!configure_params) {
         return configure_params.status();
     }
 
-    auto network_groups_result = vdevice_ptr->configure(hef, configure_params.value());
-    if (!network_groups_result || network_groups_result->empty()) {
+    // Configure only the requested network group, or the first one in the HEF, so other
+    // groups stay free to be configured separately
+    std::string network_group_name;
+    if (params->network_group_name) {
+        network_group_name = params->network_group_name;
+    } else {
+        auto names = hef.get_network_groups_names();
+        if (names.empty()) {
+            return HAILO_NOT_FOUND;
+        }
+        network_group_name = names[0];
+    }
+    auto selected_params = configure_params->find(network_group_name);
+    if (selected_params == configure_params->end()) {
+        log_message(HAILORS_LOG_ERROR, std::string("No network group named ") + network_group_name + " in the HEF.");
+        return HAILO_NOT_FOUND;
+    }
+    NetworkGroupsParamsMap selected_configure_params;
+    selected_configure_params.emplace(selected_params->first, selected_params->second);
+
+    // The batch size is set per network; HailoRT rejects setting it on the network group as well
+    if (params->batch_size != HAILO_DEFAULT_BATCH_SIZE) {
+        for (auto &network_group_params : selected_configure_params) {
+            for (auto &network_params : network_group_params.second.network_params_by_name) {
+                network_params.second.batch_size = params->batch_size;
+            }
+        }
+    }
+
+    auto network_groups_result = vdevice_ptr->configure(hef, selected_configure_params);
+    if (!network_groups_result) {
         return network_groups_result.status();
     }
+    if (network_groups_result->empty()) {
+        return HAILO_NOT_FOUND;
+    }
 
-    // Access the first network group
+    // Keep our own reference to the network group; the vector owning it goes out of scope
     auto configured_network_group = network_groups_result.value()[0];
     if (!configured_network_group) {
-        std::cerr << "Failed to get network group from vector." << std::endl;
+        log_message(HAILORS_LOG_ERROR, "Failed to get network group from vector.");
         return HAILO_INVALID_OPERATION;
     }
 
     // Create input vstreams
-    auto input_vstream_params = configured_network_group->make_input_vstream_params(false, HAILO_FORMAT_TYPE_AUTO, HAILO_DEFAULT_VSTREAM_TIMEOUT_MS, HAILO_DEFAULT_VSTREAM_QUEUE_SIZE, "");
+    auto input_vstream_params = make_vstream_params(
+        [&](bool quantized, hailo_format_type_t format_type, uint32_t timeout_ms, uint32_t queue_size) {
+            return configured_network_group->make_input_vstream_params(quantized, format_type, timeout_ms, queue_size, "");
+        },
+        params->input, params->input_overrides, params->input_override_count);
     if (!input_vstream_params) {
         return input_vstream_params.status();
     }
@@ -67,18 +435,13 @@ extern "C" hailo_status hailors_configure_hef(
         return input_streams_result.status();
     }
     auto input_streams = std::move(input_streams_result.value());
-    *input_count = input_streams.size();
-
-    // Populate input vstreams
-    *input_vstreams = static_cast<void**>(malloc(input_streams.size() * sizeof(void*)));
-    for (size_t i = 0; i < input_streams.size(); i++) {
-        (*input_vstreams)[i] = new InputVStream(std::move(input_streams[i]));
-    }
-    *input_frame_size = input_streams.empty() ? 0 : static_cast<InputVStream*>((*input_vstreams)[0])->get_frame_size();
-    *input_count = input_streams.size();
 
     // Create output vstreams
-    auto output_vstream_params = configured_network_group->make_output_vstream_params(false, HAILO_FORMAT_TYPE_FLOAT32, HAILO_DEFAULT_VSTREAM_TIMEOUT_MS, HAILO_DEFAULT_VSTREAM_QUEUE_SIZE, "");
+    auto output_vstream_params = make_vstream_params(
+        [&](bool quantized, hailo_format_type_t format_type, uint32_t timeout_ms, uint32_t queue_size) {
+            return configured_network_group->make_output_vstream_params(quantized, format_type, timeout_ms, queue_size, "");
+        },
+        params->output, params->output_overrides, params->output_override_count);
     if (!output_vstream_params) {
         return output_vstream_params.status();
     }
@@ -87,84 +450,187 @@ extern "C" hailo_status hailors_configure_hef(
         return output_streams_result.status();
     }
     auto output_streams = std::move(output_streams_result.value());
-    *output_count = output_streams.size();
+
+    // Allocate the handle arrays; until ownership is handed out below, the vectors above
+    // still own the vstreams and release them on every early return
+    auto inputs = static_cast<void**>(malloc(std::max<size_t>(input_streams.size(), 1) * sizeof(void*)));
+    auto outputs = static_cast<void**>(malloc(std::max<size_t>(output_streams.size(), 1) * sizeof(void*)));
+    auto group_holder = new (std::nothrow) std::shared_ptr<ConfiguredNetworkGroup>(configured_network_group);
+    if (!inputs || !outputs || !group_holder) {
+        free(inputs);
+        free(outputs);
+        delete group_holder;
+        return HAILO_OUT_OF_HOST_MEMORY;
+    }
+
+    // Populate input vstreams
+    for (size_t i = 0; i < input_streams.size(); i++) {
+        inputs[i] = new InputVStream(std::move(input_streams[i]));
+    }
+    *input_vstreams = inputs;
+    *input_count = input_streams.size();
+    *input_frame_size = input_streams.empty() ? 0 : static_cast<InputVStream*>(inputs[0])->get_frame_size();
 
     // Populate output vstreams
-    *output_vstreams = static_cast<void**>(malloc(output_streams.size() * sizeof(void*)));
     for (size_t i = 0; i < output_streams.size(); i++) {
-        (*output_vstreams)[i] = new OutputVStream(std::move(output_streams[i]));
+        outputs[i] = new OutputVStream(std::move(output_streams[i]));
     }
-    *output_frame_size = output_streams.empty() ? 0 : static_cast<OutputVStream*>((*output_vstreams)[0])->get_frame_size();
+    *output_vstreams = outputs;
     *output_count = output_streams.size();
+    *output_frame_size = output_streams.empty() ? 0 : static_cast<OutputVStream*>(outputs[0])->get_frame_size();
 
+    // Hand out a heap-allocated shared_ptr so the network group stays alive until released
+    *network_group = group_holder;
 
+    return HAILO_SUCCESS;
+}
+
+extern "C" hailo_status hailors_get_network_group_names(
+    const char* hef_path,
+    hailors_network_group_name* names,
+    size_t max_count,
+    size_t* count
+) {
+    if (!hef_path || !count || (max_count > 0 && !names)) {
+        return HAILO_INVALID_ARGUMENT;
+    }
+    *count = 0;
 
-    // Set the network group handle
-    *network_group = configured_network_group.get();
+    auto hef_result = Hef::create(hef_path);
+    if (!hef_result) {
+        return hef_result.status();
+    }
+    auto network_group_names = hef_result->get_network_groups_names();
 
+    for (size_t i = 0; i < std::min(max_count, network_group_names.size()); i++) {
+        std::memset(names[i].name, 0, sizeof(names[i].name));
+        std::strncpy(names[i].name, network_group_names[i].c_str(), sizeof(names[i].name) - 1);
+    }
+    *count = network_group_names.size();
     return HAILO_SUCCESS;
 }
 
+extern "C" hailo_status hailors_get_network_group_name(
+    hailo_network_group_handle network_group,
+    hailors_network_group_name* name
+) {
+    if (!network_group || !name) {
+        return HAILO_INVALID_ARGUMENT;
+    }
+    auto &configured_network_group = *static_cast<std::shared_ptr<ConfiguredNetworkGroup>*>(network_group);
+    std::memset(name->name, 0, sizeof(name->name));
+    std::strncpy(name->name, configured_network_group->name().c_str(), sizeof(name->name) - 1);
+    return HAILO_SUCCESS;
+}
 
-extern "C" hailo_status hailors_infer(hailo_network_group_handle network_group, void **input_vstreams, size_t input_count, void **output_vstreams, size_t output_count)
-{
-    if (!network_group || !input_vstreams || !output_vstreams) {
+extern "C" hailo_status hailors_activate_network_group(
+    hailo_network_group_handle network_group,
+    hailo_activated_network_group_handle* activated
+) {
+    if (!network_group || !activated) {
         return HAILO_INVALID_ARGUMENT;
     }
+    *activated = nullptr;
 
-    hailo_status status = HAILO_SUCCESS;  // Default to success
+    auto &configured_network_group = *static_cast<std::shared_ptr<ConfiguredNetworkGroup>*>(network_group);
+    auto activated_result = configured_network_group->activate();
+    if (!activated_result) {
+        return activated_result.status();
+    }
+    *activated = activated_result.value().release();
+    return HAILO_SUCCESS;
+}
 
-    // Create input threads
-    std::vector<std::thread> input_threads;
-    for (size_t i = 0; i < input_count; ++i) {
-        input_threads.emplace_back([&status, input_vstreams, i]() {
-            auto *input_stream = static_cast<InputVStream*>(input_vstreams[i]);
-            std::vector<uint8_t> data(input_stream->get_frame_size(), 0);
-            for (size_t j = 0; j < 100; ++j) {
-                hailo_status write_status = input_stream->write(MemoryView(data.data(), data.size()));
-                if (write_status != HAILO_SUCCESS) {
-                    status = write_status;  // Return the actual error status
-                    return;
-                }
-            }
-            hailo_status flush_status = input_stream->flush();
-            if (flush_status != HAILO_SUCCESS) {
-                status = flush_status;  // Return the flush failure status if applicable
-            }
-        });
-    }
-
-    // Create output threads
-    std::vector<std::thread> output_threads;
-    for (size_t i = 0; i < output_count; ++i) {
-        output_threads.emplace_back([&status, output_vstreams, i]() {
-            auto *output_stream = static_cast<OutputVStream*>(output_vstreams[i]);
-            std::vector<uint8_t> data(output_stream->get_frame_size());
-            for (size_t j = 0; j < 100; ++j) {
-                hailo_status read_status = output_stream->read(MemoryView(data.data(), data.size()));
-                if (read_status != HAILO_SUCCESS) {
-                    status = read_status;  // Return the actual read error status
-                    return;
-                }
-            }
-        });
+extern "C" hailo_status hailors_deactivate_network_group(hailo_activated_network_group_handle activated) {
+    delete static_cast<ActivatedNetworkGroup*>(activated);
+    return HAILO_SUCCESS;
+}
+
+extern "C" hailo_status hailors_release_network_group(hailo_network_group_handle network_group) {
+    delete static_cast<std::shared_ptr<ConfiguredNetworkGroup>*>(network_group);
+    return HAILO_SUCCESS;
+}
+
+extern "C" hailo_status hailors_release_input_vstreams(void **input_vstreams, size_t input_count) {
+    if (!input_vstreams) {
+        return HAILO_SUCCESS;
     }
+    for (size_t i = 0; i < input_count; i++) {
+        delete static_cast<InputVStream*>(input_vstreams[i]);
+    }
+    free(input_vstreams);
+    return HAILO_SUCCESS;
+}
 
-    // Join all threads
-    for (auto &t : input_threads) {
-        t.join();
+extern "C" hailo_status hailors_release_output_vstreams(void **output_vstreams, size_t output_count) {
+    if (!output_vstreams) {
+        return HAILO_SUCCESS;
     }
-    for (auto &t : output_threads) {
-        t.join();
+    for (size_t i = 0; i < output_count; i++) {
+        delete static_cast<OutputVStream*>(output_vstreams[i]);
     }
+    free(output_vstreams);
+    return HAILO_SUCCESS;
+}
 
-    if (status == HAILO_SUCCESS) {
-        std::cout << "Inference completed successfully." << std::endl;
+template <typename VStream>
+static hailo_status fill_vstream_info(VStream* vstream, hailors_vstream_info* info)
+{
+    if (!vstream || !info) {
+        return HAILO_INVALID_ARGUMENT;
+    }
+
+    std::memset(info, 0, sizeof(*info));
+    auto name = vstream->name();
+    std::strncpy(info->name, name.c_str(), sizeof(info->name) - 1);
+    info->frame_size = vstream->get_frame_size();
+
+    // Report the format of the host-side buffer, which is what callers read and write
+    auto user_format = vstream->get_user_buffer_format();
+    info->format_type = static_cast<uint32_t>(user_format.type);
+    info->format_order = static_cast<uint32_t>(user_format.order);
+
+    const auto &vstream_info = vstream->get_info();
+    info->qp_zp = vstream_info.quant_info.qp_zp;
+    info->qp_scale = vstream_info.quant_info.qp_scale;
+    info->limvals_min = vstream_info.quant_info.limvals_min;
+    info->limvals_max = vstream_info.quant_info.limvals_max;
+
+    if (user_format.order == HAILO_FORMAT_ORDER_HAILO_NMS ||
+        user_format.order == HAILO_FORMAT_ORDER_HAILO_NMS_WITH_BYTE_MASK) {
+        info->is_nms = 1;
+        info->nms_number_of_classes = vstream_info.nms_shape.number_of_classes;
+        info->nms_max_bboxes_per_class = vstream_info.nms_shape.max_bboxes_per_class;
     } else {
-        std::cerr << "Inference failed with status: " << status << std::endl;
+        info->height = vstream_info.shape.height;
+        info->width = vstream_info.shape.width;
+        info->features = vstream_info.shape.features;
     }
 
-    return status;
+    return HAILO_SUCCESS;
+}
+
+extern "C" hailo_status hailors_get_input_vstream_info(
+    hailo_input_vstream_handle input_vstream,
+    hailors_vstream_info* info
+) {
+    return fill_vstream_info(static_cast<InputVStream*>(input_vstream), info);
+}
+
+extern "C" hailo_status hailors_get_output_vstream_info(
+    hailo_output_vstream_handle output_vstream,
+    hailors_vstream_info* info
+) {
+    return fill_vstream_info(static_cast<OutputVStream*>(output_vstream), info);
+}
+
+extern "C" hailo_status hailors_flush_input_vstream(hailo_input_vstream_handle input_vstream) {
+    if (!input_vstream) {
+        return HAILO_INVALID_ARGUMENT;
+    }
+
+    // Blocks until every written frame, including a partial batch, has been sent to the device
+    return static_cast<InputVStream*>(input_vstream)->flush();
 }
 
 extern "C" hailo_status hailors_write_input_frame(
@@ -173,7 +639,7 @@ extern "C" hailo_status hailors_write_input_frame(
     size_t data_size
 ) {
     if (!input_vstream || !data) {
-        std::cerr << "Invalid input stream handle or data buffer." << std::endl;
+        log_message(HAILORS_LOG_ERROR, "Invalid input stream handle or data buffer.");
         return HAILO_INVALID_ARGUMENT;
     }
 
@@ -186,7 +652,7 @@ extern "C" hailo_status hailors_write_input_frame(
     // Write data using the MemoryView
     auto status = vstream->write(input_view);
     if (status != HAILO_SUCCESS) {
-        std::cerr << "Failed to write data to input vstream. Status: " << status << std::endl;
+        log_message(HAILORS_LOG_DEBUG, "Failed to write data to input vstream. Status: " + std::to_string(static_cast<int>(status)));
     }
 
     return status;
@@ -198,12 +664,12 @@ extern "C" hailo_status hailors_read_output_frame(
     size_t buffer_size
 ) {
     if (!output_vstream) {
-        std::cerr << "output_vstream is null." << std::endl;
+        log_message(HAILORS_LOG_ERROR, "output_vstream is null.");
         return HAILO_INVALID_ARGUMENT;
     }
 
     if (!buffer || buffer_size == 0) {
-        std::cerr << "Buffer is null or has invalid size." << std::endl;
+        log_message(HAILORS_LOG_ERROR, "Buffer is null or has invalid size.");
         return HAILO_INVALID_ARGUMENT;
     }
 
@@ -214,7 +680,7 @@ extern "C" hailo_status hailors_read_output_frame(
     // Create a MemoryView for the output buffer
     hailort::MemoryView output_view(buffer, buffer_size);
     if (!vstream) {
-        std::cerr << "Invalid output_vstream (null pointer)." << std::endl;
+        log_message(HAILORS_LOG_ERROR, "Invalid output_vstream (null pointer).");
         return HAILO_INVALID_ARGUMENT;
     }
 
@@ -223,8 +689,191 @@ extern "C" hailo_status hailors_read_output_frame(
 
 
     if (status != HAILO_SUCCESS) {
-        std::cerr << "Failed to read data from output vstream. Status: " << status << std::endl;
+        log_message(HAILORS_LOG_DEBUG, "Failed to read data from output vstream. Status: " + std::to_string(static_cast<int>(status)));
     }
 
     return status;
-}
\ No newline at end of file
+}
+// A long-lived thread that runs the timed operations of one calling thread, so that a timed
+// write or read does not start a thread of its own
+class TimedWorker {
+public:
+    struct Task {
+        std::function<hailo_status()> operation;
+        std::mutex mutex;
+        std::condition_variable done_cv;
+        bool done = false;
+        hailo_status status = HAILO_UNINITIALIZED;
+    };
+
+    TimedWorker() : state(std::make_shared<State>())
+    {
+        // Detached, and sharing its state, so that thread exit never waits on it
+        std::thread([state = state]() {
+            while (true) {
+                std::shared_ptr<Task> task;
+                {
+                    std::unique_lock<std::mutex> lock(state->mutex);
+                    state->cv.wait(lock, [&state]() { return state->stopping || !state->tasks.empty(); });
+                    if (state->tasks.empty()) {
+                        return;
+                    }
+                    task = state->tasks.front();
+                    state->tasks.pop_front();
+                }
+                auto status = task->operation();
+                {
+                    std::lock_guard<std::mutex> lock(task->mutex);
+                    task->status = status;
+                    task->done = true;
+                }
+                task->done_cv.notify_all();
+            }
+        }).detach();
+    }
+
+    ~TimedWorker()
+    {
+        {
+            std::lock_guard<std::mutex> lock(state->mutex);
+            state->stopping = true;
+        }
+        state->cv.notify_all();
+    }
+
+    std::shared_ptr<Task> submit(std::function<hailo_status()> operation)
+    {
+        auto task = std::make_shared<Task>();
+        task->operation = std::move(operation);
+        {
+            std::lock_guard<std::mutex> lock(state->mutex);
+            state->tasks.push_back(task);
+        }
+        state->cv.notify_all();
+        return task;
+    }
+
+private:
+    struct State {
+        std::mutex mutex;
+        std::condition_variable cv;
+        std::deque<std::shared_ptr<Task>> tasks;
+        bool stopping = false;
+    };
+
+    std::shared_ptr<State> state;
+};
+
+static TimedWorker &timed_worker()
+{
+    static thread_local TimedWorker worker;
+    return worker;
+}
+
+// Runs a blocking vstream operation with a deadline on the calling thread's worker. When the
+// deadline passes, the vstream is aborted to unblock the operation and resumed afterwards, which
+// discards the frames queued in it. The operation has always finished when this returns, so the
+// vstream can be released right after, and a frame is never written or read behind the caller's
+// back. If the abort fails, the operation is left to end on the vstream's own timeout and its
+// status is returned
+template <typename VStream>
+static hailo_status run_with_timeout(VStream *vstream, uint32_t timeout_ms, std::function<hailo_status()> operation)
+{
+    auto task = timed_worker().submit(std::move(operation));
+    std::unique_lock<std::mutex> lock(task->mutex);
+    if (task->done_cv.wait_for(lock, std::chrono::milliseconds(timeout_ms), [&task]() { return task->done; })) {
+        return task->status;
+    }
+    lock.unlock();
+
+    auto abort_status = vstream->abort();
+    lock.lock();
+    task->done_cv.wait(lock, [&task]() { return task->done; });
+    auto status = task->status;
+    lock.unlock();
+    if (abort_status != HAILO_SUCCESS) {
+        return status;
+    }
+
+    auto resume_status = vstream->resume();
+    if (resume_status != HAILO_SUCCESS) {
+        return resume_status;
+    }
+    // The operation may have completed just before the abort
+    return (status == HAILO_SUCCESS) ? HAILO_SUCCESS : HAILO_TIMEOUT;
+}
+
+extern "C" hailo_status hailors_write_input_frame_timeout(
+    hailo_input_vstream_handle input_vstream,
+    const void* data,
+    size_t data_size,
+    uint32_t timeout_ms
+) {
+    if (!input_vstream || !data) {
+        return HAILO_INVALID_ARGUMENT;
+    }
+
+    auto vstream = static_cast<InputVStream*>(input_vstream);
+    if (timeout_ms == 0) {
+        return vstream->write(MemoryView(const_cast<void*>(data), data_size));
+    }
+    // The worker writes a copy, so the caller's frame is never used after a timeout
+    auto frame = std::make_shared<std::vector<uint8_t>>(
+        static_cast<const uint8_t*>(data), static_cast<const uint8_t*>(data) + data_size);
+    return run_with_timeout(vstream, timeout_ms, [vstream, frame]() {
+        return vstream->write(MemoryView(frame->data(), frame->size()));
+    });
+}
+
+extern "C" hailo_status hailors_read_output_frame_timeout(
+    hailo_output_vstream_handle output_vstream,
+    void* buffer,
+    size_t buffer_size,
+    uint32_t timeout_ms
+) {
+    if (!output_vstream || !buffer || buffer_size == 0) {
+        return HAILO_INVALID_ARGUMENT;
+    }
+
+    auto vstream = static_cast<OutputVStream*>(output_vstream);
+    if (timeout_ms == 0) {
+        return vstream->read(MemoryView(buffer, buffer_size));
+    }
+    // The worker reads into its own frame, which is copied out only once the read succeeded
+    auto frame = std::make_shared<std::vector<uint8_t>>(buffer_size);
+    auto status = run_with_timeout(vstream, timeout_ms, [vstream, frame]() {
+        return vstream->read(MemoryView(frame->data(), frame->size()));
+    });
+    if (status == HAILO_SUCCESS) {
+        std::memcpy(buffer, frame->data(), buffer_size);
+    }
+    return status;
+}
+
+extern "C" hailo_status hailors_abort_input_vstream(hailo_input_vstream_handle input_vstream) {
+    if (!input_vstream) {
+        return HAILO_INVALID_ARGUMENT;
+    }
+    return static_cast<InputVStream*>(input_vstream)->abort();
+}
+
+extern "C" hailo_status hailors_resume_input_vstream(hailo_input_vstream_handle input_vstream) {
+    if (!input_vstream) {
+        return HAILO_INVALID_ARGUMENT;
+    }
+    return static_cast<InputVStream*>(input_vstream)->resume();
+}
+
+extern "C" hailo_status hailors_abort_output_vstream(hailo_output_vstream_handle output_vstream) {
+    if (!output_vstream) {
+        return HAILO_INVALID_ARGUMENT;
+    }
+    return static_cast<OutputVStream*>(output_vstream)->abort();
+}
+
+extern "C" hailo_status hailors_resume_output_vstream(hailo_output_vstream_handle output_vstream) {
+    if (!output_vstream) {
+        return HAILO_INVALID_ARGUMENT;
+    }
+    return static_cast<OutputVStream*>(output_vstream)->resume();
+}
@@ -13,14 +13,138 @@ typedef void* hailo_vdevice_handle;
 typedef void* hailo_network_group_handle;
 typedef void* hailo_input_vstream_handle;
 typedef void* hailo_output_vstream_handle;
+typedef void* hailo_activated_network_group_handle;
+
+// Per-stream information returned by the vstream info getters
+typedef struct {
+    char name[HAILO_MAX_STREAM_NAME_SIZE];  // Null-terminated vstream name
+    size_t frame_size;                      // Size of one frame in bytes
+    uint32_t height;                        // Frame height (0 for NMS streams)
+    uint32_t width;                         // Frame width (0 for NMS streams)
+    uint32_t features;                      // Features per pixel (0 for NMS streams)
+    uint32_t format_type;                   // hailo_format_type_t of the user buffer
+    uint32_t format_order;                  // hailo_format_order_t of the user buffer
+    float qp_zp;                            // Quantization zero point
+    float qp_scale;                         // Quantization scale
+    float limvals_min;                      // Minimum dequantized value
+    float limvals_max;                      // Maximum dequantized value
+    uint32_t is_nms;                        // Non-zero if the nms_* fields are valid
+    uint32_t nms_number_of_classes;         // NMS number of classes
+    uint32_t nms_max_bboxes_per_class;      // NMS maximum bounding boxes per class
+} hailors_vstream_info;
+
+// Severity of a message passed to the log callback
+typedef enum {
+    HAILORS_LOG_ERROR = 1,
+    HAILORS_LOG_WARN = 2,
+    HAILORS_LOG_INFO = 3,
+    HAILORS_LOG_DEBUG = 4,
+    HAILORS_LOG_TRACE = 5,
+} hailors_log_level;
+
+// Receives the wrapper's diagnostic messages; the message is only valid during the call
+typedef void (*hailors_log_callback)(int32_t level, const char* message);
+
+// Virtual stream parameters used when creating vstreams
+typedef struct {
+    uint32_t format_type;   // hailo_format_type_t of the user buffer
+    uint32_t quantized;     // Non-zero to keep the user buffer quantized
+    uint32_t timeout_ms;    // Read/write timeout; 0 selects HAILO_DEFAULT_VSTREAM_TIMEOUT_MS
+    uint32_t queue_size;    // Pipeline queue depth; 0 selects HAILO_DEFAULT_VSTREAM_QUEUE_SIZE
+} hailors_vstream_params;
+
+// Parameters for a single vstream, overriding the defaults of its direction
+typedef struct {
+    const char *name;                // Null-terminated vstream name
+    hailors_vstream_params params;
+} hailors_named_vstream_params;
+
+// Parameters for hailors_configure_hef
+typedef struct {
+    hailors_vstream_params input;                          // Defaults for all input vstreams
+    hailors_vstream_params output;                         // Defaults for all output vstreams
+    const hailors_named_vstream_params *input_overrides;   // Per-stream input parameters
+    size_t input_override_count;
+    const hailors_named_vstream_params *output_overrides;  // Per-stream output parameters
+    size_t output_override_count;
+    uint16_t batch_size;                                   // Frames per batch; 0 selects HAILO_DEFAULT_BATCH_SIZE
+    const char *network_group_name;                        // Network group to configure; NULL selects the first
+} hailors_configure_params;
+
+// Network group name as stored in a HEF
+typedef struct {
+    char name[HAILO_MAX_NETWORK_GROUP_NAME_SIZE];  // Null-terminated network group name
+} hailors_network_group_name;
+
+// Physical device found by hailors_scan_devices
+typedef struct {
+    char id[HAILO_MAX_DEVICE_ID_LENGTH];  // Null-terminated device ID, e.g. "0000:01:00.0"
+    uint32_t is_pcie;                     // Non-zero if the pcie_* fields are valid
+    uint32_t pcie_domain;
+    uint32_t pcie_bus;
+    uint32_t pcie_device;
+    uint32_t pcie_func;
+    uint32_t has_architecture;            // Non-zero if architecture is valid
+    uint32_t architecture;                // hailo_device_architecture_t
+} hailors_device_info;
+
+// Maximum length of the strings in hailors_device_identity, including the null terminator
+#define HAILORS_MAX_INFO_STRING_LENGTH (64)
+
+// Board identity and firmware of a physical device, from identify and the extended device information
+typedef struct {
+    char device_id[HAILO_MAX_DEVICE_ID_LENGTH];
+    char board_name[HAILORS_MAX_INFO_STRING_LENGTH];
+    char serial_number[HAILORS_MAX_INFO_STRING_LENGTH];
+    char part_number[HAILORS_MAX_INFO_STRING_LENGTH];
+    char product_name[HAILORS_MAX_INFO_STRING_LENGTH];
+    uint32_t fw_major;
+    uint32_t fw_minor;
+    uint32_t fw_revision;
+    uint32_t is_release;                // Non-zero for release firmware
+    uint32_t protocol_version;
+    uint32_t architecture;              // hailo_device_architecture_t
+    uint32_t has_extended_info;         // Non-zero if the fields below are valid
+    uint32_t nn_clock_rate;             // Neural network core clock rate in Hz
+    uint32_t boot_source;               // hailo_device_boot_source_t
+    uint32_t lcs;                       // Life cycle state
+    uint8_t soc_id[32];
+    uint8_t eth_mac_address[6];
+} hailors_device_identity;
+
+// Chip temperature of a physical device
+typedef struct {
+    float ts0_temperature;              // Temperature sensor 0, in degrees Celsius
+    float ts1_temperature;              // Temperature sensor 1, in degrees Celsius
+    uint32_t sample_count;
+} hailors_temperature_info;
+
+// Parameters for hailors_create_vdevice_with_params
+typedef struct {
+    const char *const *device_ids;  // Devices to use; NULL selects any available devices
+    size_t device_id_count;
+    uint32_t device_count;          // Number of devices when device_ids is NULL; 0 selects the default
+    uint32_t scheduling_algorithm;  // hailo_scheduling_algorithm_t
+    const char *group_id;           // VDevice group for sharing devices between processes; NULL selects the default
+} hailors_vdevice_params;
 
 // Function declarations
+
+// Version of the HailoRT library the wrapper is linked against
+hailo_status hailors_get_library_version(uint32_t* major, uint32_t* minor, uint32_t* revision);
+
 hailo_status hailors_create_vdevice(hailo_vdevice_handle* vdevice);
+hailo_status hailors_create_vdevice_with_params(const hailors_vdevice_params* params, hailo_vdevice_handle* vdevice);
+
+// Lists the physical devices. Fills up to max_count entries and sets count to the total number
+// of devices, which may be larger than max_count
+hailo_status hailors_scan_devices(hailors_device_info* devices, size_t max_count, size_t* count);
 hailo_status hailors_release_vdevice(hailo_vdevice_handle vdevice);
 
 hailo_status hailors_configure_hef(
     hailo_vdevice_handle vdevice,
     const char* hef_path,
+    const hailors_configure_params *params,  // Optional; NULL selects the default parameters
     hailo_network_group_handle* network_group,
     void ***input_vstreams,   // Pointer to an array of input vstreams
     size_t *input_count,      // Pointer to the number of input vstreams
@@ -30,12 +154,68 @@ hailo_status hailors_configure_hef(
     size_t* output_frame_size  // New parameter for output frame size
 );
 
-hailo_status hailors_infer(
+// Physical devices for health queries. Devices opened with hailors_open_device are owned by the
+// caller; devices returned by hailors_get_physical_devices are owned by the VDevice
+typedef void* hailo_device_handle;
+hailo_status hailors_open_device(const char* device_id, hailo_device_handle* device);
+hailo_status hailors_release_device(hailo_device_handle device);
+hailo_status hailors_get_physical_devices(
+    hailo_vdevice_handle vdevice,
+    hailo_device_handle* devices,
+    size_t max_count,
+    size_t* count
+);
+
+hailo_status hailors_identify_device(hailo_device_handle device, hailors_device_identity* identity);
+// Copies the ID of an open device without querying its firmware
+hailo_status hailors_get_device_id(hailo_device_handle device, char* device_id, size_t device_id_size);
+hailo_status hailors_get_chip_temperature(hailo_device_handle device, hailors_temperature_info* temperature);
+
+// Takes one power measurement in watts; fails on boards without a power sensor
+hailo_status hailors_measure_power(hailo_device_handle device, float* power);
+
+// Lists the network groups in a HEF. Fills up to max_count names and sets count to the total
+// number of network groups, which may be larger than max_count
+hailo_status hailors_get_network_group_names(
+    const char* hef_path,
+    hailors_network_group_name* names,
+    size_t max_count,
+    size_t* count
+);
+
+// Gets the name of a configured network group
+hailo_status hailors_get_network_group_name(
+    hailo_network_group_handle network_group,
+    hailors_network_group_name* name
+);
+
+// Activates a configured network group for manual switching; fails with
+// HAILO_INVALID_OPERATION when the VDevice runs the model scheduler
+hailo_status hailors_activate_network_group(
     hailo_network_group_handle network_group,
-    hailo_input_vstream_handle* input_vstreams,
-    size_t input_count,
-    hailo_output_vstream_handle* output_vstreams,
-    size_t output_count);
+    hailo_activated_network_group_handle* activated
+);
+hailo_status hailors_deactivate_network_group(hailo_activated_network_group_handle activated);
+
+// Release functions; vstreams must be released before their network group, and the
+// network group before the vdevice it was configured on
+hailo_status hailors_release_network_group(hailo_network_group_handle network_group);
+hailo_status hailors_release_input_vstreams(void **input_vstreams, size_t input_count);
+hailo_status hailors_release_output_vstreams(void **output_vstreams, size_t output_count);
+
+hailo_status hailors_get_input_vstream_info(
+    hailo_input_vstream_handle input_vstream,
+    hailors_vstream_info* info
+);
+
+hailo_status hailors_get_output_vstream_info(
+    hailo_output_vstream_handle output_vstream,
+    hailors_vstream_info* info
+);
+
+// Blocks until every frame written to the input vstream has been sent to the device, so that
+// a partial batch is processed without waiting for more frames
+hailo_status hailors_flush_input_vstream(hailo_input_vstream_handle input_vstream);
 
 }
 
@@ -51,4 +231,37 @@ extern "C" hailo_status hailors_read_output_frame(
     size_t buffer_size
 );
 
+// Write and read with a per-call timeout; 0 waits for the vstream's own timeout. When the timeout
+// passes, the vstream is aborted and resumed, which discards the frames queued in it, and
+// HAILO_TIMEOUT is returned.
+//
+// A non-zero timeout hands the operation to a worker thread that each calling thread starts on
+// its first timed call and keeps, and costs one copy of the frame per call. The operation has
+// finished when the call returns, so the vstream may be released right after. If aborting fails,
+// the call waits for the operation to end on the vstream's own timeout and returns its status
+extern "C" hailo_status hailors_write_input_frame_timeout(
+    hailo_input_vstream_handle input_vstream,
+    const void* data,
+    size_t data_size,
+    uint32_t timeout_ms
+);
+
+extern "C" hailo_status hailors_read_output_frame_timeout(
+    hailo_output_vstream_handle output_vstream,
+    void* buffer,
+    size_t buffer_size,
+    uint32_t timeout_ms
+);
+
+// Abort unblocks pending and future writes and reads with HAILO_STREAM_ABORT until the vstream
+// is resumed. Frames queued in the vstream are discarded
+extern "C" hailo_status hailors_abort_input_vstream(hailo_input_vstream_handle input_vstream);
+extern "C" hailo_status hailors_resume_input_vstream(hailo_input_vstream_handle input_vstream);
+extern "C" hailo_status hailors_abort_output_vstream(hailo_output_vstream_handle output_vstream);
+extern "C" hailo_status hailors_resume_output_vstream(hailo_output_vstream_handle output_vstream);
+
+// Routes diagnostic messages to the callback, which must be safe to call from any thread.
+// Messages are dropped while no callback is set
+extern "C" void hailors_set_log_callback(hailors_log_callback callback);
+
 #endif // DEVICE_API_WRAPPER_HPP
@@ -0,0 +1,352 @@
+use crate::compat::HailoRtVersion;
+use crate::device::DeviceArchitecture;
+use crate::hef::HefArchitecture;
+use crate::status::HailoStatus;
+use crate::vstream::{FormatOrder, StreamDirection, VStreamShape};
+
+/// A specialized `Result` type for hailors operations.
+pub type Result<T> = std::result::Result<T, HailorsError>;
+
+/// The operation that was running when HailoRT reported a failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    /// Reading the version of the HailoRT library.
+    GetLibraryVersion,
+    /// Listing the physical devices.
+    ScanDevices,
+    /// Creating the virtual device.
+    CreateVDevice,
+    /// Opening a physical device.
+    OpenDevice,
+    /// Reading the identity or extended information of a device.
+    IdentifyDevice,
+    /// Reading the chip temperature.
+    GetChipTemperature,
+    /// Measuring power consumption.
+    MeasurePower,
+    /// Loading the HEF and configuring the network group and vstreams.
+    ConfigureHef,
+    /// Querying vstream information.
+    GetVStreamInfo,
+    /// Listing the network groups in a HEF.
+    ListNetworkGroups,
+    /// Activating a network group.
+    ActivateNetworkGroup,
+    /// Writing a frame to an input vstream.
+    WriteInput,
+    /// Flushing an input vstream.
+    FlushInput,
+    /// Reading a frame from an output vstream.
+    ReadOutput,
+    /// Aborting the vstreams.
+    AbortStreams,
+    /// Resuming aborted vstreams.
+    ResumeStreams,
+}
+
+impl std::fmt::Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operation::GetLibraryVersion => write!(f, "get HailoRT version"),
+            Operation::ScanDevices => write!(f, "scan devices"),
+            Operation::CreateVDevice => write!(f, "create VDevice"),
+            Operation::OpenDevice => write!(f, "open device"),
+            Operation::IdentifyDevice => write!(f, "identify device"),
+            Operation::GetChipTemperature => write!(f, "get chip temperature"),
+            Operation::MeasurePower => write!(f, "measure power"),
+            Operation::ConfigureHef => write!(f, "configure HEF"),
+            Operation::GetVStreamInfo => write!(f, "get vstream info"),
+            Operation::ListNetworkGroups => write!(f, "list network groups"),
+            Operation::ActivateNetworkGroup => write!(f, "activate network group"),
+            Operation::WriteInput => write!(f, "write input frame"),
+            Operation::FlushInput => write!(f, "flush input"),
+            Operation::ReadOutput => write!(f, "read output frame"),
+            Operation::AbortStreams => write!(f, "abort streams"),
+            Operation::ResumeStreams => write!(f, "resume streams"),
+        }
+    }
+}
+
+/// Errors returned by hailors.
+#[derive(Debug)]
+pub enum HailorsError {
+    /// A HailoRT call returned a non-success status.
+    Hailo {
+        /// The operation that failed.
+        operation: Operation,
+        /// The status returned by HailoRT.
+        status: HailoStatus,
+        /// The HEF being configured, if any.
+        hef_path: Option<String>,
+        /// The vstream being accessed, if any.
+        stream: Option<String>,
+    },
+    /// A write or read did not complete in time (`HailoStatus::Timeout`).
+    ///
+    /// Frames queued in the stream when the timeout passed have been discarded.
+    Timeout {
+        /// The operation that timed out.
+        operation: Operation,
+        /// The vstream being accessed, if any.
+        stream: Option<String>,
+    },
+    /// A write or read was interrupted because the streams were aborted
+    /// (`HailoStatus::StreamAbort`). The streams stay aborted until they are resumed.
+    StreamAborted {
+        /// The operation that was interrupted.
+        operation: Operation,
+        /// The vstream being accessed, if any.
+        stream: Option<String>,
+    },
+    /// A stream index is out of range.
+    StreamIndexOutOfRange {
+        /// Direction of the stream.
+        direction: StreamDirection,
+        /// The requested index.
+        index: usize,
+        /// The number of streams in that direction.
+        count: usize,
+    },
+    /// No stream has the requested name.
+    StreamNotFound {
+        /// Direction of the stream.
+        direction: StreamDirection,
+        /// The requested name.
+        name: String,
+    },
+    /// A frame or buffer does not match the frame size of its stream.
+    FrameSizeMismatch {
+        /// Name of the stream.
+        stream: String,
+        /// Frame size of the stream in bytes.
+        expected: usize,
+        /// Size of the provided frame in bytes.
+        actual: usize,
+    },
+    /// A tensor does not match the shape of its stream.
+    ShapeMismatch {
+        /// Name of the stream.
+        stream: String,
+        /// Shape of the stream.
+        expected: VStreamShape,
+        /// Shape of the tensor.
+        actual: VStreamShape,
+    },
+    /// A format order does not store elements by height, width and feature, so a tensor in it
+    /// cannot be indexed or converted.
+    UnsupportedLayout(FormatOrder),
+    /// The number of frames does not match the number of streams.
+    StreamCountMismatch {
+        /// Direction of the streams.
+        direction: StreamDirection,
+        /// The number of streams.
+        expected: usize,
+        /// The number of frames provided.
+        actual: usize,
+    },
+    /// An argument is invalid, such as a HEF path containing a nul byte.
+    InvalidArgument(String),
+    /// A file is not a valid HEF, or its model description could not be read.
+    InvalidHef(String),
+    /// The HEF was compiled for an architecture the device cannot run.
+    ArchitectureMismatch {
+        /// Architecture the HEF was compiled for.
+        hef_architecture: HefArchitecture,
+        /// ID of the device.
+        device_id: String,
+        /// Architecture of the device.
+        device_architecture: DeviceArchitecture,
+        /// Path of the HEF, if known.
+        hef_path: Option<String>,
+    },
+    /// The HEF was compiled by a Dataflow Compiler newer than the installed HailoRT supports.
+    UnsupportedHefVersion {
+        /// Version of the Dataflow Compiler that compiled the HEF.
+        sdk_version: String,
+        /// Version of the installed HailoRT library.
+        hailort_version: HailoRtVersion,
+        /// Path of the HEF, if known.
+        hef_path: Option<String>,
+    },
+    /// A model manifest is malformed or does not match its model.
+    InvalidManifest(String),
+    /// A label file does not hold a list or map of class names.
+    InvalidLabels(String),
+    /// The worker thread running a request stopped before completing it.
+    WorkerStopped,
+    /// The backend does not run on a Hailo device, so there is no device to query.
+    NoDevice,
+    /// An I/O error, such as a missing recording file.
+    Io(std::io::Error),
+}
+
+impl HailorsError {
+    /// Returns the HailoRT status carried by this error, if any.
+    pub fn status(&self) -> Option<HailoStatus> {
+        match self {
+            HailorsError::Hailo { status, .. } => Some(*status),
+            HailorsError::Timeout { .. } => Some(HailoStatus::Timeout),
+            HailorsError::StreamAborted { .. } => Some(HailoStatus::StreamAbort),
+            _ => None,
+        }
+    }
+
+    /// Adds the HEF path to a HailoRT error.
+    pub(crate) fn with_hef_path(mut self, path: &str) -> Self {
+        if let HailorsError::Hailo { hef_path, .. }
+        | HailorsError::ArchitectureMismatch { hef_path, .. }
+        | HailorsError::UnsupportedHefVersion { hef_path, .. } = &mut self
+        {
+            *hef_path = Some(path.to_string());
+        }
+        self
+    }
+
+    /// Adds the stream name to a HailoRT error.
+    pub(crate) fn with_stream(mut self, name: &str) -> Self {
+        if let HailorsError::Hailo { stream, .. }
+        | HailorsError::Timeout { stream, .. }
+        | HailorsError::StreamAborted { stream, .. } = &mut self
+        {
+            *stream = Some(name.to_string());
+        }
+        self
+    }
+}
+
+impl std::fmt::Display for HailorsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HailorsError::Hailo { operation, status, hef_path, stream } => {
+                write!(f, "Failed to {}: {} ({})", operation, status, *status as i32)?;
+                if let Some(hef_path) = hef_path {
+                    write!(f, ", HEF: {}", hef_path)?;
+                }
+                if let Some(stream) = stream {
+                    write!(f, ", stream: {}", stream)?;
+                }
+                Ok(())
+            }
+            HailorsError::Timeout { operation, stream } => {
+                write!(f, "Timed out trying to {}", operation)?;
+                if let Some(stream) = stream {
+                    write!(f, ", stream: {}", stream)?;
+                }
+                Ok(())
+            }
+            HailorsError::StreamAborted { operation, stream } => {
+                write!(f, "Failed to {}: streams were aborted", operation)?;
+                if let Some(stream) = stream {
+                    write!(f, ", stream: {}", stream)?;
+                }
+                Ok(())
+            }
+            HailorsError::StreamIndexOutOfRange { direction, index, count } => write!(
+                f,
+                "{} stream index {} out of range ({} streams)",
+                direction, index, count
+            ),
+            HailorsError::StreamNotFound { direction, name } => {
+                write!(f, "No {} stream named {}", direction, name)
+            }
+            HailorsError::FrameSizeMismatch { stream, expected, actual } => write!(
+                f,
+                "Frame size mismatch for {}: expected {}, got {}",
+                stream, expected, actual
+            ),
+            HailorsError::ShapeMismatch { stream, expected, actual } => write!(
+                f,
+                "Shape mismatch for {}: expected {}x{}x{}, got {}x{}x{}",
+                stream, expected.height, expected.width, expected.features, actual.height, actual.width, actual.features
+            ),
+            HailorsError::UnsupportedLayout(order) => {
+                write!(f, "Format order {} has no height, width and feature layout", order)
+            }
+            HailorsError::StreamCountMismatch { direction, expected, actual } => write!(
+                f,
+                "{} count mismatch: expected {}, got {}",
+                direction, expected, actual
+            ),
+            HailorsError::InvalidArgument(message) => write!(f, "Invalid argument: {}", message),
+            HailorsError::InvalidHef(message) => write!(f, "Invalid HEF: {}", message),
+            HailorsError::ArchitectureMismatch {
+                hef_architecture,
+                device_id,
+                device_architecture,
+                hef_path,
+            } => {
+                write!(f, "HEF ")?;
+                if let Some(hef_path) = hef_path {
+                    write!(f, "{} ", hef_path)?;
+                }
+                write!(
+                    f,
+                    "is compiled for {} but device {} is {}",
+                    hef_architecture, device_id, device_architecture
+                )?;
+                if let Some(needed) = HefArchitecture::for_device(*device_architecture) {
+                    write!(f, "; use a HEF compiled for {}", needed)?;
+                }
+                Ok(())
+            }
+            HailorsError::UnsupportedHefVersion {
+                sdk_version,
+                hailort_version,
+                hef_path,
+            } => {
+                write!(f, "HEF ")?;
+                if let Some(hef_path) = hef_path {
+                    write!(f, "{} ", hef_path)?;
+                }
+                write!(
+                    f,
+                    "was compiled with Dataflow Compiler {}, which HailoRT {} does not support; upgrade HailoRT",
+                    sdk_version, hailort_version
+                )?;
+                if let Some((major, minor)) = hailort_version.newest_compiler() {
+                    write!(f, " or recompile the HEF with Dataflow Compiler {}.{} or older", major, minor)?;
+                }
+                Ok(())
+            }
+            HailorsError::InvalidManifest(message) => write!(f, "Invalid model manifest: {}", message),
+            HailorsError::InvalidLabels(message) => write!(f, "Invalid label file: {}", message),
+            HailorsError::WorkerStopped => write!(f, "Inference worker stopped before completing the request"),
+            HailorsError::NoDevice => write!(f, "Backend does not run on a Hailo device"),
+            HailorsError::Io(err) => write!(f, "I/O error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for HailorsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HailorsError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for HailorsError {
+    fn from(err: std::io::Error) -> Self {
+        HailorsError::Io(err)
+    }
+}
+
+/// Converts a raw status returned over FFI into a `Result`.
+///
+/// Timeouts and aborts are returned as `HailorsError::Timeout` and `HailorsError::StreamAborted`
+/// so that callers can tell a hung stream from a failed one.
+pub(crate) fn check_status(status: i32, operation: Operation) -> Result<()> {
+    let status = HailoStatus::from_i32(status);
+    match status {
+        _ if status.is_success() => Ok(()),
+        HailoStatus::Timeout => Err(HailorsError::Timeout { operation, stream: None }),
+        HailoStatus::StreamAbort => Err(HailorsError::StreamAborted { operation, stream: None }),
+        _ => Err(HailorsError::Hailo {
+            operation,
+            status,
+            hef_path: None,
+            stream: None,
+        }),
+    }
+}
@@ -0,0 +1,464 @@
+//! Raw bindings to the C++ wrapper in `device_api_wrapper.cpp` and owning handle types.
+//!
+//! Every object handed out by the wrapper is owned by exactly one handle type below, whose
+//! `Drop` calls the matching release function. Owners must drop vstreams before the network
+//! group and the network group before the virtual device.
+
+use std::ffi::{c_char, c_void};
+use std::ptr;
+
+/// Maximum length of a stream name in HailoRT, including the null terminator.
+pub(crate) const MAX_STREAM_NAME_SIZE: usize = 128;
+
+/// Maximum length of a network group name in HailoRT, including the null terminator.
+pub(crate) const MAX_NETWORK_GROUP_NAME_SIZE: usize = 128;
+
+/// Maximum length of a device ID in HailoRT, including the null terminator.
+pub(crate) const MAX_DEVICE_ID_LENGTH: usize = 32;
+
+/// Mirror of `hailors_device_info` in `device_api_wrapper.hpp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub(crate) struct RawDeviceInfo {
+    pub id: [c_char; MAX_DEVICE_ID_LENGTH],
+    pub is_pcie: u32,
+    pub pcie_domain: u32,
+    pub pcie_bus: u32,
+    pub pcie_device: u32,
+    pub pcie_func: u32,
+    pub has_architecture: u32,
+    pub architecture: u32,
+}
+
+impl Default for RawDeviceInfo {
+    fn default() -> Self {
+        Self {
+            id: [0; MAX_DEVICE_ID_LENGTH],
+            is_pcie: 0,
+            pcie_domain: 0,
+            pcie_bus: 0,
+            pcie_device: 0,
+            pcie_func: 0,
+            has_architecture: 0,
+            architecture: 0,
+        }
+    }
+}
+
+/// Maximum length of the strings in `hailors_device_identity`, including the null terminator.
+pub(crate) const MAX_INFO_STRING_LENGTH: usize = 64;
+
+/// Mirror of `hailors_device_identity` in `device_api_wrapper.hpp`.
+#[repr(C)]
+pub(crate) struct RawDeviceIdentity {
+    pub device_id: [c_char; MAX_DEVICE_ID_LENGTH],
+    pub board_name: [c_char; MAX_INFO_STRING_LENGTH],
+    pub serial_number: [c_char; MAX_INFO_STRING_LENGTH],
+    pub part_number: [c_char; MAX_INFO_STRING_LENGTH],
+    pub product_name: [c_char; MAX_INFO_STRING_LENGTH],
+    pub fw_major: u32,
+    pub fw_minor: u32,
+    pub fw_revision: u32,
+    pub is_release: u32,
+    pub protocol_version: u32,
+    pub architecture: u32,
+    pub has_extended_info: u32,
+    pub nn_clock_rate: u32,
+    pub boot_source: u32,
+    pub lcs: u32,
+    pub soc_id: [u8; 32],
+    pub eth_mac_address: [u8; 6],
+}
+
+impl Default for RawDeviceIdentity {
+    fn default() -> Self {
+        Self {
+            device_id: [0; MAX_DEVICE_ID_LENGTH],
+            board_name: [0; MAX_INFO_STRING_LENGTH],
+            serial_number: [0; MAX_INFO_STRING_LENGTH],
+            part_number: [0; MAX_INFO_STRING_LENGTH],
+            product_name: [0; MAX_INFO_STRING_LENGTH],
+            fw_major: 0,
+            fw_minor: 0,
+            fw_revision: 0,
+            is_release: 0,
+            protocol_version: 0,
+            architecture: 0,
+            has_extended_info: 0,
+            nn_clock_rate: 0,
+            boot_source: 0,
+            lcs: 0,
+            soc_id: [0; 32],
+            eth_mac_address: [0; 6],
+        }
+    }
+}
+
+/// Mirror of `hailors_temperature_info` in `device_api_wrapper.hpp`.
+#[repr(C)]
+#[derive(Default)]
+pub(crate) struct RawTemperatureInfo {
+    pub ts0_temperature: f32,
+    pub ts1_temperature: f32,
+    pub sample_count: u32,
+}
+
+/// Mirror of `hailors_vdevice_params` in `device_api_wrapper.hpp`.
+#[repr(C)]
+pub(crate) struct RawVDeviceParams {
+    pub device_ids: *const *const c_char,
+    pub device_id_count: usize,
+    pub device_count: u32,
+    pub scheduling_algorithm: u32,
+    pub group_id: *const c_char,
+}
+
+/// Mirror of `hailors_network_group_name` in `device_api_wrapper.hpp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub(crate) struct RawNetworkGroupName {
+    pub name: [c_char; MAX_NETWORK_GROUP_NAME_SIZE],
+}
+
+impl Default for RawNetworkGroupName {
+    fn default() -> Self {
+        Self {
+            name: [0; MAX_NETWORK_GROUP_NAME_SIZE],
+        }
+    }
+}
+
+/// Mirror of `hailors_vstream_info` in `device_api_wrapper.hpp`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub(crate) struct RawVStreamInfo {
+    pub name: [c_char; MAX_STREAM_NAME_SIZE],
+    pub frame_size: usize,
+    pub height: u32,
+    pub width: u32,
+    pub features: u32,
+    pub format_type: u32,
+    pub format_order: u32,
+    pub qp_zp: f32,
+    pub qp_scale: f32,
+    pub limvals_min: f32,
+    pub limvals_max: f32,
+    pub is_nms: u32,
+    pub nms_number_of_classes: u32,
+    pub nms_max_bboxes_per_class: u32,
+}
+
+impl Default for RawVStreamInfo {
+    fn default() -> Self {
+        Self {
+            name: [0; MAX_STREAM_NAME_SIZE],
+            frame_size: 0,
+            height: 0,
+            width: 0,
+            features: 0,
+            format_type: 0,
+            format_order: 0,
+            qp_zp: 0.0,
+            qp_scale: 0.0,
+            limvals_min: 0.0,
+            limvals_max: 0.0,
+            is_nms: 0,
+            nms_number_of_classes: 0,
+            nms_max_bboxes_per_class: 0,
+        }
+    }
+}
+
+/// Mirror of `hailors_vstream_params` in `device_api_wrapper.hpp`.
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+pub(crate) struct RawVStreamParams {
+    pub format_type: u32,
+    pub quantized: u32,
+    pub timeout_ms: u32,
+    pub queue_size: u32,
+}
+
+/// Mirror of `hailors_named_vstream_params` in `device_api_wrapper.hpp`.
+#[repr(C)]
+pub(crate) struct RawNamedVStreamParams {
+    pub name: *const c_char,
+    pub params: RawVStreamParams,
+}
+
+/// Mirror of `hailors_configure_params` in `device_api_wrapper.hpp`.
+#[repr(C)]
+pub(crate) struct RawConfigureParams {
+    pub input: RawVStreamParams,
+    pub output: RawVStreamParams,
+    pub input_overrides: *const RawNamedVStreamParams,
+    pub input_override_count: usize,
+    pub output_overrides: *const RawNamedVStreamParams,
+    pub output_override_count: usize,
+    pub batch_size: u16,
+    pub network_group_name: *const c_char,
+}
+
+/// Owns a `VDevice` created by `hailors_create_vdevice`.
+pub(crate) struct VDeviceHandle(pub *mut c_void);
+
+impl Drop for VDeviceHandle {
+    fn drop(&mut self) {
+        if !self.0.is_null() {
+            unsafe {
+                hailors_release_vdevice(self.0);
+            }
+        }
+    }
+}
+
+// SAFETY: a HailoRT VDevice may be shared between threads; each network group configured on it
+// is driven through its own handles.
+unsafe impl Send for VDeviceHandle {}
+unsafe impl Sync for VDeviceHandle {}
+
+/// Owns a physical device opened by `hailors_open_device`.
+pub(crate) struct DeviceHandle(pub *mut c_void);
+
+impl Drop for DeviceHandle {
+    fn drop(&mut self) {
+        if !self.0.is_null() {
+            unsafe {
+                hailors_release_device(self.0);
+            }
+        }
+    }
+}
+
+/// Owns an activation of a network group returned by `hailors_activate_network_group`.
+pub(crate) struct ActivatedNetworkGroupHandle(pub *mut c_void);
+
+impl Drop for ActivatedNetworkGroupHandle {
+    fn drop(&mut self) {
+        if !self.0.is_null() {
+            unsafe {
+                hailors_deactivate_network_group(self.0);
+            }
+        }
+    }
+}
+
+/// Owns a configured network group returned by `hailors_configure_hef`.
+pub(crate) struct NetworkGroupHandle(pub *mut c_void);
+
+impl Drop for NetworkGroupHandle {
+    fn drop(&mut self) {
+        if !self.0.is_null() {
+            unsafe {
+                hailors_release_network_group(self.0);
+            }
+        }
+    }
+}
+
+/// Owns the `malloc`'d array of input vstreams returned by `hailors_configure_hef`.
+pub(crate) struct InputVStreams {
+    handles: *mut *mut c_void,
+    count: usize,
+}
+
+impl InputVStreams {
+    /// Takes ownership of an array of `count` input vstreams.
+    pub fn new(handles: *mut *mut c_void, count: usize) -> Self {
+        Self { handles, count }
+    }
+
+    /// Returns the number of vstreams in the array.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the vstream handle at `index`, if it exists.
+    pub fn get(&self, index: usize) -> Option<*mut c_void> {
+        if index < self.count && !self.handles.is_null() {
+            Some(unsafe { *self.handles.add(index) })
+        } else {
+            None
+        }
+    }
+}
+
+impl Drop for InputVStreams {
+    fn drop(&mut self) {
+        unsafe {
+            hailors_release_input_vstreams(self.handles, self.count);
+        }
+        self.handles = ptr::null_mut();
+    }
+}
+
+/// Owns the `malloc`'d array of output vstreams returned by `hailors_configure_hef`.
+pub(crate) struct OutputVStreams {
+    handles: *mut *mut c_void,
+    count: usize,
+}
+
+impl OutputVStreams {
+    /// Takes ownership of an array of `count` output vstreams.
+    pub fn new(handles: *mut *mut c_void, count: usize) -> Self {
+        Self { handles, count }
+    }
+
+    /// Returns the number of vstreams in the array.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the vstream handle at `index`, if it exists.
+    pub fn get(&self, index: usize) -> Option<*mut c_void> {
+        if index < self.count && !self.handles.is_null() {
+            Some(unsafe { *self.handles.add(index) })
+        } else {
+            None
+        }
+    }
+}
+
+impl Drop for OutputVStreams {
+    fn drop(&mut self) {
+        unsafe {
+            hailors_release_output_vstreams(self.handles, self.count);
+        }
+        self.handles = ptr::null_mut();
+    }
+}
+
+/// Receives the diagnostic messages of the C++ wrapper (`hailors_log_callback`).
+pub(crate) type LogCallback = extern "C" fn(level: i32, message: *const c_char);
+
+extern "C" {
+    /// Routes the diagnostic messages of the C++ wrapper to `callback`.
+    pub(crate) fn hailors_set_log_callback(callback: Option<LogCallback>);
+
+    /// Returns the version of the linked HailoRT library.
+    pub(crate) fn hailors_get_library_version(major: *mut u32, minor: *mut u32, revision: *mut u32) -> i32;
+
+    /// Creates a Hailo virtual device.
+    pub(crate) fn hailors_create_vdevice(device_handle: *mut *mut c_void) -> i32;
+
+    /// Creates a Hailo virtual device with the given parameters.
+    pub(crate) fn hailors_create_vdevice_with_params(
+        params: *const RawVDeviceParams,
+        device_handle: *mut *mut c_void,
+    ) -> i32;
+
+    /// Lists the physical Hailo devices.
+    pub(crate) fn hailors_scan_devices(devices: *mut RawDeviceInfo, max_count: usize, count: *mut usize) -> i32;
+
+    /// Opens a physical device by its ID.
+    pub(crate) fn hailors_open_device(device_id: *const c_char, device: *mut *mut c_void) -> i32;
+
+    /// Releases a physical device opened by `hailors_open_device`.
+    pub(crate) fn hailors_release_device(device: *mut c_void) -> i32;
+
+    /// Lists the physical devices of a virtual device, which keeps ownership of them.
+    pub(crate) fn hailors_get_physical_devices(
+        vdevice: *mut c_void,
+        devices: *mut *mut c_void,
+        max_count: usize,
+        count: *mut usize,
+    ) -> i32;
+
+    /// Reads the board identity, firmware version and extended information of a physical device.
+    pub(crate) fn hailors_identify_device(device: *mut c_void, identity: *mut RawDeviceIdentity) -> i32;
+
+    /// Copies the ID of a physical device, without querying its firmware.
+    pub(crate) fn hailors_get_device_id(device: *mut c_void, device_id: *mut c_char, device_id_size: usize) -> i32;
+
+    /// Reads the chip temperature of a physical device.
+    pub(crate) fn hailors_get_chip_temperature(device: *mut c_void, temperature: *mut RawTemperatureInfo) -> i32;
+
+    /// Takes one power measurement of a physical device, in watts.
+    pub(crate) fn hailors_measure_power(device: *mut c_void, power: *mut f32) -> i32;
+
+    /// Releases a Hailo virtual device.
+    pub(crate) fn hailors_release_vdevice(device_handle: *mut c_void) -> i32;
+
+    /// Configures a Hailo Execution File (HEF) and sets up virtual streams.
+    pub(crate) fn hailors_configure_hef(
+        device_handle: *mut c_void,
+        hef_path: *const c_char,
+        params: *const RawConfigureParams,
+        network_group: *mut *mut c_void,
+        input_vstreams: *mut *mut *mut c_void,
+        input_count: *mut usize,
+        output_vstreams: *mut *mut *mut c_void,
+        output_count: *mut usize,
+        input_frame_size: *mut usize,
+        output_frame_size: *mut usize,
+    ) -> i32;
+
+    /// Lists the network groups in a HEF.
+    pub(crate) fn hailors_get_network_group_names(
+        hef_path: *const c_char,
+        names: *mut RawNetworkGroupName,
+        max_count: usize,
+        count: *mut usize,
+    ) -> i32;
+
+    /// Gets the name of a configured network group.
+    pub(crate) fn hailors_get_network_group_name(network_group: *mut c_void, name: *mut RawNetworkGroupName) -> i32;
+
+    /// Activates a configured network group.
+    pub(crate) fn hailors_activate_network_group(network_group: *mut c_void, activated: *mut *mut c_void) -> i32;
+
+    /// Deactivates a network group.
+    pub(crate) fn hailors_deactivate_network_group(activated: *mut c_void) -> i32;
+
+    /// Releases a configured network group.
+    pub(crate) fn hailors_release_network_group(network_group: *mut c_void) -> i32;
+
+    /// Releases an array of input virtual streams and the array itself.
+    pub(crate) fn hailors_release_input_vstreams(input_vstreams: *mut *mut c_void, input_count: usize) -> i32;
+
+    /// Releases an array of output virtual streams and the array itself.
+    pub(crate) fn hailors_release_output_vstreams(output_vstreams: *mut *mut c_void, output_count: usize) -> i32;
+
+    /// Gets the name, shape, format, quantization and NMS parameters of an input virtual stream.
+    pub(crate) fn hailors_get_input_vstream_info(input_vstream: *mut c_void, info: *mut RawVStreamInfo) -> i32;
+
+    /// Gets the name, shape, format, quantization and NMS parameters of an output virtual stream.
+    pub(crate) fn hailors_get_output_vstream_info(output_vstream: *mut c_void, info: *mut RawVStreamInfo) -> i32;
+
+    /// Writes a frame to the input virtual stream.
+    pub(crate) fn hailors_write_input_frame(input_vstream: *mut c_void, data: *const c_void, len: usize) -> i32;
+
+    /// Blocks until every frame written to the input virtual stream has been sent to the device.
+    pub(crate) fn hailors_flush_input_vstream(input_vstream: *mut c_void) -> i32;
+
+    /// Reads data from the output virtual stream.
+    pub(crate) fn hailors_read_output_frame(output_vstream: *mut c_void, data: *mut c_void, len: usize) -> i32;
+
+    /// Writes a frame to the input virtual stream, aborting the write after `timeout_ms`.
+    pub(crate) fn hailors_write_input_frame_timeout(
+        input_vstream: *mut c_void,
+        data: *const c_void,
+        len: usize,
+        timeout_ms: u32,
+    ) -> i32;
+
+    /// Reads a frame from the output virtual stream, aborting the read after `timeout_ms`.
+    pub(crate) fn hailors_read_output_frame_timeout(
+        output_vstream: *mut c_void,
+        data: *mut c_void,
+        len: usize,
+        timeout_ms: u32,
+    ) -> i32;
+
+    /// Aborts pending and future writes to the input virtual stream until it is resumed.
+    pub(crate) fn hailors_abort_input_vstream(input_vstream: *mut c_void) -> i32;
+
+    /// Resumes an aborted input virtual stream.
+    pub(crate) fn hailors_resume_input_vstream(input_vstream: *mut c_void) -> i32;
+
+    /// Aborts pending and future reads from the output virtual stream until it is resumed.
+    pub(crate) fn hailors_abort_output_vstream(output_vstream: *mut c_void) -> i32;
+
+    /// Resumes an aborted output virtual stream.
+    pub(crate) fn hailors_resume_output_vstream(output_vstream: *mut c_void) -> i32;
+}
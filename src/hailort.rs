@@ -0,0 +1,459 @@
+use std::ffi::{CStr, CString, c_void};
+use std::ptr;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::backend::InferenceBackend;
+use crate::config::{DeviceConfig, StreamConfig};
+use crate::error::{check_status, HailorsError, Operation, Result};
+use crate::ffi::{
+    self, ActivatedNetworkGroupHandle, InputVStreams, NetworkGroupHandle, OutputVStreams, RawNetworkGroupName,
+    RawVStreamInfo,
+};
+use crate::status::HailoStatus;
+use crate::vdevice::VDevice;
+use crate::vstream::{
+    FormatOrder, FormatType, NmsShape, QuantInfo, StreamDirection, VStreamFormat, VStreamInfo, VStreamShape,
+};
+
+/// Inference backend that runs on Hailo hardware through the HailoRT C++ wrapper.
+///
+/// Fields are declared in release order: the network group is deactivated first, vstreams are
+/// dropped before the network group they belong to, and the network group before the virtual
+/// device it is configured on.
+pub struct HailoRtBackend {
+    /// Activation of the network group, when it is switched manually.
+    activation: Mutex<Option<ActivatedNetworkGroupHandle>>,
+    /// Input virtual streams.
+    input_vstreams: InputVStreams,
+    /// Output virtual streams.
+    output_vstreams: OutputVStreams,
+    /// Configured network group.
+    network_group: NetworkGroupHandle,
+    /// Virtual device the network group is configured on, shared with other network groups.
+    vdevice: VDevice,
+    /// Name of the configured network group.
+    network_group_name: String,
+    /// Information about each input virtual stream.
+    inputs: Vec<VStreamInfo>,
+    /// Information about each output virtual stream.
+    outputs: Vec<VStreamInfo>,
+    /// Serializes access to each input vstream.
+    input_locks: Vec<Mutex<()>>,
+    /// Serializes access to each output vstream.
+    output_locks: Vec<Mutex<()>>,
+}
+
+// SAFETY: the backend exclusively owns its network group and vstreams, shares the VDevice only
+// through its thread-safe handle, and HailoRT objects are not bound to the thread that created
+// them, so moving the backend to another thread is sound.
+unsafe impl Send for HailoRtBackend {}
+
+// SAFETY: each vstream is only accessed while holding its own lock, and HailoRT supports
+// writing and reading different vstreams of a network group from different threads.
+unsafe impl Sync for HailoRtBackend {}
+
+impl HailoRtBackend {
+    /// Creates a virtual device and configures it with the provided HEF file.
+    ///
+    /// # Arguments
+    ///
+    /// * `hef_path` - Path to the Hailo Execution File (HEF).
+    ///
+    /// # Returns
+    ///
+    /// Returns a `HailoRtBackend` instance on success or an error on failure.
+    pub fn new(hef_path: &str) -> Result<Self> {
+        Self::with_config(hef_path, &DeviceConfig::default())
+    }
+
+    /// Creates a virtual device and configures it with the provided HEF file and vstream parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `hef_path` - Path to the Hailo Execution File (HEF).
+    /// * `config` - Virtual device parameters, network group selection and vstream parameters.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `HailoRtBackend` instance on success or an error on failure.
+    pub fn with_config(hef_path: &str, config: &DeviceConfig) -> Result<Self> {
+        Self::configure(&VDevice::with_params(&config.vdevice)?, hef_path, config)
+    }
+
+    /// Configures a network group of a HEF on an existing virtual device.
+    ///
+    /// # Arguments
+    ///
+    /// * `vdevice` - The virtual device, which may already run other network groups.
+    /// * `hef_path` - Path to the Hailo Execution File (HEF).
+    /// * `config` - Network group selection and vstream parameters.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `HailoRtBackend` instance on success or an error on failure.
+    pub fn configure(vdevice: &VDevice, hef_path: &str, config: &DeviceConfig) -> Result<Self> {
+        let _span = tracing::info_span!("configure", hef_path, network_group = config.network_group.as_deref())
+            .entered();
+        let hef_path_cstr = CString::new(hef_path)
+            .map_err(|_| HailorsError::InvalidArgument(format!("HEF path contains a nul byte: {}", hef_path)))?;
+        if config.check_compatibility {
+            vdevice.check_hef(hef_path)?;
+        }
+
+        // The names must outlive the configure call, which only borrows them
+        let input_names = stream_names(&config.input_streams)?;
+        let output_names = stream_names(&config.output_streams)?;
+        let network_group_name = config
+            .network_group
+            .as_deref()
+            .map(|name| {
+                CString::new(name).map_err(|_| {
+                    HailorsError::InvalidArgument(format!("Network group name contains a nul byte: {}", name))
+                })
+            })
+            .transpose()?;
+        let input_overrides = named_stream_params(&config.input_streams, &input_names);
+        let output_overrides = named_stream_params(&config.output_streams, &output_names);
+        let params = ffi::RawConfigureParams {
+            input: raw_stream_params(&config.input),
+            output: raw_stream_params(&config.output),
+            input_overrides: input_overrides.as_ptr(),
+            input_override_count: input_overrides.len(),
+            output_overrides: output_overrides.as_ptr(),
+            output_override_count: output_overrides.len(),
+            batch_size: config.batch_size.unwrap_or(0),
+            network_group_name: network_group_name.as_ref().map_or(ptr::null(), |name| name.as_ptr()),
+        };
+
+        // Each handle is wrapped as soon as it is created, so every early return below
+        // releases whatever has been created so far.
+        let mut network_group: *mut c_void = ptr::null_mut();
+        let mut input_vstreams: *mut *mut c_void = ptr::null_mut();
+        let mut output_vstreams: *mut *mut c_void = ptr::null_mut();
+        let mut input_count: usize = 0;
+        let mut output_count: usize = 0;
+        let mut input_frame_size: usize = 0;
+        let mut output_frame_size: usize = 0;
+
+        // Call FFI function to configure the HEF and virtual streams
+        let status = unsafe {
+            ffi::hailors_configure_hef(
+                vdevice.handle().0,
+                hef_path_cstr.as_ptr(),
+                &params,
+                &mut network_group,
+                &mut input_vstreams,
+                &mut input_count,
+                &mut output_vstreams,
+                &mut output_count,
+                &mut input_frame_size,
+                &mut output_frame_size,
+            )
+        };
+        let network_group = NetworkGroupHandle(network_group);
+        let input_vstreams = InputVStreams::new(input_vstreams, input_count);
+        let output_vstreams = OutputVStreams::new(output_vstreams, output_count);
+        check_status(status, Operation::ConfigureHef).map_err(|err| err.with_hef_path(hef_path))?;
+
+        if network_group.0.is_null() || input_vstreams.get(0).is_none() || output_vstreams.get(0).is_none() {
+            return Err(HailorsError::Hailo {
+                operation: Operation::ConfigureHef,
+                status: HailoStatus::InvalidOperation,
+                hef_path: Some(hef_path.to_string()),
+                stream: None,
+            });
+        }
+
+        let mut raw_name = RawNetworkGroupName::default();
+        let status = unsafe { ffi::hailors_get_network_group_name(network_group.0, &mut raw_name) };
+        check_status(status, Operation::ConfigureHef).map_err(|err| err.with_hef_path(hef_path))?;
+        let network_group_name = unsafe { CStr::from_ptr(raw_name.name.as_ptr()) }
+            .to_string_lossy()
+            .into_owned();
+
+        // Collect the metadata of every stream
+        let inputs = (0..input_vstreams.len())
+            .map(|index| {
+                let vstream = input_vstreams.get(index).unwrap_or(ptr::null_mut());
+                read_vstream_info(|raw| unsafe { ffi::hailors_get_input_vstream_info(vstream, raw) })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let outputs = (0..output_vstreams.len())
+            .map(|index| {
+                let vstream = output_vstreams.get(index).unwrap_or(ptr::null_mut());
+                read_vstream_info(|raw| unsafe { ffi::hailors_get_output_vstream_info(vstream, raw) })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        tracing::info!(
+            network_group = %network_group_name,
+            inputs = inputs.len(),
+            outputs = outputs.len(),
+            "Configured network group"
+        );
+
+        Ok(Self {
+            activation: Mutex::new(None),
+            input_vstreams,
+            output_vstreams,
+            network_group,
+            vdevice: vdevice.clone(),
+            network_group_name,
+            input_locks: inputs.iter().map(|_| Mutex::new(())).collect(),
+            output_locks: outputs.iter().map(|_| Mutex::new(())).collect(),
+            inputs,
+            outputs,
+        })
+    }
+
+    /// Returns the name of the configured network group.
+    pub fn network_group_name(&self) -> &str {
+        &self.network_group_name
+    }
+
+    /// Returns the handle of the input vstream at `index`.
+    fn input_vstream(&self, index: usize) -> Result<*mut c_void> {
+        self.input_vstreams.get(index).ok_or(HailorsError::StreamIndexOutOfRange {
+            direction: StreamDirection::Input,
+            index,
+            count: self.input_vstreams.len(),
+        })
+    }
+
+    /// Returns the handle of the output vstream at `index`.
+    fn output_vstream(&self, index: usize) -> Result<*mut c_void> {
+        self.output_vstreams.get(index).ok_or(HailorsError::StreamIndexOutOfRange {
+            direction: StreamDirection::Output,
+            index,
+            count: self.output_vstreams.len(),
+        })
+    }
+}
+
+impl InferenceBackend for HailoRtBackend {
+    fn inputs(&self) -> &[VStreamInfo] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[VStreamInfo] {
+        &self.outputs
+    }
+
+    fn network_group(&self) -> Option<&str> {
+        Some(&self.network_group_name)
+    }
+
+    fn vdevice(&self) -> Option<&VDevice> {
+        Some(&self.vdevice)
+    }
+
+    fn activate(&self) -> Result<()> {
+        let mut activation = self.activation.lock().unwrap_or_else(|err| err.into_inner());
+        if activation.is_some() {
+            return Ok(());
+        }
+        let mut activated: *mut c_void = ptr::null_mut();
+        let status = unsafe { ffi::hailors_activate_network_group(self.network_group.0, &mut activated) };
+        let handle = ActivatedNetworkGroupHandle(activated);
+        check_status(status, Operation::ActivateNetworkGroup)?;
+        *activation = Some(handle);
+        Ok(())
+    }
+
+    fn deactivate(&self) -> Result<()> {
+        self.activation.lock().unwrap_or_else(|err| err.into_inner()).take();
+        Ok(())
+    }
+
+    fn write_input(&self, index: usize, frame: &[u8]) -> Result<()> {
+        let vstream = self.input_vstream(index)?;
+        let _span = tracing::trace_span!("write_input", stream = %self.inputs[index].name).entered();
+        let _guard = self.input_locks[index].lock().unwrap_or_else(|err| err.into_inner());
+        let status = unsafe { ffi::hailors_write_input_frame(vstream, frame.as_ptr() as *const c_void, frame.len()) };
+        check_status(status, Operation::WriteInput).map_err(|err| err.with_stream(&self.inputs[index].name))
+    }
+
+    fn flush_input(&self, index: usize) -> Result<()> {
+        let vstream = self.input_vstream(index)?;
+        let _span = tracing::trace_span!("flush_input", stream = %self.inputs[index].name).entered();
+        let _guard = self.input_locks[index].lock().unwrap_or_else(|err| err.into_inner());
+        let status = unsafe { ffi::hailors_flush_input_vstream(vstream) };
+        check_status(status, Operation::FlushInput).map_err(|err| err.with_stream(&self.inputs[index].name))
+    }
+
+    fn read_output(&self, index: usize, buffer: &mut [u8]) -> Result<()> {
+        let vstream = self.output_vstream(index)?;
+        let _span = tracing::trace_span!("read_output", stream = %self.outputs[index].name).entered();
+        let _guard = self.output_locks[index].lock().unwrap_or_else(|err| err.into_inner());
+        let status = unsafe {
+            ffi::hailors_read_output_frame(
+                vstream,
+                buffer.as_mut_ptr() as *mut c_void,
+                buffer.len(),
+            )
+        };
+        check_status(status, Operation::ReadOutput).map_err(|err| err.with_stream(&self.outputs[index].name))
+    }
+
+    fn write_input_timeout(&self, index: usize, frame: &[u8], timeout: Duration) -> Result<()> {
+        let vstream = self.input_vstream(index)?;
+        let _span = tracing::trace_span!("write_input", stream = %self.inputs[index].name, ?timeout).entered();
+        let _guard = self.input_locks[index].lock().unwrap_or_else(|err| err.into_inner());
+        let status = unsafe {
+            ffi::hailors_write_input_frame_timeout(
+                vstream,
+                frame.as_ptr() as *const c_void,
+                frame.len(),
+                timeout_ms(timeout),
+            )
+        };
+        check_status(status, Operation::WriteInput).map_err(|err| err.with_stream(&self.inputs[index].name))
+    }
+
+    fn read_output_timeout(&self, index: usize, buffer: &mut [u8], timeout: Duration) -> Result<()> {
+        let vstream = self.output_vstream(index)?;
+        let _span = tracing::trace_span!("read_output", stream = %self.outputs[index].name, ?timeout).entered();
+        let _guard = self.output_locks[index].lock().unwrap_or_else(|err| err.into_inner());
+        let status = unsafe {
+            ffi::hailors_read_output_frame_timeout(
+                vstream,
+                buffer.as_mut_ptr() as *mut c_void,
+                buffer.len(),
+                timeout_ms(timeout),
+            )
+        };
+        check_status(status, Operation::ReadOutput).map_err(|err| err.with_stream(&self.outputs[index].name))
+    }
+
+    // Abort and resume deliberately skip the stream locks, which are held by the blocked
+    // writes and reads they unblock. HailoRT allows aborting a vstream from another thread.
+    fn abort(&self) -> Result<()> {
+        tracing::warn!(network_group = %self.network_group_name, "Aborting vstreams");
+        for (index, info) in self.inputs.iter().enumerate() {
+            let status = unsafe { ffi::hailors_abort_input_vstream(self.input_vstream(index)?) };
+            check_status(status, Operation::AbortStreams).map_err(|err| err.with_stream(&info.name))?;
+        }
+        for (index, info) in self.outputs.iter().enumerate() {
+            let status = unsafe { ffi::hailors_abort_output_vstream(self.output_vstream(index)?) };
+            check_status(status, Operation::AbortStreams).map_err(|err| err.with_stream(&info.name))?;
+        }
+        Ok(())
+    }
+
+    fn resume(&self) -> Result<()> {
+        tracing::info!(network_group = %self.network_group_name, "Resuming vstreams");
+        for (index, info) in self.inputs.iter().enumerate() {
+            let status = unsafe { ffi::hailors_resume_input_vstream(self.input_vstream(index)?) };
+            check_status(status, Operation::ResumeStreams).map_err(|err| err.with_stream(&info.name))?;
+        }
+        for (index, info) in self.outputs.iter().enumerate() {
+            let status = unsafe { ffi::hailors_resume_output_vstream(self.output_vstream(index)?) };
+            check_status(status, Operation::ResumeStreams).map_err(|err| err.with_stream(&info.name))?;
+        }
+        Ok(())
+    }
+}
+
+/// Lists the names of the network groups in a HEF, in the order they are stored.
+///
+/// # Arguments
+///
+/// * `hef_path` - Path to the Hailo Execution File (HEF).
+pub fn network_group_names(hef_path: &str) -> Result<Vec<String>> {
+    let hef_path_cstr = CString::new(hef_path)
+        .map_err(|_| HailorsError::InvalidArgument(format!("HEF path contains a nul byte: {}", hef_path)))?;
+
+    let mut names = vec![RawNetworkGroupName::default(); 8];
+    loop {
+        let mut count: usize = 0;
+        let status = unsafe {
+            ffi::hailors_get_network_group_names(hef_path_cstr.as_ptr(), names.as_mut_ptr(), names.len(), &mut count)
+        };
+        check_status(status, Operation::ListNetworkGroups).map_err(|err| err.with_hef_path(hef_path))?;
+
+        // Retry with room for every name if the HEF holds more than fit
+        if count > names.len() {
+            names = vec![RawNetworkGroupName::default(); count];
+            continue;
+        }
+        return Ok(names[..count]
+            .iter()
+            .map(|raw| unsafe { CStr::from_ptr(raw.name.as_ptr()) }.to_string_lossy().into_owned())
+            .collect());
+    }
+}
+
+/// Converts per-stream parameter names into C strings.
+fn stream_names(streams: &[(String, StreamConfig)]) -> Result<Vec<CString>> {
+    streams
+        .iter()
+        .map(|(name, _)| {
+            CString::new(name.as_str())
+                .map_err(|_| HailorsError::InvalidArgument(format!("Stream name contains a nul byte: {}", name)))
+        })
+        .collect()
+}
+
+/// Pairs per-stream parameters with their C string names.
+fn named_stream_params(streams: &[(String, StreamConfig)], names: &[CString]) -> Vec<ffi::RawNamedVStreamParams> {
+    streams
+        .iter()
+        .zip(names)
+        .map(|((_, config), name)| ffi::RawNamedVStreamParams {
+            name: name.as_ptr(),
+            params: raw_stream_params(config),
+        })
+        .collect()
+}
+
+/// Converts stream parameters to their FFI representation, where zero selects the HailoRT default.
+fn raw_stream_params(config: &StreamConfig) -> ffi::RawVStreamParams {
+    ffi::RawVStreamParams {
+        format_type: config.format_type.as_u32(),
+        quantized: config.quantized as u32,
+        timeout_ms: config.timeout.map_or(0, timeout_ms),
+        queue_size: config.queue_size.unwrap_or(0),
+    }
+}
+
+/// Converts a timeout to whole milliseconds for FFI, where zero would mean no timeout.
+fn timeout_ms(timeout: Duration) -> u32 {
+    timeout.as_millis().clamp(1, u32::MAX as u128) as u32
+}
+
+/// Fetches stream information through one of the vstream info getters.
+fn read_vstream_info<F>(getter: F) -> Result<VStreamInfo>
+where
+    F: FnOnce(*mut RawVStreamInfo) -> i32,
+{
+    let mut raw = RawVStreamInfo::default();
+    check_status(getter(&mut raw), Operation::GetVStreamInfo)?;
+
+    let name = unsafe { CStr::from_ptr(raw.name.as_ptr()) };
+    let nms = (raw.is_nms != 0).then_some(NmsShape {
+        number_of_classes: raw.nms_number_of_classes,
+        max_bboxes_per_class: raw.nms_max_bboxes_per_class,
+    });
+    let shape = match nms {
+        // NMS frames have no image shape; describe them as one flat row of elements
+        Some(_) => VStreamShape::new(1, 1, (raw.frame_size / 4) as u32),
+        None => VStreamShape::new(raw.height, raw.width, raw.features),
+    };
+
+    Ok(VStreamInfo {
+        name: name.to_string_lossy().into_owned(),
+        frame_size: raw.frame_size,
+        shape,
+        format: VStreamFormat {
+            format_type: FormatType::from_u32(raw.format_type),
+            order: FormatOrder::from_u32(raw.format_order),
+        },
+        quant: QuantInfo {
+            zero_point: raw.qp_zp,
+            scale: raw.qp_scale,
+            limvals_min: raw.limvals_min,
+            limvals_max: raw.limvals_max,
+        },
+        nms,
+    })
+}
@@ -0,0 +1,345 @@
+use std::ffi::{c_char, c_void, CStr, CString};
+use std::ptr;
+
+use serde::{Serialize, Serializer};
+
+use crate::device::{DeviceArchitecture, PhysicalDevice};
+use crate::error::{check_status, HailorsError, Operation, Result};
+use crate::ffi::{self, DeviceHandle, RawDeviceIdentity, RawTemperatureInfo};
+use crate::status::HailoStatus;
+use crate::vdevice::VDevice;
+
+/// Firmware version of a device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FirmwareVersion {
+    /// Major version.
+    pub major: u32,
+    /// Minor version.
+    pub minor: u32,
+    /// Revision.
+    pub revision: u32,
+}
+
+impl std::fmt::Display for FirmwareVersion {
+    /// Formats the version as `major.minor.revision`, e.g. `4.19.0`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.revision)
+    }
+}
+
+/// Where a device loaded its firmware from (`hailo_device_boot_source_t`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BootSource {
+    /// The firmware was loaded by the host over PCIe.
+    Pcie,
+    /// The firmware was loaded from the on-board flash.
+    Flash,
+    /// A boot source this crate does not know about.
+    Unknown(u32),
+}
+
+impl BootSource {
+    /// Converts a raw `hailo_device_boot_source_t` value into a `BootSource`.
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            1 => BootSource::Pcie,
+            2 => BootSource::Flash,
+            other => BootSource::Unknown(other),
+        }
+    }
+}
+
+impl std::fmt::Display for BootSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BootSource::Pcie => write!(f, "PCIE"),
+            BootSource::Flash => write!(f, "FLASH"),
+            BootSource::Unknown(value) => write!(f, "UNKNOWN({})", value),
+        }
+    }
+}
+
+/// Information that only some devices report, from HailoRT's extended device information.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExtendedDeviceInfo {
+    /// Clock rate of the neural network core, in Hz.
+    pub nn_clock_rate_hz: u32,
+    /// Where the firmware was loaded from.
+    #[serde(serialize_with = "serialize_display")]
+    pub boot_source: BootSource,
+    /// Life cycle state of the chip.
+    pub lcs: u32,
+    /// Unique ID of the chip, as lowercase hex.
+    pub soc_id: String,
+    /// Ethernet MAC address, e.g. `00:11:22:33:44:55`.
+    pub eth_mac_address: String,
+}
+
+/// Board identity and firmware of a physical device.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DeviceInfo {
+    /// Device ID, as accepted by `VDeviceParams::with_device_ids`.
+    pub device_id: String,
+    /// Board name.
+    pub board_name: String,
+    /// Board serial number.
+    pub serial_number: String,
+    /// Board part number.
+    pub part_number: String,
+    /// Product name.
+    pub product_name: String,
+    /// Version of the running firmware.
+    #[serde(serialize_with = "serialize_display")]
+    pub firmware_version: FirmwareVersion,
+    /// Whether the running firmware is a release build.
+    pub release_firmware: bool,
+    /// Version of the control protocol between HailoRT and the firmware.
+    pub protocol_version: u32,
+    /// Device architecture.
+    #[serde(serialize_with = "serialize_display")]
+    pub architecture: DeviceArchitecture,
+    /// Extended information, if the device reports it.
+    pub extended: Option<ExtendedDeviceInfo>,
+}
+
+/// Chip temperature read from the two on-die sensors.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ChipTemperature {
+    /// Temperature of sensor 0, in degrees Celsius.
+    pub ts0_celsius: f32,
+    /// Temperature of sensor 1, in degrees Celsius.
+    pub ts1_celsius: f32,
+    /// Number of samples the firmware averaged.
+    pub sample_count: u32,
+}
+
+impl ChipTemperature {
+    /// Returns the hotter of the two sensor readings, in degrees Celsius.
+    pub fn max_celsius(&self) -> f32 {
+        self.ts0_celsius.max(self.ts1_celsius)
+    }
+}
+
+/// Power consumption sampled over a number of measurements.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct PowerMeasurement {
+    /// Number of measurements taken.
+    pub samples: u32,
+    /// Average power, in watts.
+    pub average_watts: f32,
+    /// Lowest measured power, in watts.
+    pub min_watts: f32,
+    /// Highest measured power, in watts.
+    pub max_watts: f32,
+}
+
+impl PowerMeasurement {
+    /// Summarizes a set of power measurements in watts, or returns `None` if there are none.
+    pub fn from_samples(samples: &[f32]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        Some(Self {
+            samples: samples.len() as u32,
+            average_watts: samples.iter().sum::<f32>() / samples.len() as f32,
+            min_watts: samples.iter().copied().fold(f32::INFINITY, f32::min),
+            max_watts: samples.iter().copied().fold(f32::NEG_INFINITY, f32::max),
+        })
+    }
+}
+
+/// Health snapshot of a physical device.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeviceHealth {
+    /// Device ID, as accepted by `VDeviceParams::with_device_ids`.
+    pub device_id: String,
+    /// Chip temperature.
+    pub temperature: ChipTemperature,
+    /// Power consumption, or `None` if no samples were requested or the board has no power
+    /// sensor, as is the case for most M.2 modules.
+    pub power: Option<PowerMeasurement>,
+}
+
+impl PhysicalDevice {
+    /// Reads the board identity and firmware of this device.
+    ///
+    /// The device is opened by its ID for the duration of the call, which works while another
+    /// process runs inference on it.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// for device in hailors::devices().expect("Failed to scan devices") {
+    ///     let info = device.info().expect("Failed to identify device");
+    ///     println!("{}: {} firmware {}", info.device_id, info.board_name, info.firmware_version);
+    /// }
+    /// ```
+    pub fn info(&self) -> Result<DeviceInfo> {
+        let device = open_device(&self.id)?;
+        device_info(device.0)
+    }
+
+    /// Reads the temperature of this device and samples its power consumption.
+    ///
+    /// # Arguments
+    ///
+    /// * `power_samples` - Number of power measurements to average; 0 skips power measurement.
+    pub fn health(&self, power_samples: u32) -> Result<DeviceHealth> {
+        let device = open_device(&self.id)?;
+        device_health(device.0, self.id.clone(), power_samples)
+    }
+}
+
+impl VDevice {
+    /// Reads the board identity and firmware of every physical device of this virtual device.
+    pub fn device_info(&self) -> Result<Vec<DeviceInfo>> {
+        self.physical_devices()?.into_iter().map(device_info).collect()
+    }
+
+    /// Reads the temperature and samples the power consumption of every physical device of this
+    /// virtual device.
+    ///
+    /// # Arguments
+    ///
+    /// * `power_samples` - Number of power measurements to average per device; 0 skips power
+    ///   measurement.
+    pub fn health(&self, power_samples: u32) -> Result<Vec<DeviceHealth>> {
+        self.physical_devices()?
+            .into_iter()
+            .map(|device| device_health(device, device_id(device)?, power_samples))
+            .collect()
+    }
+
+    /// Returns the physical devices of this virtual device, which stay owned by it.
+    fn physical_devices(&self) -> Result<Vec<*mut c_void>> {
+        let mut devices = vec![ptr::null_mut(); 4];
+        loop {
+            let mut count: usize = 0;
+            let status = unsafe {
+                ffi::hailors_get_physical_devices(self.handle().0, devices.as_mut_ptr(), devices.len(), &mut count)
+            };
+            check_status(status, Operation::OpenDevice)?;
+
+            // Retry with room for every device if the virtual device spans more than fit
+            if count > devices.len() {
+                devices = vec![ptr::null_mut(); count];
+                continue;
+            }
+            devices.truncate(count);
+            return Ok(devices);
+        }
+    }
+}
+
+/// Opens a physical device by its ID.
+fn open_device(device_id: &str) -> Result<DeviceHandle> {
+    let device_id_cstr = CString::new(device_id)
+        .map_err(|_| HailorsError::InvalidArgument(format!("Device ID contains a nul byte: {}", device_id)))?;
+    let mut device: *mut c_void = ptr::null_mut();
+    let status = unsafe { ffi::hailors_open_device(device_id_cstr.as_ptr(), &mut device) };
+    let device = DeviceHandle(device);
+    check_status(status, Operation::OpenDevice)?;
+    Ok(device)
+}
+
+/// Reads the identity of an open physical device.
+fn device_info(device: *mut c_void) -> Result<DeviceInfo> {
+    let mut raw = RawDeviceIdentity::default();
+    let status = unsafe { ffi::hailors_identify_device(device, &mut raw) };
+    check_status(status, Operation::IdentifyDevice)?;
+
+    let extended = (raw.has_extended_info != 0).then(|| ExtendedDeviceInfo {
+        nn_clock_rate_hz: raw.nn_clock_rate,
+        boot_source: BootSource::from_u32(raw.boot_source),
+        lcs: raw.lcs,
+        soc_id: raw.soc_id.iter().map(|byte| format!("{:02x}", byte)).collect(),
+        eth_mac_address: raw
+            .eth_mac_address
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<Vec<_>>()
+            .join(":"),
+    });
+    Ok(DeviceInfo {
+        device_id: c_string(&raw.device_id),
+        board_name: c_string(&raw.board_name),
+        serial_number: c_string(&raw.serial_number),
+        part_number: c_string(&raw.part_number),
+        product_name: c_string(&raw.product_name),
+        firmware_version: FirmwareVersion {
+            major: raw.fw_major,
+            minor: raw.fw_minor,
+            revision: raw.fw_revision,
+        },
+        release_firmware: raw.is_release != 0,
+        protocol_version: raw.protocol_version,
+        architecture: DeviceArchitecture::from_u32(raw.architecture),
+        extended,
+    })
+}
+
+/// Returns the ID of an open physical device.
+fn device_id(device: *mut c_void) -> Result<String> {
+    let mut device_id: [c_char; ffi::MAX_DEVICE_ID_LENGTH] = [0; ffi::MAX_DEVICE_ID_LENGTH];
+    let status = unsafe { ffi::hailors_get_device_id(device, device_id.as_mut_ptr(), device_id.len()) };
+    check_status(status, Operation::IdentifyDevice)?;
+    Ok(c_string(&device_id))
+}
+
+/// Reads the temperature and samples the power consumption of an open physical device, whose ID
+/// the caller already knows.
+fn device_health(device: *mut c_void, device_id: String, power_samples: u32) -> Result<DeviceHealth> {
+    let mut raw_temperature = RawTemperatureInfo::default();
+    let status = unsafe { ffi::hailors_get_chip_temperature(device, &mut raw_temperature) };
+    check_status(status, Operation::GetChipTemperature)?;
+
+    // Boards without a power sensor reject the first measurement; report that as no power
+    // reading rather than failing the whole snapshot. Any later failure means the device stopped
+    // answering mid-measurement and is returned as an error
+    let mut samples = Vec::with_capacity(power_samples as usize);
+    for sample in 0..power_samples {
+        let mut watts: f32 = 0.0;
+        let status = unsafe { ffi::hailors_measure_power(device, &mut watts) };
+        if sample == 0 && lacks_power_sensor(HailoStatus::from_i32(status)) {
+            break;
+        }
+        check_status(status, Operation::MeasurePower)?;
+        samples.push(watts);
+    }
+
+    Ok(DeviceHealth {
+        device_id,
+        temperature: ChipTemperature {
+            ts0_celsius: raw_temperature.ts0_temperature,
+            ts1_celsius: raw_temperature.ts1_temperature,
+            sample_count: raw_temperature.sample_count,
+        },
+        power: PowerMeasurement::from_samples(&samples),
+    })
+}
+
+/// Returns whether a failed power measurement means the board has no power sensor.
+fn lacks_power_sensor(status: HailoStatus) -> bool {
+    matches!(
+        status,
+        HailoStatus::NotImplemented
+            | HailoStatus::NotAvailable
+            | HailoStatus::UnsupportedOpcode
+            | HailoStatus::InvalidOperation
+            | HailoStatus::FirmwareControlFailure
+    )
+}
+
+/// Converts a null-terminated string buffer filled by the wrapper into a `String`.
+fn c_string(buffer: &[c_char]) -> String {
+    unsafe { CStr::from_ptr(buffer.as_ptr()) }.to_string_lossy().into_owned()
+}
+
+/// Serializes a value through its `Display` implementation, e.g. `"4.19.0"` or `"HAILO8L"`.
+pub(crate) fn serialize_display<T, S>(value: &T, serializer: S) -> std::result::Result<S::Ok, S::Error>
+where
+    T: std::fmt::Display,
+    S: Serializer,
+{
+    serializer.collect_str(value)
+}
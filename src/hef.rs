@@ -0,0 +1,616 @@
+//! Pure-Rust inspection of Hailo Execution Files (HEF).
+//!
+//! A HEF starts with a fixed header followed by a protobuf message (`ProtoHEFHef`) that
+//! describes the compiled model, and then the compiled code itself. This module reads the header
+//! and the model description without HailoRT, so a HEF can be checked on any host before it is
+//! deployed: which architecture it was compiled for, which network groups it holds, and the
+//! streams, shapes, quantization and NMS settings of each network group.
+//!
+//! Streams are the boundary streams of the device. When a network group ends in an NMS
+//! post-process, its raw output streams are listed together with the NMS settings, while the
+//! configured model exposes a single NMS output vstream named after the NMS operation instead.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use hailors::hef::Hef;
+//!
+//! let hef = Hef::open("./hef/yolov8s_h8.hef").expect("Failed to read HEF");
+//! println!("{}", hef);
+//! ```
+
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::error::{HailorsError, Result};
+use crate::health::serialize_display;
+use crate::vstream::{FormatType, QuantInfo, VStreamShape};
+
+/// Magic number at the start of every HEF (`"\x01HEF"`).
+pub const HEF_MAGIC: u32 = 0x0148_4546;
+
+/// Size of the header fields shared by every HEF version: magic, version and protobuf size.
+const COMMON_HEADER_SIZE: usize = 12;
+
+/// Returns the size of the header of a HEF version, or `None` for unknown versions.
+fn header_size(version: u32) -> Option<usize> {
+    let distinct = match version {
+        // Reserved word and MD5 of the protobuf
+        0 => 4 + 16,
+        // CRC, size of the compiled code and a reserved word
+        1 => 4 + 8 + 4,
+        // XXH3 hash, size of the compiled code and two reserved words
+        2 => 8 + 8 + 8 + 8,
+        // XXH3 hash, padded size of the compiled code, padding, additional info and two reserved words
+        3 => 8 + 8 + 4 + 8 + 8 + 8,
+        _ => return None,
+    };
+    Some(COMMON_HEADER_SIZE + distinct)
+}
+
+/// Architecture a HEF was compiled for (`ProtoHEFHwArch`).
+///
+/// Hailo-8P and Hailo-8R are Hailo-8 variants that run on Hailo-8 devices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HefArchitecture {
+    /// Hailo-8.
+    Hailo8,
+    /// Hailo-8P.
+    Hailo8P,
+    /// Hailo-8R.
+    Hailo8R,
+    /// Hailo-8L.
+    Hailo8L,
+    /// Hailo-15H.
+    Hailo15H,
+    /// Hailo-15M.
+    Hailo15M,
+    /// Hailo-10H.
+    Hailo10H,
+    /// An architecture this crate does not know about.
+    Unknown(u32),
+}
+
+impl HefArchitecture {
+    /// Converts a raw `ProtoHEFHwArch` value into a `HefArchitecture`.
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            0 => HefArchitecture::Hailo8,
+            1 => HefArchitecture::Hailo8P,
+            2 => HefArchitecture::Hailo8R,
+            3 => HefArchitecture::Hailo8L,
+            4 => HefArchitecture::Hailo15M,
+            5 => HefArchitecture::Hailo10H,
+            103 => HefArchitecture::Hailo15H,
+            other => HefArchitecture::Unknown(other),
+        }
+    }
+}
+
+impl std::fmt::Display for HefArchitecture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HefArchitecture::Hailo8 => write!(f, "HAILO8"),
+            HefArchitecture::Hailo8P => write!(f, "HAILO8P"),
+            HefArchitecture::Hailo8R => write!(f, "HAILO8R"),
+            HefArchitecture::Hailo8L => write!(f, "HAILO8L"),
+            HefArchitecture::Hailo15H => write!(f, "HAILO15H"),
+            HefArchitecture::Hailo15M => write!(f, "HAILO15M"),
+            HefArchitecture::Hailo10H => write!(f, "HAILO10H"),
+            HefArchitecture::Unknown(value) => write!(f, "UNKNOWN({})", value),
+        }
+    }
+}
+
+/// One boundary stream of a network group.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HefStream {
+    /// Stream name, e.g. `yolov8s/input_layer1`.
+    pub name: String,
+    /// Frame shape.
+    pub shape: VStreamShape,
+    /// Data type of the elements on the device, UINT8 or UINT16.
+    #[serde(serialize_with = "serialize_display")]
+    pub format_type: FormatType,
+    /// Quantization parameters.
+    pub quant: QuantInfo,
+}
+
+impl std::fmt::Display for HefStream {
+    /// Summarizes the stream, e.g. `yolov8s/input_layer1: UINT8 640x640x3 (zero point 0, scale 1)`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} {}x{}x{} (zero point {}, scale {})",
+            self.name,
+            self.format_type,
+            self.shape.height,
+            self.shape.width,
+            self.shape.features,
+            self.quant.zero_point,
+            self.quant.scale
+        )
+    }
+}
+
+/// NMS post-process settings of a network group.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HefNms {
+    /// Name of the NMS operation, which is also the name of its output vstream.
+    pub name: String,
+    /// Number of detection classes.
+    pub number_of_classes: u32,
+    /// Maximum number of bounding boxes per class.
+    pub max_bboxes_per_class: u32,
+    /// Minimum score of a reported bounding box.
+    pub score_threshold: f32,
+    /// IoU above which overlapping bounding boxes are suppressed.
+    pub iou_threshold: f32,
+}
+
+impl std::fmt::Display for HefNms {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} classes, {} boxes per class, score threshold {}, IoU threshold {}",
+            self.name, self.number_of_classes, self.max_bboxes_per_class, self.score_threshold, self.iou_threshold
+        )
+    }
+}
+
+/// One network group of a HEF.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HefNetworkGroup {
+    /// Network group name, as accepted by `DeviceConfig::with_network_group`.
+    pub name: String,
+    /// Names of the networks in the network group.
+    pub networks: Vec<String>,
+    /// Input streams.
+    pub inputs: Vec<HefStream>,
+    /// Output streams, before any NMS post-process.
+    pub outputs: Vec<HefStream>,
+    /// NMS post-process settings, if the network group ends in one.
+    pub nms: Option<HefNms>,
+}
+
+/// The model description of a HEF.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Hef {
+    /// Version of the HEF container format.
+    pub version: u32,
+    /// Architecture the HEF was compiled for.
+    #[serde(serialize_with = "serialize_display")]
+    pub architecture: HefArchitecture,
+    /// Version of the Dataflow Compiler that produced the HEF, if recorded.
+    pub sdk_version: Option<String>,
+    /// Network groups, in the order they are stored.
+    pub network_groups: Vec<HefNetworkGroup>,
+}
+
+impl Hef {
+    /// Reads the model description of a HEF file.
+    ///
+    /// Only the header and the model description are read, not the compiled code.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HailorsError::Io` if the file cannot be read, or `HailorsError::InvalidHef` if it
+    /// is not a HEF.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        use std::io::Read;
+
+        let mut file = std::fs::File::open(path)?;
+        let mut header = vec![0u8; COMMON_HEADER_SIZE];
+        file.read_exact(&mut header).map_err(|_| invalid("File is too short for a HEF header"))?;
+        let (version, proto_size) = parse_common_header(&header)?;
+        let size = header_size(version).ok_or_else(|| invalid(format!("Unsupported HEF version {}", version)))?;
+
+        let mut rest = Vec::new();
+        file.take((size - COMMON_HEADER_SIZE) as u64 + proto_size as u64)
+            .read_to_end(&mut rest)?;
+        header.extend_from_slice(&rest);
+        Self::parse(&header)
+    }
+
+    /// Parses the model description from the bytes of a HEF, which may stop after the model
+    /// description.
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let (version, proto_size) = parse_common_header(data)?;
+        let start = header_size(version).ok_or_else(|| invalid(format!("Unsupported HEF version {}", version)))?;
+        let proto = start
+            .checked_add(proto_size)
+            .and_then(|end| data.get(start..end))
+            .ok_or_else(|| invalid("File ends before the end of the model description"))?;
+        parse_hef(version, proto)
+    }
+
+    /// Returns the network group with the given name.
+    pub fn network_group(&self, name: &str) -> Option<&HefNetworkGroup> {
+        self.network_groups.iter().find(|group| group.name == name)
+    }
+}
+
+impl std::fmt::Display for Hef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "HEF version {}, architecture {}", self.version, self.architecture)?;
+        if let Some(sdk_version) = &self.sdk_version {
+            write!(f, ", SDK {}", sdk_version)?;
+        }
+        for group in &self.network_groups {
+            write!(f, "\nNetwork group {}", group.name)?;
+            if !group.networks.is_empty() {
+                write!(f, " (networks: {})", group.networks.join(", "))?;
+            }
+            for input in &group.inputs {
+                write!(f, "\n  Input  {}", input)?;
+            }
+            for output in &group.outputs {
+                write!(f, "\n  Output {}", output)?;
+            }
+            if let Some(nms) = &group.nms {
+                write!(f, "\n  NMS    {}", nms)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn invalid<S: Into<String>>(message: S) -> HailorsError {
+    HailorsError::InvalidHef(message.into())
+}
+
+/// Checks the magic and returns the version and protobuf size from the common header fields,
+/// which are stored big-endian.
+fn parse_common_header(data: &[u8]) -> Result<(u32, usize)> {
+    let word = |offset: usize| {
+        data.get(offset..offset + 4)
+            .map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            .ok_or_else(|| invalid("File is too short for a HEF header"))
+    };
+    let magic = word(0)?;
+    if magic != HEF_MAGIC {
+        return Err(invalid(format!("Bad magic {:#010x}", magic)));
+    }
+    Ok((word(4)?, word(8)? as usize))
+}
+
+/// Field numbers of the `hef.proto` messages read by the inspector, grouped by message.
+mod schema {
+    // ProtoHEFHef
+    pub const HEF_HEADER: u32 = 1;
+    pub const HEF_NETWORK_GROUPS: u32 = 2;
+    // ProtoHEFHeader
+    pub const HEADER_HW_ARCH: u32 = 1;
+    pub const HEADER_SDK_VERSION: u32 = 3;
+    // ProtoHEFNetworkGroup
+    pub const NETWORK_GROUP_METADATA: u32 = 1;
+    pub const NETWORK_GROUP_CONTEXTS: u32 = 3;
+    pub const NETWORK_GROUP_NETWORKS_NAMES: u32 = 6;
+    pub const NETWORK_GROUP_OPS: u32 = 8;
+    // ProtoHEFNetworkGroupMetadata
+    pub const METADATA_NAME: u32 = 1;
+    // ProtoHEFContext
+    pub const CONTEXT_METADATA: u32 = 3;
+    // ProtoHEFContextMetadata
+    pub const CONTEXT_EDGE_LAYERS: u32 = 1;
+    // ProtoHEFEdgeLayer
+    pub const EDGE_LAYER_DIRECTION: u32 = 1;
+    pub const EDGE_LAYER_INFO: u32 = 3;
+    pub const EDGE_LAYER_SWITCH_INFO: u32 = 5;
+    // ProtoHEFEdgeLayerDirection
+    pub const DIRECTION_DEVICE_TO_HOST: u64 = 1;
+    // ProtoHEFContextSwitchInformation
+    pub const SWITCH_INFO_CONNECTION_TYPE: u32 = 1;
+    // ProtoHEFEdgeConnectionType
+    pub const CONNECTION_BOUNDARY: u64 = 0;
+    // ProtoHEFEdgeLayerInfo
+    pub const LAYER_INFO_NAME: u32 = 1;
+    pub const LAYER_INFO_BASE: u32 = 3;
+    pub const LAYER_INFO_NUMERIC: u32 = 5;
+    // ProtoHEFEdgeLayerBase
+    pub const BASE_HEIGHT: u32 = 1;
+    pub const BASE_WIDTH: u32 = 3;
+    pub const BASE_FEATURES: u32 = 5;
+    pub const BASE_DATA_BYTES: u32 = 10;
+    // ProtoHEFEdgeLayerNumericInfo
+    pub const NUMERIC_QP_ZP: u32 = 1;
+    pub const NUMERIC_QP_SCALE: u32 = 2;
+    pub const NUMERIC_LIMVALS_MIN: u32 = 3;
+    pub const NUMERIC_LIMVALS_MAX: u32 = 4;
+    // ProtoHEFOp
+    pub const OP_NAME: u32 = 1;
+    pub const OP_NMS: u32 = 5;
+    // ProtoHEFNmsOp
+    pub const NMS_SCORE_THRESHOLD: u32 = 1;
+    pub const NMS_IOU_THRESHOLD: u32 = 2;
+    pub const NMS_MAX_PROPOSALS_PER_CLASS: u32 = 3;
+    pub const NMS_CLASSES: u32 = 4;
+}
+
+fn parse_hef(version: u32, proto: &[u8]) -> Result<Hef> {
+    let mut hef = Hef {
+        version,
+        architecture: HefArchitecture::Hailo8,
+        sdk_version: None,
+        network_groups: Vec::new(),
+    };
+    for field in proto::Fields::new(proto) {
+        match field? {
+            (schema::HEF_HEADER, value) => {
+                for field in proto::Fields::new(value.bytes()?) {
+                    match field? {
+                        (schema::HEADER_HW_ARCH, value) => {
+                            hef.architecture = HefArchitecture::from_u32(value.varint()? as u32)
+                        }
+                        (schema::HEADER_SDK_VERSION, value) => hef.sdk_version = Some(value.string()?),
+                        _ => {}
+                    }
+                }
+            }
+            (schema::HEF_NETWORK_GROUPS, value) => hef.network_groups.push(parse_network_group(value.bytes()?)?),
+            _ => {}
+        }
+    }
+    Ok(hef)
+}
+
+fn parse_network_group(data: &[u8]) -> Result<HefNetworkGroup> {
+    let mut group = HefNetworkGroup {
+        name: String::new(),
+        networks: Vec::new(),
+        inputs: Vec::new(),
+        outputs: Vec::new(),
+        nms: None,
+    };
+    for field in proto::Fields::new(data) {
+        match field? {
+            (schema::NETWORK_GROUP_METADATA, value) => {
+                for field in proto::Fields::new(value.bytes()?) {
+                    if let (schema::METADATA_NAME, value) = field? {
+                        group.name = value.string()?;
+                    }
+                }
+            }
+            (schema::NETWORK_GROUP_CONTEXTS, value) => {
+                for field in proto::Fields::new(value.bytes()?) {
+                    if let (schema::CONTEXT_METADATA, value) = field? {
+                        parse_context_metadata(value.bytes()?, &mut group)?;
+                    }
+                }
+            }
+            (schema::NETWORK_GROUP_NETWORKS_NAMES, value) => group.networks.push(value.string()?),
+            (schema::NETWORK_GROUP_OPS, value) => {
+                if let Some(nms) = parse_op(value.bytes()?)? {
+                    group.nms = Some(nms);
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(group)
+}
+
+/// Adds the boundary streams of one context, skipping streams already seen in earlier contexts.
+fn parse_context_metadata(data: &[u8], group: &mut HefNetworkGroup) -> Result<()> {
+    for field in proto::Fields::new(data) {
+        let (schema::CONTEXT_EDGE_LAYERS, value) = field? else {
+            continue;
+        };
+        let mut output = false;
+        let mut boundary = true;
+        let mut stream = None;
+        for field in proto::Fields::new(value.bytes()?) {
+            match field? {
+                (schema::EDGE_LAYER_DIRECTION, value) => output = value.varint()? == schema::DIRECTION_DEVICE_TO_HOST,
+                (schema::EDGE_LAYER_INFO, value) => stream = Some(parse_edge_layer_info(value.bytes()?)?),
+                (schema::EDGE_LAYER_SWITCH_INFO, value) => {
+                    for field in proto::Fields::new(value.bytes()?) {
+                        if let (schema::SWITCH_INFO_CONNECTION_TYPE, value) = field? {
+                            boundary = value.varint()? == schema::CONNECTION_BOUNDARY;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let Some(stream) = stream.filter(|_| boundary) else {
+            continue;
+        };
+        let streams = if output { &mut group.outputs } else { &mut group.inputs };
+        if !streams.iter().any(|existing| existing.name == stream.name) {
+            streams.push(stream);
+        }
+    }
+    Ok(())
+}
+
+fn parse_edge_layer_info(data: &[u8]) -> Result<HefStream> {
+    let mut stream = HefStream {
+        name: String::new(),
+        shape: VStreamShape::new(0, 0, 0),
+        format_type: FormatType::Uint8,
+        quant: QuantInfo::default(),
+    };
+    for field in proto::Fields::new(data) {
+        match field? {
+            (schema::LAYER_INFO_NAME, value) => stream.name = value.string()?,
+            (schema::LAYER_INFO_BASE, value) => {
+                for field in proto::Fields::new(value.bytes()?) {
+                    match field? {
+                        (schema::BASE_HEIGHT, value) => stream.shape.height = value.varint()? as u32,
+                        (schema::BASE_WIDTH, value) => stream.shape.width = value.varint()? as u32,
+                        (schema::BASE_FEATURES, value) => stream.shape.features = value.varint()? as u32,
+                        (schema::BASE_DATA_BYTES, value) => {
+                            stream.format_type = match value.varint()? {
+                                2 => FormatType::Uint16,
+                                _ => FormatType::Uint8,
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            (schema::LAYER_INFO_NUMERIC, value) => {
+                for field in proto::Fields::new(value.bytes()?) {
+                    match field? {
+                        (schema::NUMERIC_QP_ZP, value) => stream.quant.zero_point = value.float()?,
+                        (schema::NUMERIC_QP_SCALE, value) => stream.quant.scale = value.float()?,
+                        (schema::NUMERIC_LIMVALS_MIN, value) => stream.quant.limvals_min = value.float()?,
+                        (schema::NUMERIC_LIMVALS_MAX, value) => stream.quant.limvals_max = value.float()?,
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(stream)
+}
+
+/// Returns the NMS settings of an operation, or `None` if it is not an NMS operation.
+fn parse_op(data: &[u8]) -> Result<Option<HefNms>> {
+    let mut name = String::new();
+    let mut nms = None;
+    for field in proto::Fields::new(data) {
+        match field? {
+            (schema::OP_NAME, value) => name = value.string()?,
+            (schema::OP_NMS, value) => {
+                let mut settings = HefNms {
+                    name: String::new(),
+                    number_of_classes: 0,
+                    max_bboxes_per_class: 0,
+                    score_threshold: 0.0,
+                    iou_threshold: 0.0,
+                };
+                for field in proto::Fields::new(value.bytes()?) {
+                    match field? {
+                        (schema::NMS_SCORE_THRESHOLD, value) => settings.score_threshold = value.float()?,
+                        (schema::NMS_IOU_THRESHOLD, value) => settings.iou_threshold = value.float()?,
+                        (schema::NMS_MAX_PROPOSALS_PER_CLASS, value) => {
+                            settings.max_bboxes_per_class = value.varint()? as u32
+                        }
+                        (schema::NMS_CLASSES, value) => settings.number_of_classes = value.varint()? as u32,
+                        _ => {}
+                    }
+                }
+                nms = Some(settings);
+            }
+            _ => {}
+        }
+    }
+    Ok(nms.map(|settings| HefNms { name, ..settings }))
+}
+
+/// A minimal reader for the protobuf wire format.
+mod proto {
+    use super::invalid;
+    use crate::error::Result;
+
+    /// The value of one field, by wire type.
+    pub(super) enum Value<'a> {
+        Varint(u64),
+        /// A 64-bit value; no field read by the inspector has this type, so it is skipped.
+        Fixed64,
+        Bytes(&'a [u8]),
+        Fixed32(u32),
+    }
+
+    impl<'a> Value<'a> {
+        pub(super) fn varint(&self) -> Result<u64> {
+            match self {
+                Value::Varint(value) => Ok(*value),
+                _ => Err(invalid("Expected a varint field")),
+            }
+        }
+
+        pub(super) fn float(&self) -> Result<f32> {
+            match self {
+                Value::Fixed32(bits) => Ok(f32::from_bits(*bits)),
+                _ => Err(invalid("Expected a float field")),
+            }
+        }
+
+        pub(super) fn bytes(&self) -> Result<&'a [u8]> {
+            match self {
+                Value::Bytes(bytes) => Ok(bytes),
+                _ => Err(invalid("Expected a length-delimited field")),
+            }
+        }
+
+        pub(super) fn string(&self) -> Result<String> {
+            Ok(String::from_utf8_lossy(self.bytes()?).into_owned())
+        }
+    }
+
+    /// Iterates over the fields of a message as `(field number, value)` pairs.
+    pub(super) struct Fields<'a> {
+        data: &'a [u8],
+    }
+
+    impl<'a> Fields<'a> {
+        pub(super) fn new(data: &'a [u8]) -> Self {
+            Self { data }
+        }
+
+        fn varint(&mut self) -> Result<u64> {
+            let mut value = 0u64;
+            for (index, &byte) in self.data.iter().enumerate().take(10) {
+                value |= u64::from(byte & 0x7f) << (7 * index);
+                if byte & 0x80 == 0 {
+                    self.data = &self.data[index + 1..];
+                    return Ok(value);
+                }
+            }
+            Err(invalid("Truncated or overlong varint"))
+        }
+
+        fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+            if len > self.data.len() {
+                return Err(invalid("Field extends past the end of its message"));
+            }
+            let (taken, rest) = self.data.split_at(len);
+            self.data = rest;
+            Ok(taken)
+        }
+
+        fn field(&mut self) -> Result<(u32, Value<'a>)> {
+            let key = self.varint()?;
+            let number = (key >> 3) as u32;
+            let value = match key & 0x7 {
+                0 => Value::Varint(self.varint()?),
+                1 => {
+                    self.take(8)?;
+                    Value::Fixed64
+                }
+                2 => {
+                    let len = self.varint()? as usize;
+                    Value::Bytes(self.take(len)?)
+                }
+                5 => {
+                    let bytes = self.take(4)?;
+                    Value::Fixed32(u32::from_le_bytes(bytes.try_into().expect("Took 4 bytes")))
+                }
+                wire_type => return Err(invalid(format!("Unsupported wire type {}", wire_type))),
+            };
+            Ok((number, value))
+        }
+    }
+
+    impl<'a> Iterator for Fields<'a> {
+        type Item = Result<(u32, Value<'a>)>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.data.is_empty() {
+                return None;
+            }
+            let field = self.field();
+            if field.is_err() {
+                // Stop after the first error instead of reading garbage
+                self.data = &[];
+            }
+            Some(field)
+        }
+    }
+}
@@ -0,0 +1,170 @@
+//! Class labels for detection results.
+//!
+//! Detection networks report a numeric class ID per detection. A `Labels` table maps those IDs
+//! to names, so results read "dog 0.91" instead of "Class 16". The COCO-80 labels used by the
+//! YOLO models in the Hailo Model Zoo are built in, and custom tables are loaded from files in
+//! one of three formats, chosen by extension:
+//!
+//! - `.json`: a list of names, a map from class ID to name, or an object with such a `names` key.
+//! - `.yaml` or `.yml`: the same shapes as JSON, which covers Ultralytics dataset files.
+//! - anything else: plain text with one name per line, blank lines skipped.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+use crate::error::{HailorsError, Result};
+
+/// The 80 COCO class names, indexed by the class IDs of COCO-trained YOLO models.
+pub const COCO_LABELS: [&str; 80] = [
+    "person", "bicycle", "car", "motorcycle", "airplane", "bus", "train", "truck", "boat", "traffic light",
+    "fire hydrant", "stop sign", "parking meter", "bench", "bird", "cat", "dog", "horse", "sheep", "cow",
+    "elephant", "bear", "zebra", "giraffe", "backpack", "umbrella", "handbag", "tie", "suitcase", "frisbee",
+    "skis", "snowboard", "sports ball", "kite", "baseball bat", "baseball glove", "skateboard", "surfboard",
+    "tennis racket", "bottle", "wine glass", "cup", "fork", "knife", "spoon", "bowl", "banana", "apple",
+    "sandwich", "orange", "broccoli", "carrot", "hot dog", "pizza", "donut", "cake", "chair", "couch",
+    "potted plant", "bed", "dining table", "toilet", "tv", "laptop", "mouse", "remote", "keyboard",
+    "cell phone", "microwave", "oven", "toaster", "sink", "refrigerator", "book", "clock", "vase", "scissors",
+    "teddy bear", "hair drier", "toothbrush",
+];
+
+/// A table of class names, indexed by class ID.
+///
+/// Names are reference-counted, so attaching them to every detection does not copy them, and
+/// kept by class ID, so sparse maps with large IDs take no more memory than dense lists.
+///
+/// # Example
+///
+/// ```rust
+/// use hailors::Labels;
+///
+/// let labels = Labels::coco();
+/// assert_eq!(labels.get(16).as_deref(), Some("dog"));
+/// assert_eq!(labels.class_id("dog"), Some(16));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Labels {
+    /// Name per class ID; IDs a sparse map leaves out have no entry.
+    names: BTreeMap<u32, Arc<str>>,
+}
+
+/// The shapes a JSON or YAML label file may take.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LabelFile {
+    List(Vec<String>),
+    Map(BTreeMap<ClassId, String>),
+    Names { names: Box<LabelFile> },
+}
+
+/// A class ID key, an integer in YAML but a string in JSON.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(untagged)]
+enum ClassId {
+    Integer(u32),
+    String(String),
+}
+
+impl ClassId {
+    fn value(&self) -> Result<u32> {
+        match self {
+            ClassId::Integer(class_id) => Ok(*class_id),
+            ClassId::String(key) => key
+                .parse()
+                .map_err(|_| HailorsError::InvalidLabels(format!("{} is not a class ID", key))),
+        }
+    }
+}
+
+impl Labels {
+    /// Creates a table from names in class ID order.
+    pub fn new<I, S>(names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<Arc<str>>,
+    {
+        Self {
+            names: (0..).zip(names.into_iter().map(Into::into)).collect(),
+        }
+    }
+
+    /// Returns the COCO-80 labels.
+    pub fn coco() -> Self {
+        Self::new(COCO_LABELS)
+    }
+
+    /// Loads a label file, choosing the format by its extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HailorsError::Io` if the file cannot be read, or `HailorsError::InvalidLabels` if
+    /// a JSON or YAML file does not hold a list or map of names.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)?;
+        let extension = path.extension().and_then(|extension| extension.to_str()).unwrap_or("");
+        match extension.to_ascii_lowercase().as_str() {
+            "json" => Self::from_json(&contents),
+            "yaml" | "yml" => Self::from_yaml(&contents),
+            _ => Ok(Self::from_text(&contents)),
+        }
+    }
+
+    /// Parses plain text with one name per line, skipping blank lines.
+    pub fn from_text(text: &str) -> Self {
+        Self::new(text.lines().map(str::trim).filter(|line| !line.is_empty()))
+    }
+
+    /// Parses a JSON list of names, map from class ID to name, or object with a `names` key.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let file = serde_json::from_str(json).map_err(|err| HailorsError::InvalidLabels(err.to_string()))?;
+        Self::from_file(file)
+    }
+
+    /// Parses a YAML list of names, map from class ID to name, or mapping with a `names` key.
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        let file = serde_yaml::from_str(yaml).map_err(|err| HailorsError::InvalidLabels(err.to_string()))?;
+        Self::from_file(file)
+    }
+
+    fn from_file(file: LabelFile) -> Result<Self> {
+        match file {
+            LabelFile::List(names) => Ok(Self::new(names)),
+            LabelFile::Map(map) => {
+                let names = map
+                    .into_iter()
+                    .map(|(class_id, name)| Ok((class_id.value()?, name.into())))
+                    .collect::<Result<_>>()?;
+                Ok(Self { names })
+            }
+            LabelFile::Names { names } => Self::from_file(*names),
+        }
+    }
+
+    /// Returns the name of a class, or `None` if the table has no name for it.
+    pub fn get(&self, class_id: u32) -> Option<Arc<str>> {
+        self.names.get(&class_id).cloned()
+    }
+
+    /// Returns the class ID of a name.
+    pub fn class_id(&self, name: &str) -> Option<u32> {
+        self.names
+            .iter()
+            .find(|(_, label)| label.as_ref() == name)
+            .map(|(&class_id, _)| class_id)
+    }
+
+    /// Returns the number of class IDs the table covers, i.e. one more than the highest ID.
+    pub fn len(&self) -> usize {
+        self.names
+            .last_key_value()
+            .map_or(0, |(&class_id, _)| (class_id as usize).saturating_add(1))
+    }
+
+    /// Returns whether the table is empty.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
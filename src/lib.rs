@@ -1,25 +1,79 @@
-use std::ffi::{CString, c_void};
-use std::ptr;
-use anyhow::Result;
-
-mod status;
-use status::HailoStatus;
+pub mod status;
+#[cfg(feature = "async")]
+pub mod async_device;
+pub mod backend;
+pub mod bench;
+pub mod buffer;
+pub mod compat;
+pub mod config;
+pub mod device;
+pub mod error;
+mod ffi;
+pub mod hailort;
+pub mod hef;
+pub mod health;
+pub mod labels;
+mod logging;
+pub mod manifest;
+pub mod mock;
 pub mod network;
+pub mod pipeline;
+pub mod quant;
+pub mod shared;
+pub mod tensor;
+pub mod vdevice;
+pub mod vstream;
+#[cfg(feature = "async")]
+pub use crate::async_device::{AsyncDevice, InferFuture, InferStream};
+pub use crate::backend::{AbortHandle, InferenceBackend};
+pub use crate::buffer::{AlignedBuffer, BufferPool, OutputBuffers, PooledOutputBuffers};
+pub use crate::compat::{hailort_version, HailoRtVersion};
+pub use crate::config::{DeviceConfig, StreamConfig};
+pub use crate::device::{devices, DeviceArchitecture, PcieAddress, PhysicalDevice};
+pub use crate::error::{HailorsError, Operation, Result};
+pub use crate::hailort::{network_group_names, HailoRtBackend};
+pub use crate::hef::{Hef, HefArchitecture, HefNetworkGroup, HefNms, HefStream};
+pub use crate::health::{
+    BootSource, ChipTemperature, DeviceHealth, DeviceInfo, ExtendedDeviceInfo, FirmwareVersion, PowerMeasurement,
+};
+pub use crate::labels::Labels;
+pub use crate::manifest::{ModelManifest, NetworkSpec};
+pub use crate::mock::MockBackend;
 pub use crate::network::Network;
+pub use crate::pipeline::{Pipeline, PipelineReader, PipelineWriter};
+pub use crate::shared::{InputWriter, OutputReader, SharedDevice};
+pub use crate::status::HailoStatus;
+pub use crate::tensor::{RawTensor, Tensor};
+pub use crate::vdevice::{SchedulingAlgorithm, VDevice, VDeviceParams};
+pub use crate::vstream::{
+    FormatOrder, FormatType, NmsShape, QuantInfo, StreamDirection, VStreamFormat, VStreamInfo, VStreamShape,
+};
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
 
 /// Represents a device for interfacing with the Hailo AI hardware.
+///
+/// The device delegates all stream I/O to an `InferenceBackend`. `HailoDevice::new` uses the
+/// HailoRT backend, while `HailoDevice::with_backend` accepts any backend, such as `MockBackend`.
+///
+/// Models may have several input and output virtual streams. Streams can be addressed by index
+/// or by name, and `infer` writes all inputs and reads all outputs of one inference.
+///
+/// # Thread safety
+///
+/// `HailoDevice` is `Send` and `Sync`. Each stream has its own lock, so inputs can be written on
+/// one thread while outputs are read on another, and no global lock is needed. Outputs are
+/// returned in the order the inputs were written, so when several threads submit frames, use
+/// `split` for one writer thread and one reader thread, or `into_shared` for any number of
+/// worker threads, to keep each frame paired with its outputs. See the `shared` module.
 pub struct HailoDevice {
-    /// Handle to the Hailo device.
-    pub device_handle: *mut c_void,
-    /// Handle to the configured network group.
-    pub network_group: *mut c_void,
-    /// Pointer to an array of input virtual streams.
-    pub input_vstream: *mut *mut c_void,
-    /// Pointer to an array of output virtual streams.
-    pub output_vstream: *mut *mut c_void,
-    /// Size of the input frame in bytes.
+    /// Backend that owns the configured model and its virtual streams.
+    backend: Arc<dyn InferenceBackend>,
+    /// Size of the first input frame in bytes.
     pub input_frame_size: usize,
-    /// Size of the output frame in bytes.
+    /// Size of the first output frame in bytes.
     pub output_frame_size: usize,
 }
 
@@ -36,62 +90,180 @@ impl HailoDevice {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```no_run
     /// use hailors::HailoDevice;
     ///
     /// let device = HailoDevice::new("./hef/yolov8s_h8.hef")
     ///     .expect("Failed to create HailoDevice");
     /// ```
     pub fn new(hef_path: &str) -> Result<Self> {
-        let mut device_handle: *mut c_void = ptr::null_mut();
-        let mut network_group: *mut c_void = ptr::null_mut();
-        let mut input_vstreams: *mut *mut c_void = ptr::null_mut();
-        let mut output_vstreams: *mut *mut c_void = ptr::null_mut();
-        let mut input_count: usize = 0;
-        let mut output_count: usize = 0;
-        let mut input_frame_size: usize = 0;
-        let mut output_frame_size: usize = 0;
-
-        // Call FFI function to configure the HEF and virtual streams
-        let hef_path_cstr = CString::new(hef_path)?;
-        unsafe {
-            let status = hailors_create_vdevice(&mut device_handle);
-            if status != HailoStatus::Success {
-                return Err(anyhow::anyhow!("Failed to create VDevice"));
-            }
-
-            let configure_status = hailors_configure_hef(
-                device_handle,
-                hef_path_cstr.as_ptr() as *const i8,
-                &mut network_group,
-                &mut input_vstreams,
-                &mut input_count,
-                &mut output_vstreams,
-                &mut output_count,
-                &mut input_frame_size,
-                &mut output_frame_size,
-            );
-            if configure_status != HailoStatus::Success {
-                return Err(anyhow::anyhow!("Failed to configure HEF"));
-            }
+        Ok(Self::with_backend(HailoRtBackend::new(hef_path)?))
+    }
 
-            if input_vstreams.is_null() || output_vstreams.is_null() {
-                hailors_release_vdevice(device_handle);
-                return Err(anyhow::anyhow!("Failed to allocate input or output vstreams"));
-            }
-        }
+    /// Creates a new Hailo device and configures it with the provided HEF file and vstream parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `hef_path` - Path to the Hailo Execution File (HEF).
+    /// * `config` - Virtual device parameters, network group selection and vstream parameters.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hailors::{DeviceConfig, FormatType, HailoDevice};
+    ///
+    /// let config = DeviceConfig::new().with_input_format(FormatType::Uint8);
+    /// let device = HailoDevice::with_config("./hef/yolov8s_h8.hef", &config)
+    ///     .expect("Failed to create HailoDevice");
+    /// ```
+    pub fn with_config(hef_path: &str, config: &DeviceConfig) -> Result<Self> {
+        Ok(Self::with_backend(HailoRtBackend::with_config(hef_path, config)?))
+    }
 
-        Ok(Self {
-            device_handle,
-            network_group,
-            input_vstream: input_vstreams,
-            output_vstream: output_vstreams,
+    /// Creates a new device on top of an arbitrary inference backend.
+    ///
+    /// # Arguments
+    ///
+    /// * `backend` - The backend that performs the actual inference.
+    pub fn with_backend<B: InferenceBackend + 'static>(backend: B) -> Self {
+        let input_frame_size = backend.inputs().first().map_or(0, |info| info.frame_size);
+        let output_frame_size = backend.outputs().first().map_or(0, |info| info.frame_size);
+        Self {
+            backend: Arc::new(backend),
             input_frame_size,
             output_frame_size,
-        })
+        }
     }
 
-    /// Writes a frame to the input virtual stream.
+    /// Replaces the configured model with a HEF, keeping the virtual device.
+    ///
+    /// Unlike dropping the device and calling `HailoDevice::new` again, the virtual device and the
+    /// physical devices under it stay open, so switching models is fast and other processes cannot
+    /// claim the device in between.
+    ///
+    /// # Arguments
+    ///
+    /// * `hef_path` - Path to the Hailo Execution File (HEF) to load.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HailorsError::NoDevice` if the backend does not run on Hailo hardware, or an error
+    /// if the HEF cannot be configured. On error, the previous model stays configured.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hailors::HailoDevice;
+    ///
+    /// let mut device = HailoDevice::new("./hef/yolov8s_h8.hef").expect("Failed to create HailoDevice");
+    /// device
+    ///     .reconfigure("./hef/yolov8m_h8.hef")
+    ///     .expect("Failed to load the new model");
+    /// ```
+    pub fn reconfigure(&mut self, hef_path: &str) -> Result<()> {
+        self.reconfigure_with_config(hef_path, &DeviceConfig::default())
+    }
+
+    /// Replaces the configured model with a HEF and vstream parameters, keeping the virtual device.
+    ///
+    /// The virtual device parameters in `config` are ignored, since the virtual device is kept.
+    /// The new network group is configured before the previous one is released, so the device
+    /// never runs without a model; `AbortHandle`s taken before the call still refer to the
+    /// previous model's streams.
+    ///
+    /// # Arguments
+    ///
+    /// * `hef_path` - Path to the Hailo Execution File (HEF) to load.
+    /// * `config` - Network group selection and vstream parameters.
+    pub fn reconfigure_with_config(&mut self, hef_path: &str, config: &DeviceConfig) -> Result<()> {
+        let vdevice = self.backend.vdevice().ok_or(HailorsError::NoDevice)?.clone();
+        let backend = HailoRtBackend::configure(&vdevice, hef_path, config)?;
+        *self = Self::with_backend(backend);
+        Ok(())
+    }
+
+    /// Returns the backend this device runs on.
+    pub fn backend(&self) -> &dyn InferenceBackend {
+        self.backend.as_ref()
+    }
+
+    /// Returns the name of the configured network group, if the backend has one.
+    pub fn network_group(&self) -> Option<&str> {
+        self.backend.network_group()
+    }
+
+    /// Reads the board identity and firmware of each physical device the model runs on.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HailorsError::NoDevice` if the backend does not run on Hailo hardware.
+    pub fn device_info(&self) -> Result<Vec<DeviceInfo>> {
+        self.backend.vdevice().ok_or(HailorsError::NoDevice)?.device_info()
+    }
+
+    /// Reads the temperature and samples the power consumption of each physical device the
+    /// model runs on.
+    ///
+    /// # Arguments
+    ///
+    /// * `power_samples` - Number of power measurements to average per device; 0 skips power
+    ///   measurement.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HailorsError::NoDevice` if the backend does not run on Hailo hardware, or an error
+    /// if a device fails to report, including a power measurement failing after the first sample.
+    pub fn health(&self, power_samples: u32) -> Result<Vec<DeviceHealth>> {
+        self.backend.vdevice().ok_or(HailorsError::NoDevice)?.health(power_samples)
+    }
+
+    /// Activates the network group.
+    ///
+    /// Only needed when the virtual device does not run the model scheduler; with the scheduler,
+    /// which is enabled by default, HailoRT activates network groups as frames arrive and
+    /// explicit activation fails.
+    pub fn activate(&self) -> Result<()> {
+        self.backend.activate()
+    }
+
+    /// Deactivates the network group activated by `activate`.
+    pub fn deactivate(&self) -> Result<()> {
+        self.backend.deactivate()
+    }
+
+    /// Returns the input virtual streams of the configured model.
+    pub fn inputs(&self) -> &[VStreamInfo] {
+        self.backend.inputs()
+    }
+
+    /// Returns the output virtual streams of the configured model.
+    pub fn outputs(&self) -> &[VStreamInfo] {
+        self.backend.outputs()
+    }
+
+    /// Returns the index of the input stream with the given name.
+    pub fn input_index(&self, name: &str) -> Result<usize> {
+        self.inputs()
+            .iter()
+            .position(|info| info.name == name)
+            .ok_or_else(|| HailorsError::StreamNotFound {
+                direction: StreamDirection::Input,
+                name: name.to_string(),
+            })
+    }
+
+    /// Returns the index of the output stream with the given name.
+    pub fn output_index(&self, name: &str) -> Result<usize> {
+        self.outputs()
+            .iter()
+            .position(|info| info.name == name)
+            .ok_or_else(|| HailorsError::StreamNotFound {
+                direction: StreamDirection::Output,
+                name: name.to_string(),
+            })
+    }
+
+    /// Writes a frame to the first input virtual stream.
     ///
     /// # Arguments
     ///
@@ -101,24 +273,324 @@ impl HailoDevice {
     ///
     /// Returns an error if the input frame size does not match the expected size or if writing fails.
     pub fn write_input(&self, frame: &[u8]) -> Result<()> {
-        if frame.len() != self.input_frame_size {
-            return Err(anyhow::anyhow!(
-                "Input frame size mismatch: expected {}, got {}",
-                self.input_frame_size,
-                frame.len()
-            ));
+        self.write_input_at(0, frame)
+    }
+
+    /// Writes a frame to the input virtual stream at `index`.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Index of the input stream.
+    /// * `frame` - A byte slice representing the input frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream does not exist, the frame size does not match or writing fails.
+    pub fn write_input_at(&self, index: usize, frame: &[u8]) -> Result<()> {
+        self.check_input_frame(index, frame)?;
+        self.backend.write_input(index, frame)
+    }
+
+    /// Writes a frame to the input virtual stream at `index`, giving up after `timeout`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HailorsError::Timeout` if the write did not complete in time, in which case the
+    /// frames queued in the stream are discarded, or `HailorsError::StreamAborted` if the streams
+    /// were aborted.
+    pub fn write_input_at_timeout(&self, index: usize, frame: &[u8], timeout: Duration) -> Result<()> {
+        self.check_input_frame(index, frame)?;
+        self.backend.write_input_timeout(index, frame, timeout)
+    }
+
+    /// Returns the information of the input stream at `index`.
+    fn input_info(&self, index: usize) -> Result<&VStreamInfo> {
+        self.inputs().get(index).ok_or(HailorsError::StreamIndexOutOfRange {
+            direction: StreamDirection::Input,
+            index,
+            count: self.inputs().len(),
+        })
+    }
+
+    /// Checks that `index` names an input stream and `frame` matches its frame size.
+    fn check_input_frame(&self, index: usize, frame: &[u8]) -> Result<()> {
+        let info = self.input_info(index)?;
+        if frame.len() != info.frame_size {
+            return Err(HailorsError::FrameSizeMismatch {
+                stream: info.name.clone(),
+                expected: info.frame_size,
+                actual: frame.len(),
+            });
         }
+        Ok(())
+    }
 
-        unsafe {
-            let status = hailors_write_input_frame(*self.input_vstream, frame.as_ptr() as *const c_void, frame.len());
-            if status != HailoStatus::Success {
-                return Err(anyhow::anyhow!("Failed to write input frame"));
-            }
+    /// Writes FLOAT32 values to the input virtual stream at `index`, quantizing them with the
+    /// stream's quantization parameters if the stream is UINT8 or UINT16.
+    ///
+    /// This keeps the input quantized on the wire while the caller prepares FLOAT32 data, e.g.
+    /// normalized pixels.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream does not exist, the number of values does not match its
+    /// shape or writing fails.
+    pub fn write_input_f32(&self, index: usize, values: &[f32]) -> Result<()> {
+        let info = self.input_info(index)?;
+        let mut frame = Vec::with_capacity(info.frame_size);
+        quant::quantize(info.format.format_type, info.quant, values, &mut frame);
+        self.write_input_at(index, &frame)
+    }
+
+    /// Writes a tensor to the input virtual stream at `index`.
+    ///
+    /// A tensor that already has the stream's data type, format order and quantization is
+    /// written without copying. Otherwise it is converted with `Tensor::to_frame`, so e.g. a
+    /// planar FLOAT32 image can be written to an NHWC UINT8 stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream does not exist, the tensor does not match its shape or
+    /// cannot be converted, or writing fails.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hailors::{FormatOrder, HailoDevice, Tensor};
+    ///
+    /// let device = HailoDevice::new("./hef/yolov8s_h8.hef").expect("Failed to create device");
+    /// # let planar_image = vec![0u8; device.input_frame_size];
+    /// let tensor = Tensor::new(&device.inputs()[0], &planar_image[..]).with_order(FormatOrder::Nchw);
+    /// device.write_input_tensor(0, &tensor).expect("Failed to write input");
+    /// ```
+    pub fn write_input_tensor(&self, index: usize, tensor: &Tensor<'_>) -> Result<()> {
+        let frame = tensor.to_frame(self.input_info(index)?)?;
+        self.write_input_at(index, &frame)
+    }
+
+    /// Writes a frame to the input virtual stream with the given name.
+    pub fn write_input_by_name(&self, name: &str, frame: &[u8]) -> Result<()> {
+        self.write_input_at(self.input_index(name)?, frame)
+    }
+
+    /// Writes one frame to every input virtual stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `frames` - One frame per input stream, in the order of `inputs()`.
+    pub fn write_inputs(&self, frames: &[&[u8]]) -> Result<()> {
+        self.check_input_count(frames)?;
+        for (index, frame) in frames.iter().enumerate() {
+            self.write_input_at(index, frame)?;
+        }
+        Ok(())
+    }
+
+    /// Writes one frame to every input virtual stream, giving each write `timeout`.
+    pub fn write_inputs_timeout(&self, frames: &[&[u8]], timeout: Duration) -> Result<()> {
+        self.check_input_count(frames)?;
+        for (index, frame) in frames.iter().enumerate() {
+            self.write_input_at_timeout(index, frame, timeout)?;
         }
         Ok(())
     }
 
-    /// Reads the output virtual stream and parses detection results.
+    /// Checks that there is one frame per input stream.
+    fn check_input_count(&self, frames: &[&[u8]]) -> Result<()> {
+        if frames.len() != self.inputs().len() {
+            return Err(HailorsError::StreamCountMismatch {
+                direction: StreamDirection::Input,
+                expected: self.inputs().len(),
+                actual: frames.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Blocks until every frame written to the input virtual streams has been sent to the model.
+    ///
+    /// With a batch size above one, call this after the last frame so that a partial batch is
+    /// processed without waiting for more frames.
+    pub fn flush_inputs(&self) -> Result<()> {
+        (0..self.inputs().len()).try_for_each(|index| self.backend.flush_input(index))
+    }
+
+    /// Reads one raw frame from the output virtual stream at `index`.
+    pub fn read_output_frame(&self, index: usize) -> Result<Vec<u8>> {
+        let mut frame = vec![0_u8; self.output_frame_size_at(index)?];
+        self.backend.read_output(index, &mut frame)?;
+        Ok(frame)
+    }
+
+    /// Reads one raw frame from the output virtual stream at `index`, giving up after `timeout`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HailorsError::Timeout` if no frame arrived in time, in which case the frames
+    /// queued in the stream are discarded, or `HailorsError::StreamAborted` if the streams were
+    /// aborted.
+    pub fn read_output_frame_timeout(&self, index: usize, timeout: Duration) -> Result<Vec<u8>> {
+        let mut frame = vec![0_u8; self.output_frame_size_at(index)?];
+        self.backend.read_output_timeout(index, &mut frame, timeout)?;
+        Ok(frame)
+    }
+
+    /// Reads one raw frame from every output virtual stream, giving each read `timeout`.
+    pub fn read_outputs_timeout(&self, timeout: Duration) -> Result<Vec<Vec<u8>>> {
+        (0..self.outputs().len())
+            .map(|index| self.read_output_frame_timeout(index, timeout))
+            .collect()
+    }
+
+    /// Returns the frame size of the output stream at `index`.
+    fn output_frame_size_at(&self, index: usize) -> Result<usize> {
+        self.outputs()
+            .get(index)
+            .map(|info| info.frame_size)
+            .ok_or(HailorsError::StreamIndexOutOfRange {
+                direction: StreamDirection::Output,
+                index,
+                count: self.outputs().len(),
+            })
+    }
+
+    /// Aborts every virtual stream.
+    ///
+    /// Writes and reads blocked on another thread return `HailorsError::StreamAborted`, as do
+    /// later writes and reads until `resume` is called. Use `abort_handle` to abort from a thread
+    /// that does not own the device.
+    pub fn abort(&self) -> Result<()> {
+        self.backend.abort()
+    }
+
+    /// Resumes the virtual streams after `abort`. Frames queued before the abort are discarded.
+    pub fn resume(&self) -> Result<()> {
+        self.backend.resume()
+    }
+
+    /// Returns a handle that aborts and resumes the streams of this device from any thread,
+    /// for example from a watchdog while the device is moved into a `Pipeline`.
+    pub fn abort_handle(&self) -> AbortHandle {
+        AbortHandle::new(Arc::clone(&self.backend))
+    }
+
+    /// Reads one raw frame from every output virtual stream.
+    ///
+    /// # Returns
+    ///
+    /// Returns one frame per output stream, in the order of `outputs()`.
+    pub fn read_outputs(&self) -> Result<Vec<Vec<u8>>> {
+        (0..self.outputs().len())
+            .map(|index| self.read_output_frame(index))
+            .collect()
+    }
+
+    /// Allocates caller-owned buffers for one frame of every output stream.
+    ///
+    /// Reuse the buffers with `read_output_into` and `read_outputs_into` to read frames without
+    /// allocating.
+    pub fn output_buffers(&self) -> OutputBuffers {
+        OutputBuffers::new(self.outputs())
+    }
+
+    /// Creates a pool of output buffers for this device with `capacity` preallocated sets.
+    pub fn buffer_pool(&self, capacity: usize) -> BufferPool {
+        BufferPool::new(self.outputs(), capacity)
+    }
+
+    /// Reads one raw frame from the output virtual stream at `index` into caller-owned buffers.
+    ///
+    /// # Returns
+    ///
+    /// Returns the frame, borrowed from `buffers`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `index` is out of range, if `buffers` were not allocated for this
+    /// device's outputs or if reading fails.
+    pub fn read_output_frame_into<'a>(&self, index: usize, buffers: &'a mut OutputBuffers) -> Result<&'a [u8]> {
+        let info = self
+            .outputs()
+            .get(index)
+            .ok_or(HailorsError::StreamIndexOutOfRange {
+                direction: StreamDirection::Output,
+                index,
+                count: self.outputs().len(),
+            })?;
+        if buffers.len() != self.outputs().len() {
+            return Err(HailorsError::StreamCountMismatch {
+                direction: StreamDirection::Output,
+                expected: self.outputs().len(),
+                actual: buffers.len(),
+            });
+        }
+        let frame = buffers.frame_mut(index).expect("Buffer count was checked above");
+        if frame.len() != info.frame_size {
+            return Err(HailorsError::FrameSizeMismatch {
+                stream: info.name.clone(),
+                expected: info.frame_size,
+                actual: frame.len(),
+            });
+        }
+        self.backend.read_output(index, frame)?;
+        Ok(frame)
+    }
+
+    /// Reads one raw frame from every output virtual stream into caller-owned buffers.
+    pub fn read_outputs_into(&self, buffers: &mut OutputBuffers) -> Result<()> {
+        for index in 0..self.outputs().len() {
+            self.read_output_frame_into(index, buffers)?;
+        }
+        Ok(())
+    }
+
+    /// Reads one frame from the output virtual stream at `index` as a raw tensor.
+    ///
+    /// The frame is returned as HailoRT produced it, together with its data type, shape, format
+    /// order, strides and quantization parameters, without dequantizing or parsing it.
+    pub fn read_raw_output(&self, index: usize) -> Result<RawTensor> {
+        let frame = self.read_output_frame(index)?;
+        Ok(Tensor::new(&self.outputs()[index], frame))
+    }
+
+    /// Reads one frame from every output virtual stream as raw tensors.
+    ///
+    /// # Returns
+    ///
+    /// Returns one tensor per output stream, in the order of `outputs()`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// let device = hailors::HailoDevice::new("./hef/yolov8s_h8.hef").expect("Failed to create device");
+    /// # let input_data = vec![0u8; device.input_frame_size];
+    /// device.write_input(&input_data).expect("Failed to write input");
+    /// for (index, tensor) in device.read_raw_outputs().expect("Failed to read outputs").iter().enumerate() {
+    ///     println!("{}", tensor);
+    ///     tensor.save(format!("output{}.raw", index)).expect("Failed to dump output");
+    /// }
+    /// ```
+    pub fn read_raw_outputs(&self) -> Result<Vec<RawTensor>> {
+        (0..self.outputs().len())
+            .map(|index| self.read_raw_output(index))
+            .collect()
+    }
+
+    /// Runs one inference by writing all inputs and reading all outputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `frames` - One frame per input stream, in the order of `inputs()`.
+    ///
+    /// # Returns
+    ///
+    /// Returns one raw frame per output stream, in the order of `outputs()`.
+    pub fn infer(&self, frames: &[&[u8]]) -> Result<Vec<Vec<u8>>> {
+        self.write_inputs(frames)?;
+        self.read_outputs()
+    }
+
+    /// Reads the first output virtual stream and parses detection results.
     ///
     /// # Type Parameters
     ///
@@ -132,56 +604,159 @@ impl HailoDevice {
     ///
     /// Returns a vector of parsed results of type `T::Output`.
     pub fn read_output<T: Network>(&self, network_type: &T) -> Result<Vec<T::Output>> {
-        let mut output_data = vec![0.0_f32; self.output_frame_size / 4]; // FLOAT32
-
-        unsafe {
-            let status = hailors_read_output_frame(
-                *self.output_vstream,
-                output_data.as_mut_ptr() as *mut c_void,
-                output_data.len() * 4,
-            );
-            if status != HailoStatus::Success {
-                return Err(anyhow::anyhow!("Failed to read output frame"));
-            }
-        }
+        self.read_output_at(0, network_type)
+    }
 
-        let results = network_type.parse_output(&output_data);
-        Ok(results)
+    /// Reads the output virtual stream at `index` and parses it with the given network.
+    ///
+    /// The frame is passed to `Network::parse_tensor` with the stream's metadata, which by default
+    /// dequantizes UINT8 and UINT16 outputs with the stream's quantization parameters first.
+    pub fn read_output_at<T: Network>(&self, index: usize, network_type: &T) -> Result<Vec<T::Output>> {
+        let output_bytes = self.read_output_frame(index)?;
+        let tensor = Tensor::new(&self.outputs()[index], output_bytes);
+        Ok(network_type.parse_tensor(&tensor))
     }
-}
 
-impl Drop for HailoDevice {
-    /// Releases the Hailo device and associated resources when the `HailoDevice` is dropped.
-    fn drop(&mut self) {
-        unsafe {
-            hailors_release_vdevice(self.device_handle);
-        }
+    /// Reads the first output virtual stream into caller-owned buffers and parses it.
+    ///
+    /// Unlike `read_output`, this reuses the frame and dequantization buffers in `buffers` and
+    /// the vector of results, so steady-state inference performs no heap allocation for networks
+    /// that implement `Network::parse_output_into` without allocating.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffers` - Buffers allocated for this device's outputs, e.g. by `output_buffers()`.
+    /// * `network_type` - A reference to the network type used to parse the output.
+    /// * `results` - Receives the parsed results; it is cleared first.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hailors::network::YoloDetection;
+    ///
+    /// let device = hailors::HailoDevice::new("./hef/yolov8s_h8.hef").expect("Failed to create device");
+    /// let network = YoloDetection::from_vstream_info(&device.outputs()[0], 0.5).expect("Not an NMS output");
+    /// let mut buffers = device.output_buffers();
+    /// let mut detections = Vec::new();
+    /// # let frames: Vec<Vec<u8>> = Vec::new();
+    /// for frame in &frames {
+    ///     device.write_input(frame).expect("Failed to write input");
+    ///     device.read_output_into(&mut buffers, &network, &mut detections).expect("Failed to read output");
+    /// }
+    /// ```
+    pub fn read_output_into<T: Network>(
+        &self,
+        buffers: &mut OutputBuffers,
+        network_type: &T,
+        results: &mut Vec<T::Output>,
+    ) -> Result<()> {
+        self.read_output_at_into(0, buffers, network_type, results)
+    }
+
+    /// Reads the output virtual stream at `index` into caller-owned buffers and parses it.
+    pub fn read_output_at_into<T: Network>(
+        &self,
+        index: usize,
+        buffers: &mut OutputBuffers,
+        network_type: &T,
+        results: &mut Vec<T::Output>,
+    ) -> Result<()> {
+        self.read_output_frame_into(index, buffers)?;
+        let output_data = buffers.dequantize(index, &self.outputs()[index]);
+        results.clear();
+        network_type.parse_output_into(output_data, results);
+        Ok(())
     }
-}
 
-extern "C" {
-    /// Creates a Hailo virtual device.
-    fn hailors_create_vdevice(device_handle: *mut *mut c_void) -> HailoStatus;
-
-    /// Configures a Hailo Execution File (HEF) and sets up virtual streams.
-    fn hailors_configure_hef(
-        device_handle: *mut c_void,
-        hef_path: *const i8,
-        network_group: *mut *mut c_void,
-        input_vstreams: *mut *mut *mut c_void,
-        input_count: *mut usize,
-        output_vstreams: *mut *mut *mut c_void,
-        output_count: *mut usize,
-        input_frame_size: *mut usize,
-        output_frame_size: *mut usize,
-    ) -> HailoStatus;
-
-    /// Writes a frame to the input virtual stream.
-    fn hailors_write_input_frame(input_vstream: *mut c_void, data: *const c_void, len: usize) -> HailoStatus;
-
-    /// Reads data from the output virtual stream.
-    fn hailors_read_output_frame(output_vstream: *mut c_void, data: *mut c_void, len: usize) -> HailoStatus;
-
-    /// Releases a Hailo virtual device.
-    fn hailors_release_vdevice(device_handle: *mut c_void) -> HailoStatus;
+    /// Reads the output virtual stream with the given name and parses it with the given network.
+    pub fn read_output_by_name<T: Network>(&self, name: &str, network_type: &T) -> Result<Vec<T::Output>> {
+        self.read_output_at(self.output_index(name)?, network_type)
+    }
+
+    /// Runs inference on a batch of frames and parses the first output of each.
+    ///
+    /// Frames are written to the first input stream on one thread while outputs are read on
+    /// another, which keeps the vstream queues full instead of waiting for each frame's output
+    /// before writing the next. Combine with `DeviceConfig::with_batch_size` so the device also
+    /// processes the frames in batches. Outputs other than the first are read and discarded.
+    ///
+    /// # Arguments
+    ///
+    /// * `frames` - The input frames, each of exactly `input_frame_size` bytes.
+    /// * `network_type` - A reference to the network type used to parse the output.
+    ///
+    /// # Returns
+    ///
+    /// Returns the parsed results of every frame, in the order of `frames`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the model does not have exactly one input stream, if a frame has the
+    /// wrong size (before anything is written) or if writing or reading fails.
+    pub fn infer_batch<T>(&self, frames: &[&[u8]], network_type: &T) -> Result<Vec<Vec<T::Output>>>
+    where
+        T: Network + Sync,
+        T::Output: Send,
+    {
+        if self.inputs().len() != 1 {
+            return Err(HailorsError::StreamCountMismatch {
+                direction: StreamDirection::Input,
+                expected: 1,
+                actual: self.inputs().len(),
+            });
+        }
+        let input = &self.inputs()[0];
+        if let Some(frame) = frames.iter().find(|frame| frame.len() != input.frame_size) {
+            return Err(HailorsError::FrameSizeMismatch {
+                stream: input.name.clone(),
+                expected: input.frame_size,
+                actual: frame.len(),
+            });
+        }
+
+        let failed = &AtomicBool::new(false);
+        std::thread::scope(|scope| {
+            // The writer announces every written frame, so the reader never waits for a frame
+            // that was not written, and stops reading once the writer is done
+            let (written, to_read) = mpsc::channel();
+            let writer = scope.spawn(move || {
+                for frame in frames {
+                    if failed.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    if let Err(err) = self.backend.write_input(0, frame) {
+                        failed.store(true, Ordering::Relaxed);
+                        return Err(err);
+                    }
+                    let _ = written.send(());
+                }
+                // Push out a partial batch so the reader can finish
+                if !failed.load(Ordering::Relaxed) {
+                    if let Err(err) = self.backend.flush_input(0) {
+                        failed.store(true, Ordering::Relaxed);
+                        return Err(err);
+                    }
+                }
+                Ok(())
+            });
+
+            let mut results = Vec::with_capacity(frames.len());
+            let mut buffers = self.output_buffers();
+            let read_result = to_read.iter().try_for_each(|()| {
+                let mut frame_results = Vec::new();
+                self.read_output_at_into(0, &mut buffers, network_type, &mut frame_results)?;
+                results.push(frame_results);
+                for index in 1..self.outputs().len() {
+                    self.read_output_frame_into(index, &mut buffers)?;
+                }
+                Ok(())
+            });
+            if read_result.is_err() {
+                failed.store(true, Ordering::Relaxed);
+            }
+
+            writer.join().expect("Batch writer thread panicked")?;
+            read_result.map(|()| results)
+        })
+    }
 }
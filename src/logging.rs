@@ -0,0 +1,38 @@
+//! Routes the diagnostic messages of the C++ wrapper into `tracing`.
+//!
+//! hailors logs through the `tracing` facade and never prints on its own. Messages from the C++
+//! wrapper are forwarded under the `hailors::wrapper` target, while the library's own events and
+//! spans use their module paths, such as `hailors::hailort` for the configure, write and read
+//! spans.
+
+use std::ffi::{c_char, CStr};
+use std::sync::Once;
+
+use crate::ffi;
+
+/// Target of the events forwarded from the C++ wrapper.
+const WRAPPER_TARGET: &str = "hailors::wrapper";
+
+/// Registers the log callback with the C++ wrapper, once per process.
+///
+/// Called before the first virtual device is created, since every wrapper function that logs
+/// runs on a virtual device.
+pub(crate) fn install() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| unsafe { ffi::hailors_set_log_callback(Some(forward)) });
+}
+
+/// Forwards one message from the C++ wrapper at its `hailors_log_level`.
+extern "C" fn forward(level: i32, message: *const c_char) {
+    if message.is_null() {
+        return;
+    }
+    let message = unsafe { CStr::from_ptr(message) }.to_string_lossy();
+    match level {
+        1 => tracing::error!(target: WRAPPER_TARGET, "{}", message),
+        2 => tracing::warn!(target: WRAPPER_TARGET, "{}", message),
+        3 => tracing::info!(target: WRAPPER_TARGET, "{}", message),
+        4 => tracing::debug!(target: WRAPPER_TARGET, "{}", message),
+        _ => tracing::trace!(target: WRAPPER_TARGET, "{}", message),
+    }
+}
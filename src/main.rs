@@ -1,21 +1,164 @@
-use clap::Parser;
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use anyhow::Result;
 
-pub mod network;
-use hailors::network::{NetworkType, YoloDetection};
+use std::time::Duration;
+
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::EnvFilter;
+
+use hailors::bench::{Bench, BenchConfig};
+use hailors::labels::{Labels, COCO_LABELS};
+use hailors::manifest::{ModelManifest, NetworkSpec};
+use hailors::network::NetworkType;
+use hailors::{HailorsError, StreamDirection};
 
 /// Command-line interface for the Hailo inference application.
 ///
-/// This CLI allows users to run inference using the Hailo AI hardware.
-/// Users can specify the HEF file, input data, and the network type (e.g., YOLO Detection).
+/// This CLI allows users to run inference using the Hailo AI hardware and to inspect the
+/// devices attached to the host.
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
+    /// The command to run.
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// Commands supported by the CLI.
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run inference on an input file.
+    Run(RunArgs),
+    /// List the Hailo devices attached to this host.
+    Devices,
+    /// Print the identity, temperature and power consumption of the Hailo devices.
+    Health(HealthArgs),
+    /// Measure the throughput and latency of a HEF.
+    Bench(BenchArgs),
+    /// Print the architecture, network groups and streams of a HEF without a device.
+    Inspect(InspectArgs),
+}
+
+/// Arguments of the `inspect` command.
+#[derive(Args, Debug)]
+struct InspectArgs {
+    /// Path to the Hailo Execution File (HEF).
+    #[arg(long)]
+    hef: String,
+
+    /// Output format.
+    #[arg(long, value_enum, default_value = "text")]
+    format: OutputFormat,
+}
+
+/// Arguments of the `bench` command.
+///
+/// The run ends after `--frames` frames or `--duration` seconds, whichever comes first, and
+/// defaults to 1000 frames.
+#[derive(Args, Debug)]
+struct BenchArgs {
+    /// Path to the Hailo Execution File (HEF).
+    #[arg(long)]
+    hef: String,
+
+    /// Network group to benchmark from a HEF holding several network groups.
+    #[arg(long)]
+    network_group: Option<String>,
+
+    /// ID of the device to run on, as listed by the `devices` command.
+    #[arg(long = "device-id")]
+    device_ids: Vec<String>,
+
+    /// Number of frames the device processes per batch.
+    #[arg(long)]
+    batch_size: Option<u16>,
+
+    /// Number of timed frames.
+    #[arg(long)]
+    frames: Option<u64>,
+
+    /// Length of the timed run, in seconds.
+    #[arg(long)]
+    duration: Option<f64>,
+
+    /// Frames inferred before the timed run.
+    #[arg(long, default_value = "10")]
+    warmup: u32,
+
+    /// Maximum number of frames in flight.
+    #[arg(long, default_value = "4")]
+    queue_depth: usize,
+
+    /// Raw input frame to send to the first input stream; defaults to a zero-filled frame.
+    #[arg(short, long)]
+    input: Option<String>,
+
+    /// Output format.
+    #[arg(long, value_enum, default_value = "text")]
+    format: OutputFormat,
+}
+
+/// Arguments of the `health` command.
+#[derive(Args, Debug)]
+struct HealthArgs {
+    /// ID of the device to report on, as listed by the `devices` command.
+    ///
+    /// May be given several times. Defaults to every attached device.
+    #[arg(long = "device-id")]
+    device_ids: Vec<String>,
+
+    /// Output format.
+    #[arg(long, value_enum, default_value = "text")]
+    format: OutputFormat,
+
+    /// Number of power measurements to average; 0 skips power measurement.
+    #[arg(long, default_value = "10")]
+    power_samples: u32,
+}
+
+/// Output format of the reporting commands.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum OutputFormat {
+    /// Human-readable text.
+    Text,
+    /// Pretty-printed JSON.
+    Json,
+}
+
+/// Identity and health of one device, as printed by the `health` command.
+#[derive(serde::Serialize)]
+struct HealthReport {
+    info: hailors::DeviceInfo,
+    health: hailors::DeviceHealth,
+}
+
+/// Arguments of the `run` command.
+///
+/// Users can specify the HEF file, input data, and the network type (e.g., YOLO Detection), or
+/// a model manifest that declares all of them.
+#[derive(Args, Debug)]
+struct RunArgs {
+    /// Path to a model manifest (TOML) declaring the HEF, network, labels and preprocessing.
+    #[arg(long, conflicts_with_all = ["hef", "network"])]
+    model: Option<String>,
+
     /// Path to the Hailo Execution File (HEF).
     ///
     /// This file contains the compiled neural network model for the Hailo hardware.
-    #[arg(short, long)]
-    hef: String,
+    #[arg(long, required_unless_present = "model")]
+    hef: Option<String>,
+
+    /// Network group to run from a HEF holding several network groups.
+    ///
+    /// Defaults to the network group of the manifest, or the first network group in the HEF.
+    #[arg(long)]
+    network_group: Option<String>,
+
+    /// ID of the device to run on, as listed by the `devices` command.
+    ///
+    /// May be given several times to run on several devices. Defaults to any available device.
+    #[arg(long = "device-id")]
+    device_ids: Vec<String>,
 
     /// Input file to process.
     ///
@@ -27,38 +170,245 @@ struct Cli {
     /// Select the network type.
     ///
     /// Choose between available network types, such as YOLO Detection or YOLO Pose.
-    #[arg(short, long, value_enum)]
-    network: NetworkType,
+    #[arg(short, long, value_enum, required_unless_present = "model")]
+    network: Option<NetworkType>,
+
+    /// Label file naming the detected classes: text with one label per line, JSON or YAML.
+    ///
+    /// Defaults to the labels of the manifest, or the COCO labels for 80-class models.
+    #[arg(long)]
+    labels: Option<String>,
 
-    /// Confidence threshold for detections (default: 0.5).
+    /// Confidence threshold for detections (default: 0.5, or the threshold of the manifest).
     ///
     /// Only detections with a confidence score above this threshold will be reported.
-    #[arg(short, long, default_value = "0.5")]
-    threshold: f32,
+    #[arg(short, long)]
+    threshold: Option<f32>,
 }
 
 fn main() -> Result<()> {
     // Parse command-line arguments provided by the user.
     let cli = Cli::parse();
+    init_logging();
+
+    match cli.command {
+        Command::Run(args) => run(args),
+        Command::Devices => list_devices(),
+        Command::Health(args) => print_health(args),
+        Command::Bench(args) => bench(args),
+        Command::Inspect(args) => inspect(args),
+    }
+}
 
-    // Initialize the Hailo device with the provided HEF file.
-    let device = hailors::HailoDevice::new(&cli.hef)
+/// Sends log messages to stderr, filtered by `RUST_LOG` and showing warnings by default.
+///
+/// Closing spans are logged with their duration, so `RUST_LOG=hailors=info` times the HEF
+/// configuration and `RUST_LOG=hailors=trace` every write and read.
+fn init_logging() {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn")))
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(std::io::stderr)
+        .init();
+}
+
+/// Lists the physical devices with their PCIe bus address and architecture.
+fn list_devices() -> Result<()> {
+    let devices = hailors::devices()?;
+    if devices.is_empty() {
+        println!("No Hailo devices found.");
+        return Ok(());
+    }
+
+    println!("{:<16} {:<14} ARCHITECTURE", "DEVICE ID", "PCIE");
+    for device in devices {
+        let pcie = device.pcie.map_or_else(|| "-".to_string(), |address| address.to_string());
+        let architecture = device
+            .architecture
+            .map_or_else(|| "unknown".to_string(), |architecture| architecture.to_string());
+        println!("{:<16} {:<14} {}", device.id, pcie, architecture);
+    }
+    Ok(())
+}
+
+/// Prints the identity and health of the selected devices.
+fn print_health(args: HealthArgs) -> Result<()> {
+    let reports = hailors::devices()?
+        .into_iter()
+        .filter(|device| args.device_ids.is_empty() || args.device_ids.contains(&device.id))
+        .map(|device| {
+            Ok(HealthReport {
+                info: device.info()?,
+                health: device.health(args.power_samples)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    match args.format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&reports)?),
+        OutputFormat::Text => {
+            if reports.is_empty() {
+                println!("No Hailo devices found.");
+            }
+            for report in reports {
+                print_health_report(&report);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Prints the identity and health of one device as text.
+fn print_health_report(report: &HealthReport) {
+    let info = &report.info;
+    let health = &report.health;
+    println!("Device {}", info.device_id);
+    println!("  Board:            {} ({})", info.board_name, info.product_name);
+    println!("  Serial number:    {}", info.serial_number);
+    println!("  Part number:      {}", info.part_number);
+    println!("  Architecture:     {}", info.architecture);
+    println!(
+        "  Firmware:         {}{}",
+        info.firmware_version,
+        if info.release_firmware { "" } else { " (debug)" }
+    );
+    if let Some(extended) = &info.extended {
+        println!("  NN clock rate:    {} MHz", extended.nn_clock_rate_hz / 1_000_000);
+        println!("  Boot source:      {}", extended.boot_source);
+    }
+    println!(
+        "  Temperature:      {:.1} C / {:.1} C",
+        health.temperature.ts0_celsius, health.temperature.ts1_celsius
+    );
+    match &health.power {
+        Some(power) => println!(
+            "  Power:            {:.2} W avg, {:.2} W min, {:.2} W max ({} samples)",
+            power.average_watts, power.min_watts, power.max_watts, power.samples
+        ),
+        None => println!("  Power:            not available"),
+    }
+}
+
+/// Benchmarks a HEF and prints the report.
+fn bench(args: BenchArgs) -> Result<()> {
+    let mut config = hailors::DeviceConfig::new()
+        .with_vdevice_params(hailors::VDeviceParams::new().with_device_ids(&args.device_ids));
+    if let Some(network_group) = &args.network_group {
+        config = config.with_network_group(network_group);
+    }
+    if let Some(batch_size) = args.batch_size {
+        config = config.with_batch_size(batch_size);
+    }
+    let device = hailors::HailoDevice::with_config(&args.hef, &config)?;
+
+    let mut bench_config = BenchConfig::new()
+        .with_warmup_frames(args.warmup)
+        .with_queue_depth(args.queue_depth);
+    if let Some(frames) = args.frames {
+        bench_config = bench_config.with_frames(frames);
+    }
+    if let Some(duration) = args.duration {
+        bench_config = bench_config.with_duration(Duration::try_from_secs_f64(duration)?);
+    }
+
+    let mut bench = Bench::new(bench_config);
+    if let Some(input) = &args.input {
+        // Check the file against the first input here, rather than failing in the writer thread
+        let info = device.inputs().first().ok_or(HailorsError::StreamCountMismatch {
+            direction: StreamDirection::Input,
+            expected: 1,
+            actual: 0,
+        })?;
+        let frame = std::fs::read(input)?;
+        if frame.len() != info.frame_size {
+            return Err(HailorsError::FrameSizeMismatch {
+                stream: info.name.clone(),
+                expected: info.frame_size,
+                actual: frame.len(),
+            }
+            .into());
+        }
+        let mut frames: Vec<Vec<u8>> = device.inputs().iter().map(|info| vec![0u8; info.frame_size]).collect();
+        frames[0] = frame;
+        bench = bench.with_preprocess(move |_| frames.clone());
+    }
+    let report = bench.run(&device)?;
+
+    match args.format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Text => println!("{}", report),
+    }
+    Ok(())
+}
+
+/// Prints the model description of a HEF.
+fn inspect(args: InspectArgs) -> Result<()> {
+    let hef = hailors::Hef::open(&args.hef)?;
+    match args.format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&hef)?),
+        OutputFormat::Text => println!("{}", hef),
+    }
+    Ok(())
+}
+
+/// Runs inference on the input file and prints the parsed results.
+fn run(cli: RunArgs) -> Result<()> {
+    // Take the HEF and network from the model manifest, or from the command line.
+    let manifest = cli.model.as_deref().map(ModelManifest::load).transpose()?;
+    let (hef, mut network, mut config) = match &manifest {
+        Some(manifest) => (
+            manifest.hef.to_string_lossy().into_owned(),
+            manifest.network.clone(),
+            manifest.device_config(),
+        ),
+        None => (
+            cli.hef.clone().expect("clap requires --hef without --model"),
+            NetworkSpec::from(cli.network.clone().expect("clap requires --network without --model")),
+            hailors::DeviceConfig::new(),
+        ),
+    };
+    if let Some(threshold) = cli.threshold {
+        network = network.with_threshold(threshold);
+    }
+
+    // Initialize the Hailo device with the HEF file.
+    config = config.with_vdevice_params(hailors::VDeviceParams::new().with_device_ids(&cli.device_ids));
+    if let Some(network_group) = &cli.network_group {
+        config = config.with_network_group(network_group);
+    }
+    let device = hailors::HailoDevice::with_config(&hef, &config)
         .expect("Failed to initialize the Hailo device with the specified HEF file.");
 
     // Load the input data (e.g., an image file) into memory.
-    let input_data = std::fs::read(&cli.input)
+    let mut input_data = std::fs::read(&cli.input)
         .expect("Failed to read the specified input file.");
 
+    // Check the input against the manifest and apply its preprocessing.
+    let mut labels = None;
+    if let Some(manifest) = &manifest {
+        let input_info = &device.inputs()[0];
+        manifest.check_input(input_info)?;
+        input_data = manifest.preprocess(&input_data, input_info)?;
+        labels = manifest.load_labels()?;
+    }
+    if let Some(path) = &cli.labels {
+        labels = Some(Labels::load(path)?);
+    }
+
     // Determine the network type specified by the user.
-    match cli.network {
+    match network {
         // YOLO Detection branch: Processes the input data for object detection.
-        NetworkType::YoloDetection => {
-            // Configure the YOLO Detection network with specified parameters.
-            let network = YoloDetection {
-                num_classes: 80,              // Number of object classes (e.g., COCO dataset classes).
-                max_bboxes_per_class: 100,    // Maximum bounding boxes per class.
-                threshold: cli.threshold,     // Confidence threshold for detections.
-            };
+        NetworkSpec::YoloDetection(spec) => {
+            // Configure the YOLO Detection network, taking unset parameters from the model.
+            let mut network = spec.network(&device.outputs()[0])?;
+
+            // Name the classes, falling back to COCO for 80-class models.
+            if labels.is_none() && network.num_classes == COCO_LABELS.len() {
+                labels = Some(Labels::coco());
+            }
+            if let Some(labels) = labels {
+                network = network.with_labels(labels);
+            }
 
             // Write the input data to the Hailo device for inference.
             device.write_input(&input_data)
@@ -70,25 +420,15 @@ fn main() -> Result<()> {
 
             // Iterate over and display the detection results.
             for detection in detections {
-                println!(
-                    "YOLO Detection: Class {}, Confidence {:.2}, BBox: ({:.2}, {:.2}, {:.2}, {:.2})",
-                    detection.class_id,       // Detected object class ID.
-                    detection.confidence,     // Confidence score of the detection.
-                    detection.bbox.0,         // Bounding box: Top-left X coordinate.
-                    detection.bbox.1,         // Bounding box: Top-left Y coordinate.
-                    detection.bbox.2,         // Bounding box: Bottom-right X coordinate.
-                    detection.bbox.3          // Bounding box: Bottom-right Y coordinate.
-                );
+                // Prints e.g. "YOLO Detection: dog 0.91 (0.10, 0.20, 0.60, 0.90)".
+                println!("YOLO Detection: {}", detection);
             }
         }
 
         // YOLO Pose branch: Processes the input data for pose detection.
-        NetworkType::YoloPose => {
-            // Configure the YOLO Pose network with specified parameters.
-            let network = hailors::network::YoloPose {
-                num_keypoints: 17,            // Number of keypoints for pose detection.
-                threshold: cli.threshold,     // Confidence threshold for detections.
-            };
+        NetworkSpec::YoloPose(spec) => {
+            // Configure the YOLO Pose network with its parameters.
+            let network = spec.network();
 
             // Write the input data to the Hailo device for inference.
             device.write_input(&input_data)
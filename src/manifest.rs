@@ -0,0 +1,374 @@
+//! Model manifests that bundle a HEF with everything needed to run it.
+//!
+//! A manifest is a TOML file next to the HEF that declares which `Network` parses the outputs
+//! and with which parameters, where the class labels are, and how input files are laid out and
+//! preprocessed, so applications do not hardcode them per model:
+//!
+//! ```toml
+//! hef = "yolov8s_h8.hef"
+//! labels = "coco.txt"
+//!
+//! [network]
+//! type = "yolo-detection"
+//! threshold = 0.4
+//!
+//! [input]
+//! width = 640
+//! height = 640
+//! channels = 3
+//! order = "nhwc"
+//!
+//! [preprocess]
+//! swap_rb = true
+//! ```
+//!
+//! Relative paths are resolved against the directory of the manifest.
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::config::DeviceConfig;
+use crate::error::{HailorsError, Result};
+use crate::labels::Labels;
+use crate::network::{NetworkType, YoloDetection, YoloPose};
+use crate::vstream::{FormatType, VStreamInfo};
+
+/// A model manifest, as loaded from a TOML file.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModelManifest {
+    /// Path to the HEF.
+    pub hef: PathBuf,
+    /// Network group to run from a HEF holding several network groups.
+    pub network_group: Option<String>,
+    /// Path to the class label file, as text with one label per line, JSON or YAML.
+    pub labels: Option<PathBuf>,
+    /// Network that parses the model outputs, with its parameters.
+    pub network: NetworkSpec,
+    /// Expected layout of input files. Defaults to the shape of the first input stream in NHWC
+    /// order.
+    pub input: Option<InputLayout>,
+    /// Preprocessing applied to input files before they are written to the device.
+    #[serde(default)]
+    pub preprocess: Preprocess,
+}
+
+/// The network named by a manifest, selected by its `type` key.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum NetworkSpec {
+    /// YOLO Detection, `type = "yolo-detection"`.
+    YoloDetection(DetectionSpec),
+    /// YOLO Pose estimation, `type = "yolo-pose"`.
+    YoloPose(PoseSpec),
+}
+
+impl NetworkSpec {
+    /// Overrides the confidence threshold.
+    pub fn with_threshold(mut self, threshold: f32) -> Self {
+        match &mut self {
+            NetworkSpec::YoloDetection(spec) => spec.threshold = threshold,
+            NetworkSpec::YoloPose(spec) => spec.threshold = threshold,
+        }
+        self
+    }
+}
+
+impl From<NetworkType> for NetworkSpec {
+    /// Returns the network with its default parameters.
+    fn from(network_type: NetworkType) -> Self {
+        match network_type {
+            NetworkType::YoloDetection => NetworkSpec::YoloDetection(DetectionSpec {
+                threshold: default_threshold(),
+                num_classes: None,
+                max_bboxes_per_class: None,
+            }),
+            NetworkType::YoloPose => NetworkSpec::YoloPose(PoseSpec {
+                threshold: default_threshold(),
+                num_keypoints: default_num_keypoints(),
+            }),
+        }
+    }
+}
+
+/// Parameters of a YOLO Detection network.
+///
+/// The number of classes and boxes per class default to the NMS parameters of the model.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DetectionSpec {
+    /// Confidence threshold for detections.
+    #[serde(default = "default_threshold")]
+    pub threshold: f32,
+    /// Number of object classes.
+    pub num_classes: Option<usize>,
+    /// Maximum number of bounding boxes per class.
+    pub max_bboxes_per_class: Option<usize>,
+}
+
+impl DetectionSpec {
+    /// Configures YOLO Detection for the given output stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HailorsError::InvalidManifest` if the manifest leaves out the number of classes
+    /// or boxes per class and the stream carries no NMS parameters to take them from.
+    pub fn network(&self, output: &VStreamInfo) -> Result<YoloDetection> {
+        let nms = output.nms;
+        let num_classes = self.num_classes.or(nms.map(|nms| nms.number_of_classes as usize));
+        let max_bboxes_per_class = self
+            .max_bboxes_per_class
+            .or(nms.map(|nms| nms.max_bboxes_per_class as usize));
+        match (num_classes, max_bboxes_per_class) {
+            (Some(num_classes), Some(max_bboxes_per_class)) => Ok(YoloDetection {
+                num_classes,
+                max_bboxes_per_class,
+                threshold: self.threshold,
+                labels: None,
+            }),
+            _ => Err(HailorsError::InvalidManifest(format!(
+                "Output stream {} is not an NMS stream; set num_classes and max_bboxes_per_class",
+                output.name
+            ))),
+        }
+    }
+}
+
+/// Parameters of a YOLO Pose estimation network.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PoseSpec {
+    /// Confidence threshold for pose detections.
+    #[serde(default = "default_threshold")]
+    pub threshold: f32,
+    /// Number of keypoints per pose.
+    #[serde(default = "default_num_keypoints")]
+    pub num_keypoints: usize,
+}
+
+impl PoseSpec {
+    /// Configures YOLO Pose.
+    pub fn network(&self) -> YoloPose {
+        YoloPose {
+            num_keypoints: self.num_keypoints,
+            threshold: self.threshold,
+        }
+    }
+}
+
+fn default_threshold() -> f32 {
+    0.5
+}
+
+fn default_num_keypoints() -> usize {
+    17
+}
+
+/// Layout of input files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InputLayout {
+    /// Frame width in pixels.
+    pub width: u32,
+    /// Frame height in pixels.
+    pub height: u32,
+    /// Number of channels per pixel.
+    pub channels: u32,
+    /// Order of the values in an input file.
+    #[serde(default)]
+    pub order: InputOrder,
+}
+
+/// Order of the values in an input file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InputOrder {
+    /// Interleaved channels, row by row, as the device expects.
+    #[default]
+    Nhwc,
+    /// One plane per channel, converted to NHWC before writing.
+    Nchw,
+}
+
+/// Preprocessing applied to 8-bit input files.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Preprocess {
+    /// Swap the first and third channel, e.g. to feed BGR images to an RGB model.
+    #[serde(default)]
+    pub swap_rb: bool,
+    /// Per-channel mean subtracted from every value. Normalized inputs are written as FLOAT32
+    /// and quantized by HailoRT.
+    pub mean: Option<Vec<f32>>,
+    /// Per-channel standard deviation every value is divided by.
+    pub std: Option<Vec<f32>>,
+}
+
+impl Preprocess {
+    /// Returns whether the input is normalized to FLOAT32.
+    pub fn normalizes(&self) -> bool {
+        self.mean.is_some() || self.std.is_some()
+    }
+}
+
+impl ModelManifest {
+    /// Loads a manifest from a TOML file and resolves its relative paths against the directory
+    /// of the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HailorsError::Io` if the file cannot be read, or `HailorsError::InvalidManifest`
+    /// if it is not a valid manifest.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let mut manifest = Self::from_toml(&std::fs::read_to_string(path)?)?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+        manifest.hef = dir.join(&manifest.hef);
+        manifest.labels = manifest.labels.map(|labels| dir.join(labels));
+        Ok(manifest)
+    }
+
+    /// Parses a manifest from TOML, leaving relative paths as they are.
+    pub fn from_toml(toml: &str) -> Result<Self> {
+        let manifest: Self = toml::from_str(toml).map_err(|err| HailorsError::InvalidManifest(err.to_string()))?;
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    fn validate(&self) -> Result<()> {
+        for (name, values) in [("mean", &self.preprocess.mean), ("std", &self.preprocess.std)] {
+            let (Some(values), Some(input)) = (values, &self.input) else {
+                continue;
+            };
+            if values.len() != input.channels as usize {
+                return Err(HailorsError::InvalidManifest(format!(
+                    "preprocess.{} has {} values for {} channels",
+                    name,
+                    values.len(),
+                    input.channels
+                )));
+            }
+        }
+        if self.preprocess.std.iter().flatten().any(|&std| std == 0.0) {
+            return Err(HailorsError::InvalidManifest("preprocess.std contains 0".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Returns the device configuration of the model: its network group, and FLOAT32 inputs if
+    /// the preprocessing normalizes them.
+    ///
+    /// Further options, such as the devices to run on, can be added to the returned
+    /// configuration.
+    pub fn device_config(&self) -> DeviceConfig {
+        let mut config = DeviceConfig::new();
+        if let Some(network_group) = &self.network_group {
+            config = config.with_network_group(network_group);
+        }
+        if self.preprocess.normalizes() {
+            config = config.with_input_format(FormatType::Float32);
+        }
+        config
+    }
+
+    /// Loads the class labels, or returns `None` if the manifest names no label file.
+    ///
+    /// See `Labels::load` for the supported formats.
+    pub fn load_labels(&self) -> Result<Option<Labels>> {
+        self.labels.as_ref().map(Labels::load).transpose()
+    }
+
+    /// Checks the declared input layout against the input stream of the configured model.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HailorsError::InvalidManifest` if the width, height or channels differ.
+    pub fn check_input(&self, input: &VStreamInfo) -> Result<()> {
+        let Some(layout) = &self.input else {
+            return Ok(());
+        };
+        let shape = input.shape;
+        if (layout.height, layout.width, layout.channels) != (shape.height, shape.width, shape.features) {
+            return Err(HailorsError::InvalidManifest(format!(
+                "Input is declared as {}x{}x{} but stream {} is {}x{}x{}",
+                layout.height, layout.width, layout.channels, input.name, shape.height, shape.width, shape.features
+            )));
+        }
+        Ok(())
+    }
+
+    /// Converts an 8-bit input file into a frame for the given input stream.
+    ///
+    /// Planar files are interleaved, the first and third channel are swapped if `swap_rb` is set,
+    /// and the values are normalized to native-endian FLOAT32 if a mean or standard deviation is
+    /// set.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HailorsError::FrameSizeMismatch` if the file does not hold one frame of the
+    /// stream's shape, or `HailorsError::InvalidManifest` if the mean or standard deviation does
+    /// not have one value per channel of the stream.
+    pub fn preprocess(&self, data: &[u8], input: &VStreamInfo) -> Result<Vec<u8>> {
+        let shape = input.shape;
+        let pixels = shape.height as usize * shape.width as usize;
+        let channels = shape.features as usize;
+        for (name, values) in [("mean", &self.preprocess.mean), ("std", &self.preprocess.std)] {
+            if let Some(values) = values.as_ref().filter(|values| values.len() != channels) {
+                return Err(HailorsError::InvalidManifest(format!(
+                    "preprocess.{} has {} values for {} channels of {}",
+                    name,
+                    values.len(),
+                    channels,
+                    input.name
+                )));
+            }
+        }
+        if data.len() != pixels * channels {
+            return Err(HailorsError::FrameSizeMismatch {
+                stream: input.name.clone(),
+                expected: pixels * channels,
+                actual: data.len(),
+            });
+        }
+
+        let order = self.input.map(|layout| layout.order).unwrap_or_default();
+        let swap_rb = self.preprocess.swap_rb && channels >= 3;
+        let value = |pixel: usize, channel: usize| {
+            let channel = match channel {
+                0 if swap_rb => 2,
+                2 if swap_rb => 0,
+                channel => channel,
+            };
+            match order {
+                InputOrder::Nhwc => data[pixel * channels + channel],
+                InputOrder::Nchw => data[channel * pixels + pixel],
+            }
+        };
+
+        if !self.preprocess.normalizes() {
+            if order == InputOrder::Nhwc && !swap_rb {
+                return Ok(data.to_vec());
+            }
+            return Ok((0..pixels)
+                .flat_map(|pixel| (0..channels).map(move |channel| (pixel, channel)))
+                .map(|(pixel, channel)| value(pixel, channel))
+                .collect());
+        }
+
+        // Both lengths were checked above, so only an unset mean or std falls back to identity
+        let per_channel = |values: &Option<Vec<f32>>, channel: usize, default: f32| {
+            values.as_ref().map_or(default, |values| values[channel])
+        };
+        let mut frame = Vec::with_capacity(data.len() * 4);
+        for pixel in 0..pixels {
+            for channel in 0..channels {
+                let mean = per_channel(&self.preprocess.mean, channel, 0.0);
+                let std = per_channel(&self.preprocess.std, channel, 1.0);
+                let normalized = (f32::from(value(pixel, channel)) - mean) / std;
+                frame.extend_from_slice(&normalized.to_ne_bytes());
+            }
+        }
+        Ok(frame)
+    }
+}
@@ -0,0 +1,301 @@
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::backend::InferenceBackend;
+use crate::error::{HailorsError, Operation, Result};
+use crate::quant;
+use crate::vstream::{FormatOrder, FormatType, StreamDirection, VStreamFormat, VStreamInfo, VStreamShape};
+
+/// A pure-Rust inference backend for testing without Hailo hardware.
+///
+/// The mock declares its input and output stream shapes up front, records every frame written
+/// to it and answers reads with scripted output frames. Inputs are UINT8 and outputs are FLOAT32,
+/// matching the formats the HailoRT backend configures. When no scripted frame is queued for an
+/// output stream, a zero-filled frame is returned.
+///
+/// Clones share the same state, so a test can keep a clone to script outputs and inspect inputs
+/// after handing the backend to a `HailoDevice`.
+///
+/// # Example
+///
+/// ```rust
+/// use hailors::{HailoDevice, MockBackend, VStreamShape};
+///
+/// let mock = MockBackend::new()
+///     .with_input("model/input_layer1", VStreamShape::new(2, 2, 3))
+///     .with_output("model/output_layer1", VStreamShape::new(1, 1, 4));
+/// mock.push_output_f32(0, &[1.0, 2.0, 3.0, 4.0]).unwrap();
+///
+/// let device = HailoDevice::with_backend(mock.clone());
+/// device.write_input(&[0u8; 12]).unwrap();
+/// assert_eq!(mock.written_inputs(0).len(), 1);
+/// ```
+#[derive(Clone, Default)]
+pub struct MockBackend {
+    inputs: Vec<VStreamInfo>,
+    outputs: Vec<VStreamInfo>,
+    /// Simulated inference time added to every output read.
+    latency: Option<Duration>,
+    /// Whether written input frames are dropped instead of kept for `written_inputs`.
+    skip_recording: bool,
+    state: Arc<Mutex<MockState>>,
+}
+
+#[derive(Default)]
+struct MockState {
+    /// Output frames waiting to be read, in order, per output stream.
+    scripted_outputs: Vec<VecDeque<Vec<u8>>>,
+    /// Every input frame written so far, per input stream.
+    written_inputs: Vec<Vec<Vec<u8>>>,
+    /// Number of flushes so far, per input stream.
+    flushes: Vec<usize>,
+    /// Whether the streams are aborted, until `resume` is called.
+    aborted: bool,
+}
+
+impl MockBackend {
+    /// Creates a mock backend without any streams.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares an UINT8 input stream with the given name and shape.
+    pub fn with_input(self, name: &str, shape: VStreamShape) -> Self {
+        let format = VStreamFormat {
+            format_type: FormatType::Uint8,
+            order: FormatOrder::Nhwc,
+        };
+        self.with_input_info(VStreamInfo::new(name, shape, format))
+    }
+
+    /// Declares a FLOAT32 output stream with the given name and shape.
+    pub fn with_output(self, name: &str, shape: VStreamShape) -> Self {
+        let format = VStreamFormat {
+            format_type: FormatType::Float32,
+            order: FormatOrder::Nhwc,
+        };
+        self.with_output_info(VStreamInfo::new(name, shape, format))
+    }
+
+    /// Declares an input stream with fully specified stream information.
+    pub fn with_input_info(mut self, info: VStreamInfo) -> Self {
+        self.inputs.push(info);
+        {
+            let mut state = self.state.lock().unwrap();
+            state.written_inputs.push(Vec::new());
+            state.flushes.push(0);
+        }
+        self
+    }
+
+    /// Declares an output stream with fully specified stream information, such as an NMS
+    /// stream created with `VStreamInfo::nms`.
+    pub fn with_output_info(mut self, info: VStreamInfo) -> Self {
+        self.outputs.push(info);
+        self.state.lock().unwrap().scripted_outputs.push(VecDeque::new());
+        self
+    }
+
+    /// Simulates inference time by sleeping for `latency` on every output read.
+    ///
+    /// Combined with `without_input_recording`, this makes the mock a stand-in device for
+    /// benchmarking the host side of an application.
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = Some(latency);
+        self
+    }
+
+    /// Stops keeping a copy of every written input frame, so that long runs use constant memory.
+    pub fn without_input_recording(mut self) -> Self {
+        self.skip_recording = true;
+        self
+    }
+
+    /// Queues a raw output frame to be returned by the next read of an output stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream does not exist or the frame size does not match its shape.
+    pub fn push_output(&self, index: usize, frame: Vec<u8>) -> Result<()> {
+        let frame_size = self.output_frame_size(index)?;
+        if frame.len() != frame_size {
+            return Err(HailorsError::FrameSizeMismatch {
+                stream: self.outputs[index].name.clone(),
+                expected: frame_size,
+                actual: frame.len(),
+            });
+        }
+        self.state.lock().unwrap().scripted_outputs[index].push_back(frame);
+        Ok(())
+    }
+
+    /// Queues an output frame given as FLOAT32 values.
+    ///
+    /// The values are quantized to the stream's format type with its quantization parameters, so
+    /// UINT8 and UINT16 streams read back the values as `quant::dequantize` rounds them. Values
+    /// beyond the declared output shape are ignored and missing values are filled with 0.0, which
+    /// makes it easy to script sparse outputs such as NMS results.
+    pub fn push_output_f32(&self, index: usize, values: &[f32]) -> Result<()> {
+        let frame_size = self.output_frame_size(index)?;
+        let info = &self.outputs[index];
+        let mut padded = vec![0.0; frame_size / info.format.format_type.element_size()];
+        for (padded, &value) in padded.iter_mut().zip(values) {
+            *padded = value;
+        }
+        let mut frame = Vec::with_capacity(frame_size);
+        quant::quantize(info.format.format_type, info.quant, &padded, &mut frame);
+        self.state.lock().unwrap().scripted_outputs[index].push_back(frame);
+        Ok(())
+    }
+
+    /// Queues every output frame stored in a recorded raw dump file.
+    ///
+    /// The file must contain one or more back-to-back frames of the output stream, as captured
+    /// from a real device.
+    pub fn push_output_file<P: AsRef<Path>>(&self, index: usize, path: P) -> Result<()> {
+        let frame_size = self.output_frame_size(index)?;
+        let data = std::fs::read(path)?;
+        if frame_size == 0 || data.len() % frame_size != 0 {
+            return Err(HailorsError::InvalidArgument(format!(
+                "Recorded output size {} is not a multiple of the frame size {}",
+                data.len(),
+                frame_size
+            )));
+        }
+        let mut state = self.state.lock().unwrap();
+        for frame in data.chunks_exact(frame_size) {
+            state.scripted_outputs[index].push_back(frame.to_vec());
+        }
+        Ok(())
+    }
+
+    /// Returns a copy of every frame written so far to an input stream.
+    pub fn written_inputs(&self, index: usize) -> Vec<Vec<u8>> {
+        self.state
+            .lock()
+            .unwrap()
+            .written_inputs
+            .get(index)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Returns how many times an input stream has been flushed.
+    pub fn flush_count(&self, index: usize) -> usize {
+        self.state.lock().unwrap().flushes.get(index).copied().unwrap_or_default()
+    }
+
+    /// Returns `true` while the streams are aborted.
+    pub fn is_aborted(&self) -> bool {
+        self.state.lock().unwrap().aborted
+    }
+
+    /// Fails with `HailorsError::StreamAborted` while the streams are aborted.
+    fn check_aborted(&self, operation: Operation, stream: &VStreamInfo) -> Result<()> {
+        if self.is_aborted() {
+            return Err(HailorsError::StreamAborted {
+                operation,
+                stream: Some(stream.name.clone()),
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the frame size of an output stream.
+    fn output_frame_size(&self, index: usize) -> Result<usize> {
+        self.outputs
+            .get(index)
+            .map(|info| info.frame_size)
+            .ok_or(HailorsError::StreamIndexOutOfRange {
+                direction: StreamDirection::Output,
+                index,
+                count: self.outputs.len(),
+            })
+    }
+}
+
+impl InferenceBackend for MockBackend {
+    fn inputs(&self) -> &[VStreamInfo] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[VStreamInfo] {
+        &self.outputs
+    }
+
+    fn write_input(&self, index: usize, frame: &[u8]) -> Result<()> {
+        if index >= self.inputs.len() {
+            return Err(HailorsError::StreamIndexOutOfRange {
+                direction: StreamDirection::Input,
+                index,
+                count: self.inputs.len(),
+            });
+        }
+        self.check_aborted(Operation::WriteInput, &self.inputs[index])?;
+        if !self.skip_recording {
+            self.state.lock().unwrap().written_inputs[index].push(frame.to_vec());
+        }
+        Ok(())
+    }
+
+    fn flush_input(&self, index: usize) -> Result<()> {
+        if index >= self.inputs.len() {
+            return Err(HailorsError::StreamIndexOutOfRange {
+                direction: StreamDirection::Input,
+                index,
+                count: self.inputs.len(),
+            });
+        }
+        self.check_aborted(Operation::FlushInput, &self.inputs[index])?;
+        self.state.lock().unwrap().flushes[index] += 1;
+        Ok(())
+    }
+
+    fn read_output(&self, index: usize, buffer: &mut [u8]) -> Result<()> {
+        let frame_size = self.output_frame_size(index)?;
+        if buffer.len() != frame_size {
+            return Err(HailorsError::FrameSizeMismatch {
+                stream: self.outputs[index].name.clone(),
+                expected: frame_size,
+                actual: buffer.len(),
+            });
+        }
+        if let Some(latency) = self.latency {
+            std::thread::sleep(latency);
+        }
+        self.check_aborted(Operation::ReadOutput, &self.outputs[index])?;
+        match self.state.lock().unwrap().scripted_outputs[index].pop_front() {
+            Some(frame) => buffer.copy_from_slice(&frame),
+            None => buffer.fill(0),
+        }
+        Ok(())
+    }
+
+    /// Times out when the simulated latency exceeds `timeout`, discarding the frames scripted for
+    /// the stream like the abort and resume of a real stream does.
+    fn read_output_timeout(&self, index: usize, buffer: &mut [u8], timeout: Duration) -> Result<()> {
+        match self.latency {
+            Some(latency) if latency > timeout && index < self.outputs.len() => {
+                std::thread::sleep(timeout);
+                self.state.lock().unwrap().scripted_outputs[index].clear();
+                Err(HailorsError::Timeout {
+                    operation: Operation::ReadOutput,
+                    stream: Some(self.outputs[index].name.clone()),
+                })
+            }
+            _ => self.read_output(index, buffer),
+        }
+    }
+
+    fn abort(&self) -> Result<()> {
+        self.state.lock().unwrap().aborted = true;
+        Ok(())
+    }
+
+    fn resume(&self) -> Result<()> {
+        self.state.lock().unwrap().aborted = false;
+        Ok(())
+    }
+}
@@ -1,3 +1,9 @@
+use std::sync::Arc;
+
+use crate::labels::Labels;
+use crate::tensor::Tensor;
+use crate::vstream::VStreamInfo;
+
 /// A trait representing a neural network type.
 ///
 /// This trait defines the expected output type for a network and a method to parse raw output data
@@ -16,6 +22,33 @@ pub trait Network {
     ///
     /// A vector of parsed outputs of type `Self::Output`.
     fn parse_output(&self, output_data: &[f32]) -> Vec<Self::Output>;
+
+    /// Parses the raw output data, appending the results to `results`.
+    ///
+    /// Reusing `results` across frames avoids allocating a vector per frame. The default
+    /// implementation appends the results of `parse_output`.
+    ///
+    /// # Arguments
+    ///
+    /// * `output_data` - A slice of `f32` values representing raw output data from the network.
+    /// * `results` - The vector the parsed outputs are appended to.
+    fn parse_output_into(&self, output_data: &[f32], results: &mut Vec<Self::Output>) {
+        results.extend(self.parse_output(output_data));
+    }
+
+    /// Parses an output tensor into a structured output type.
+    ///
+    /// `HailoDevice::read_output` passes the frame it read as a tensor. Networks whose outputs
+    /// have a height, width and feature layout can override this to read elements with
+    /// `Tensor::get`, which handles the format order and quantization of the stream. The default
+    /// implementation dequantizes the tensor and calls `parse_output`.
+    ///
+    /// # Arguments
+    ///
+    /// * `tensor` - The output frame with the metadata of its stream.
+    fn parse_tensor(&self, tensor: &Tensor<'_>) -> Vec<Self::Output> {
+        self.parse_output(&tensor.to_f32())
+    }
 }
 
 /// Enum representing supported network types for the CLI.
@@ -35,6 +68,36 @@ pub struct YoloDetection {
     pub max_bboxes_per_class: usize,
     /// Confidence threshold for detections.
     pub threshold: f32,
+    /// Class names attached to the detections, if any.
+    pub labels: Option<Labels>,
+}
+
+impl YoloDetection {
+    /// Configures YOLO Detection from the metadata of an NMS output stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `info` - Stream information of the model's NMS output stream.
+    /// * `threshold` - Confidence threshold for detections.
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` if the stream carries no NMS parameters.
+    pub fn from_vstream_info(info: &VStreamInfo, threshold: f32) -> Option<Self> {
+        let nms = info.nms?;
+        Some(Self {
+            num_classes: nms.number_of_classes as usize,
+            max_bboxes_per_class: nms.max_bboxes_per_class as usize,
+            threshold,
+            labels: None,
+        })
+    }
+
+    /// Attaches class names to the detections, e.g. `Labels::coco()`.
+    pub fn with_labels(mut self, labels: Labels) -> Self {
+        self.labels = Some(labels);
+        self
+    }
 }
 
 /// Represents a detection result for the YOLO Detection network.
@@ -45,6 +108,24 @@ pub struct Detection {
     pub confidence: f32,
     /// Bounding box coordinates: (x_min, y_min, x_max, y_max).
     pub bbox: (f32, f32, f32, f32),
+    /// Class name, if the network has labels for the class.
+    pub label: Option<Arc<str>>,
+}
+
+impl std::fmt::Display for Detection {
+    /// Formats the detection as e.g. `dog 0.91 (0.10, 0.20, 0.60, 0.90)`, using the class ID when
+    /// there is no label.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.label {
+            Some(label) => write!(f, "{}", label)?,
+            None => write!(f, "class {}", self.class_id)?,
+        }
+        write!(
+            f,
+            " {:.2} ({:.2}, {:.2}, {:.2}, {:.2})",
+            self.confidence, self.bbox.0, self.bbox.1, self.bbox.2, self.bbox.3
+        )
+    }
 }
 
 /// Implementation of the `Network` trait for `YoloDetection`.
@@ -55,6 +136,11 @@ impl Network for YoloDetection {
 
     fn parse_output(&self, output_data: &[f32]) -> Vec<Self::Output> {
         let mut detections = Vec::new();
+        self.parse_output_into(output_data, &mut detections);
+        detections
+    }
+
+    fn parse_output_into(&self, output_data: &[f32], detections: &mut Vec<Self::Output>) {
         let mut offset = 0;
 
         // Iterate through each class to parse its detections.
@@ -68,9 +154,11 @@ impl Network for YoloDetection {
 
             // Validate and truncate bbox_count if it exceeds the maximum allowed.
             if bbox_count > self.max_bboxes_per_class {
-                eprintln!(
-                    "Warning: Class {} has bbox_count {} exceeding max_bboxes_per_class {}. Truncating.",
-                    class_id, bbox_count, self.max_bboxes_per_class
+                tracing::warn!(
+                    class_id,
+                    bbox_count,
+                    max_bboxes_per_class = self.max_bboxes_per_class,
+                    "Bounding box count exceeds max_bboxes_per_class; truncating"
                 );
             }
             let valid_bbox_count = bbox_count.min(self.max_bboxes_per_class);
@@ -79,10 +167,7 @@ impl Network for YoloDetection {
             for _ in 0..valid_bbox_count {
                 // Ensure there is sufficient data for a complete bounding box.
                 if offset + 5 > output_data.len() {
-                    eprintln!(
-                        "Warning: Truncated data for class {}. Expected complete bbox, but data is insufficient.",
-                        class_id
-                    );
+                    tracing::warn!(class_id, "Output ends in the middle of a bounding box; ignoring the rest");
                     break;
                 }
 
@@ -98,14 +183,13 @@ impl Network for YoloDetection {
                         class_id: class_id as u32,
                         confidence,
                         bbox: (x1, y1, x2, y2),
+                        label: self.labels.as_ref().and_then(|labels| labels.get(class_id as u32)),
                     });
                 }
 
                 offset += 5; // Each bounding box consumes 5 values.
             }
         }
-
-        detections
     }
 }
 
@@ -162,4 +246,29 @@ impl Network for YoloPose {
 
         poses
     }
+
+    /// Parses a tensor holding one pose candidate per height and width position, with the
+    /// keypoints and confidence along the features. Other tensors are parsed as a flat list by
+    /// `parse_output`.
+    fn parse_tensor(&self, tensor: &Tensor<'_>) -> Vec<Self::Output> {
+        let confidence_feature = self.num_keypoints * 2;
+        if tensor.strides.is_none() || tensor.shape.features as usize != confidence_feature + 1 {
+            return self.parse_output(&tensor.to_f32());
+        }
+
+        let mut poses = Vec::new();
+        for h in 0..tensor.shape.height as usize {
+            for w in 0..tensor.shape.width as usize {
+                let value = |c| tensor.get(h, w, c).unwrap_or_default();
+                let confidence = value(confidence_feature);
+                if confidence >= self.threshold {
+                    poses.push(Pose {
+                        keypoints: (0..self.num_keypoints).map(|i| (value(i * 2), value(i * 2 + 1))).collect(),
+                        confidence,
+                    });
+                }
+            }
+        }
+        poses
+    }
 }
@@ -0,0 +1,367 @@
+//! Streaming inference with writing and reading on separate threads.
+//!
+//! A `Pipeline` moves a `HailoDevice` onto a writer thread and a reader thread. Frames are queued
+//! to the writer through a bounded queue, which blocks the caller once `queue_depth` frames are
+//! waiting, and the reader reads the outputs of every written frame as soon as they are ready.
+//! Because the writer never waits for outputs, the accelerator always has the next frame queued.
+//!
+//! Outputs are returned in the order the frames were sent. If a write or read fails, the error is
+//! returned in place of that frame's outputs and the pipeline stops: later sends fail with
+//! `HailorsError::WorkerStopped` and no further outputs are returned.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+use crate::backend::AbortHandle;
+use crate::error::{HailorsError, Result};
+use crate::vstream::{StreamDirection, VStreamInfo};
+use crate::HailoDevice;
+
+/// A request queued to the writer thread.
+enum Request {
+    /// One frame per input stream.
+    Frames(Vec<Vec<u8>>),
+    /// Flush the input streams so that a partial batch is processed.
+    Flush,
+}
+
+/// A streaming inference pipeline with a bounded input queue and in-order outputs.
+///
+/// Use `send` and `recv` from one thread, or `split` the pipeline to feed frames from one thread
+/// while consuming outputs on another.
+///
+/// # Example
+///
+/// ```rust
+/// use hailors::{HailoDevice, MockBackend, VStreamShape};
+///
+/// let mock = MockBackend::new()
+///     .with_input("model/input_layer1", VStreamShape::new(2, 2, 3))
+///     .with_output("model/output_layer1", VStreamShape::new(1, 1, 4));
+/// let mut pipeline = HailoDevice::with_backend(mock).into_pipeline(4);
+///
+/// for _ in 0..3 {
+///     pipeline.send(vec![vec![0u8; 12]]).unwrap();
+/// }
+/// let outputs = pipeline.flush().unwrap();
+/// assert_eq!(outputs.len(), 3);
+///
+/// let device = pipeline.shutdown();
+/// ```
+pub struct Pipeline {
+    writer: PipelineWriter,
+    reader: PipelineReader,
+    /// Number of frames sent whose outputs have not been received yet.
+    pending: usize,
+}
+
+impl Pipeline {
+    /// Moves a device onto a new writer and reader thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The device to run inference on.
+    /// * `queue_depth` - Number of frames that may wait for the writer before `send` blocks. A
+    ///   depth of 0 is treated as 1.
+    pub fn new(device: HailoDevice, queue_depth: usize) -> Self {
+        let queue_depth = queue_depth.max(1);
+        let inputs = device.inputs().to_vec();
+        let outputs = device.outputs().to_vec();
+        let abort = device.abort_handle();
+        let device = Arc::new(device);
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        let (requests, request_queue) = mpsc::sync_channel::<Request>(queue_depth);
+        let (written, to_read) = mpsc::channel::<Result<()>>();
+        // Outputs are never held back, so a caller that sends several frames before receiving
+        // cannot deadlock the reader
+        let (results, result_queue) = mpsc::channel();
+
+        let writer = std::thread::Builder::new()
+            .name("hailors-writer".to_string())
+            .spawn({
+                let device = Arc::clone(&device);
+                let stopped = Arc::clone(&stopped);
+                move || write_frames(&device, request_queue, written, &stopped)
+            })
+            .expect("Failed to spawn the pipeline writer thread");
+        let reader = std::thread::Builder::new()
+            .name("hailors-reader".to_string())
+            .spawn({
+                let device = Arc::clone(&device);
+                let stopped = Arc::clone(&stopped);
+                move || read_outputs(&device, to_read, results, &stopped)
+            })
+            .expect("Failed to spawn the pipeline reader thread");
+
+        Self {
+            writer: PipelineWriter { requests, inputs },
+            reader: PipelineReader {
+                results: result_queue,
+                device: Some(device),
+                stopped,
+                threads: vec![writer, reader],
+                outputs,
+                abort,
+            },
+            pending: 0,
+        }
+    }
+
+    /// Returns the input virtual streams of the configured model.
+    pub fn inputs(&self) -> &[VStreamInfo] {
+        self.writer.inputs()
+    }
+
+    /// Returns the output virtual streams of the configured model.
+    pub fn outputs(&self) -> &[VStreamInfo] {
+        self.reader.outputs()
+    }
+
+    /// Returns a handle that aborts the device streams, see `PipelineReader::abort_handle`.
+    pub fn abort_handle(&self) -> AbortHandle {
+        self.reader.abort_handle()
+    }
+
+    /// Queues one frame per input stream, blocking while the queue is full.
+    ///
+    /// See `PipelineWriter::send`.
+    pub fn send(&mut self, frames: Vec<Vec<u8>>) -> Result<()> {
+        self.writer.send(frames)?;
+        self.pending += 1;
+        Ok(())
+    }
+
+    /// Waits for the outputs of the oldest frame whose outputs have not been received yet.
+    ///
+    /// # Returns
+    ///
+    /// Returns one raw frame per output stream, or `None` if every frame sent so far has been
+    /// received or the pipeline stopped.
+    pub fn recv(&mut self) -> Option<Result<Vec<Vec<u8>>>> {
+        if self.pending == 0 {
+            return None;
+        }
+        let result = self.reader.recv();
+        self.pending = if result.is_some() { self.pending - 1 } else { 0 };
+        result
+    }
+
+    /// Flushes the input streams and waits for the outputs of every frame sent so far.
+    ///
+    /// A partial batch is processed without waiting for more frames. The pipeline stays open.
+    ///
+    /// # Returns
+    ///
+    /// Returns the outputs of the frames whose outputs had not been received yet, in order.
+    pub fn flush(&mut self) -> Result<Vec<Vec<Vec<u8>>>> {
+        self.writer.flush()?;
+        let mut outputs = Vec::with_capacity(self.pending);
+        while let Some(result) = self.recv() {
+            outputs.push(result?);
+        }
+        Ok(outputs)
+    }
+
+    /// Splits the pipeline into a writer and a reader that can be used on different threads.
+    pub fn split(self) -> (PipelineWriter, PipelineReader) {
+        (self.writer, self.reader)
+    }
+
+    /// Closes the pipeline, waits for the frames still queued and returns the device.
+    ///
+    /// Outputs that have not been received are discarded.
+    pub fn shutdown(self) -> HailoDevice {
+        drop(self.writer);
+        self.reader.shutdown()
+    }
+}
+
+/// The sending half of a `Pipeline`.
+///
+/// Dropping the writer closes the pipeline: frames already queued are still written and their
+/// outputs are still returned by the reader.
+pub struct PipelineWriter {
+    requests: SyncSender<Request>,
+    inputs: Vec<VStreamInfo>,
+}
+
+impl PipelineWriter {
+    /// Returns the input virtual streams of the configured model.
+    pub fn inputs(&self) -> &[VStreamInfo] {
+        &self.inputs
+    }
+
+    /// Queues one frame per input stream, blocking while the queue is full.
+    ///
+    /// # Arguments
+    ///
+    /// * `frames` - One frame per input stream, in the order of `inputs()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number or sizes of the frames do not match the input streams,
+    /// before anything is queued, or `HailorsError::WorkerStopped` if the pipeline stopped.
+    pub fn send(&self, frames: Vec<Vec<u8>>) -> Result<()> {
+        if frames.len() != self.inputs.len() {
+            return Err(HailorsError::StreamCountMismatch {
+                direction: StreamDirection::Input,
+                expected: self.inputs.len(),
+                actual: frames.len(),
+            });
+        }
+        if let Some((info, frame)) = self
+            .inputs
+            .iter()
+            .zip(&frames)
+            .find(|(info, frame)| frame.len() != info.frame_size)
+        {
+            return Err(HailorsError::FrameSizeMismatch {
+                stream: info.name.clone(),
+                expected: info.frame_size,
+                actual: frame.len(),
+            });
+        }
+        self.requests
+            .send(Request::Frames(frames))
+            .map_err(|_| HailorsError::WorkerStopped)
+    }
+
+    /// Queues a flush of the input streams, so that a partial batch is processed once the frames
+    /// queued before it are written.
+    pub fn flush(&self) -> Result<()> {
+        self.requests.send(Request::Flush).map_err(|_| HailorsError::WorkerStopped)
+    }
+}
+
+/// The receiving half of a `Pipeline`, which yields outputs in the order the frames were sent.
+///
+/// Dropping the reader stops the pipeline without waiting for the threads; use `shutdown` to
+/// wait for them and get the device back.
+pub struct PipelineReader {
+    results: Receiver<Result<Vec<Vec<u8>>>>,
+    device: Option<Arc<HailoDevice>>,
+    stopped: Arc<AtomicBool>,
+    threads: Vec<JoinHandle<()>>,
+    outputs: Vec<VStreamInfo>,
+    abort: AbortHandle,
+}
+
+impl PipelineReader {
+    /// Returns the output virtual streams of the configured model.
+    pub fn outputs(&self) -> &[VStreamInfo] {
+        &self.outputs
+    }
+
+    /// Returns a handle that aborts the device streams from any thread.
+    ///
+    /// Aborting a hung pipeline makes the blocked write or read return
+    /// `HailorsError::StreamAborted`, which stops the pipeline. Once it has been shut down,
+    /// call `HailoDevice::resume` on the returned device before using it again.
+    pub fn abort_handle(&self) -> AbortHandle {
+        self.abort.clone()
+    }
+
+    /// Waits for the outputs of the next frame.
+    ///
+    /// # Returns
+    ///
+    /// Returns one raw frame per output stream, or `None` once the writer has been dropped and
+    /// every queued frame has been read, or the pipeline stopped.
+    pub fn recv(&self) -> Option<Result<Vec<Vec<u8>>>> {
+        self.results.recv().ok()
+    }
+
+    /// Waits for the writer to be dropped and every queued frame to be read, then returns the
+    /// device.
+    ///
+    /// Outputs that have not been received are discarded. The writer must be dropped first, or
+    /// this waits forever.
+    pub fn shutdown(mut self) -> HailoDevice {
+        while self.results.recv().is_ok() {}
+        for thread in self.threads.drain(..) {
+            if let Err(panic) = thread.join() {
+                std::panic::resume_unwind(panic);
+            }
+        }
+        let device = self.device.take().expect("The device is only taken on shutdown");
+        Arc::into_inner(device).expect("The pipeline threads have exited")
+    }
+}
+
+impl Iterator for PipelineReader {
+    type Item = Result<Vec<Vec<u8>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.recv()
+    }
+}
+
+impl Drop for PipelineReader {
+    fn drop(&mut self) {
+        // The threads exit on their own once the writer is dropped or they see the flag
+        self.stopped.store(true, Ordering::Relaxed);
+    }
+}
+
+impl HailoDevice {
+    /// Moves the device into a streaming `Pipeline` with the given queue depth.
+    pub fn into_pipeline(self, queue_depth: usize) -> Pipeline {
+        Pipeline::new(self, queue_depth)
+    }
+}
+
+/// Writer thread: writes the queued frames and announces each written frame to the reader.
+fn write_frames(
+    device: &HailoDevice,
+    requests: Receiver<Request>,
+    written: mpsc::Sender<Result<()>>,
+    stopped: &AtomicBool,
+) {
+    for request in requests {
+        if stopped.load(Ordering::Relaxed) {
+            return;
+        }
+        let result = match request {
+            Request::Frames(frames) => {
+                let frames: Vec<&[u8]> = frames.iter().map(Vec::as_slice).collect();
+                device.write_inputs(&frames).map(|()| true)
+            }
+            Request::Flush => device.flush_inputs().map(|()| false),
+        };
+        match result {
+            Ok(true) => {
+                let _ = written.send(Ok(()));
+            }
+            Ok(false) => {}
+            Err(err) => {
+                let _ = written.send(Err(err));
+                return;
+            }
+        }
+    }
+
+    // The pipeline was closed; push out a partial batch so the reader can finish
+    if let Err(err) = device.flush_inputs() {
+        let _ = written.send(Err(err));
+    }
+}
+
+/// Reader thread: reads the outputs of every written frame, in order.
+fn read_outputs(
+    device: &HailoDevice,
+    written: Receiver<Result<()>>,
+    results: mpsc::Sender<Result<Vec<Vec<u8>>>>,
+    stopped: &AtomicBool,
+) {
+    for write_result in written {
+        let result = write_result.and_then(|()| device.read_outputs());
+        let failed = result.is_err();
+        if results.send(result).is_err() || failed {
+            stopped.store(true, Ordering::Relaxed);
+            return;
+        }
+    }
+}
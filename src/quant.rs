@@ -0,0 +1,143 @@
+//! Conversion between quantized stream values and FLOAT32.
+//!
+//! Hailo models compute on UINT8 and UINT16 values. HailoRT can convert them to and from FLOAT32
+//! on the host, but keeping the streams quantized moves a quarter or half of the bytes over PCIe.
+//! The functions in this module do the conversion with each stream's `QuantInfo` instead, so a
+//! model can run with quantized inputs and outputs and still feed FLOAT32 values to the `Network`
+//! parsers. `HailoDevice::read_output` uses them to dequantize quantized outputs.
+//!
+//! A quantized value `q` maps to `(q - zero_point) * scale`. The loops run over plain slices
+//! without branches or bounds checks, so the compiler vectorizes them with the SIMD instructions
+//! of the target.
+//!
+//! # Example
+//!
+//! ```rust
+//! use hailors::quant;
+//! use hailors::QuantInfo;
+//!
+//! let quant_info = QuantInfo { zero_point: 128.0, scale: 0.5, ..QuantInfo::default() };
+//!
+//! let mut values = [0.0f32; 3];
+//! quant::dequantize_u8(&[128, 130, 0], quant_info, &mut values);
+//! assert_eq!(values, [0.0, 1.0, -64.0]);
+//!
+//! let mut quantized = [0u8; 3];
+//! quant::quantize_u8(&values, quant_info, &mut quantized);
+//! assert_eq!(quantized, [128, 130, 0]);
+//! ```
+
+use crate::vstream::{FormatType, QuantInfo};
+
+/// Dequantizes UINT8 values into `output`.
+///
+/// # Panics
+///
+/// Panics if `input` and `output` differ in length.
+pub fn dequantize_u8(input: &[u8], quant: QuantInfo, output: &mut [f32]) {
+    assert_eq!(input.len(), output.len(), "Input and output lengths differ");
+    let (zero_point, scale) = (quant.zero_point, quant.scale);
+    for (value, &quantized) in output.iter_mut().zip(input) {
+        *value = (f32::from(quantized) - zero_point) * scale;
+    }
+}
+
+/// Dequantizes UINT16 values into `output`.
+///
+/// # Panics
+///
+/// Panics if `input` and `output` differ in length.
+pub fn dequantize_u16(input: &[u16], quant: QuantInfo, output: &mut [f32]) {
+    assert_eq!(input.len(), output.len(), "Input and output lengths differ");
+    let (zero_point, scale) = (quant.zero_point, quant.scale);
+    for (value, &quantized) in output.iter_mut().zip(input) {
+        *value = (f32::from(quantized) - zero_point) * scale;
+    }
+}
+
+/// Quantizes FLOAT32 values into UINT8 `output`, rounding to the nearest value and saturating
+/// at 0 and 255.
+///
+/// # Panics
+///
+/// Panics if `input` and `output` differ in length.
+pub fn quantize_u8(input: &[f32], quant: QuantInfo, output: &mut [u8]) {
+    assert_eq!(input.len(), output.len(), "Input and output lengths differ");
+    let (zero_point, inverse_scale) = (quant.zero_point, 1.0 / quant.scale);
+    for (quantized, &value) in output.iter_mut().zip(input) {
+        // Adding 0.5 before truncating rounds the clamped, non-negative value without `round`,
+        // which does not vectorize
+        *quantized = ((value * inverse_scale + zero_point).clamp(0.0, u8::MAX as f32) + 0.5) as u8;
+    }
+}
+
+/// Quantizes FLOAT32 values into UINT16 `output`, rounding to the nearest value and saturating
+/// at 0 and 65535.
+///
+/// # Panics
+///
+/// Panics if `input` and `output` differ in length.
+pub fn quantize_u16(input: &[f32], quant: QuantInfo, output: &mut [u16]) {
+    assert_eq!(input.len(), output.len(), "Input and output lengths differ");
+    let (zero_point, inverse_scale) = (quant.zero_point, 1.0 / quant.scale);
+    for (quantized, &value) in output.iter_mut().zip(input) {
+        *quantized = ((value * inverse_scale + zero_point).clamp(0.0, u16::MAX as f32) + 0.5) as u16;
+    }
+}
+
+/// Converts a raw frame of the given type into FLOAT32 values, replacing the contents of
+/// `values`.
+///
+/// UINT8 and UINT16 elements are dequantized with the given quantization parameters; FLOAT32
+/// elements are copied as is. `FormatType::Auto` is read as `FormatType::host_type` says.
+/// Multi-byte elements are in native byte order, as HailoRT writes them.
+pub fn dequantize(format_type: FormatType, quant: QuantInfo, frame: &[u8], values: &mut Vec<f32>) {
+    values.clear();
+    match format_type.host_type() {
+        FormatType::Auto | FormatType::Uint8 => {
+            values.resize(frame.len(), 0.0);
+            dequantize_u8(frame, quant, values);
+        }
+        FormatType::Uint16 => {
+            let (zero_point, scale) = (quant.zero_point, quant.scale);
+            values.extend(
+                frame
+                    .chunks_exact(2)
+                    .map(|chunk| (f32::from(u16::from_ne_bytes([chunk[0], chunk[1]])) - zero_point) * scale),
+            );
+        }
+        FormatType::Float32 => values.extend(
+            frame
+                .chunks_exact(4)
+                .map(|chunk| f32::from_ne_bytes([chunk[0], chunk[1], chunk[2], chunk[3]])),
+        ),
+    }
+}
+
+/// Converts FLOAT32 values into a raw frame of the given type, replacing the contents of `frame`.
+///
+/// The counterpart of `dequantize`, for writing FLOAT32 data to a quantized input stream:
+///
+/// ```rust
+/// use hailors::{quant, FormatType, QuantInfo};
+///
+/// let quant_info = QuantInfo { zero_point: 0.0, scale: 1.0 / 255.0, ..QuantInfo::default() };
+/// let mut frame = Vec::new();
+/// quant::quantize(FormatType::Uint8, quant_info, &[0.0, 0.2, 1.0], &mut frame);
+/// assert_eq!(frame, vec![0, 51, 255]);
+/// ```
+pub fn quantize(format_type: FormatType, quant: QuantInfo, values: &[f32], frame: &mut Vec<u8>) {
+    frame.clear();
+    match format_type.host_type() {
+        FormatType::Auto | FormatType::Uint8 => {
+            frame.resize(values.len(), 0);
+            quantize_u8(values, quant, frame);
+        }
+        FormatType::Uint16 => {
+            let mut quantized = vec![0u16; values.len()];
+            quantize_u16(values, quant, &mut quantized);
+            frame.extend(quantized.iter().flat_map(|value| value.to_ne_bytes()));
+        }
+        FormatType::Float32 => frame.extend(values.iter().flat_map(|value| value.to_ne_bytes())),
+    }
+}
@@ -0,0 +1,295 @@
+//! Handles for using one device from several threads.
+//!
+//! `HailoDevice` is `Send` and `Sync`: every stream is guarded by its own lock inside the backend,
+//! so a device can be moved to another thread or shared by reference without wrapping it in a
+//! `Mutex`. What the device does not guard is the pairing of frames: outputs come back in the
+//! order the inputs were written, so two threads that each write a frame and then read an output
+//! may receive each other's outputs. The handles in this module keep that pairing intact:
+//!
+//! - `HailoDevice::split` returns an `InputWriter` and an `OutputReader` that can live on
+//!   different threads. With one writer and one reader, outputs stay in the order the frames were
+//!   written without any locking between the two.
+//! - `SharedDevice` is a cloneable device that any number of worker threads can submit frames to.
+//!   Each `infer` call is given a place in line when its frames are written and reads its outputs
+//!   in that same order, while other threads keep writing, so frames from different threads are in
+//!   flight at the same time.
+
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+use crate::backend::AbortHandle;
+use crate::buffer::OutputBuffers;
+use crate::error::Result;
+use crate::network::Network;
+use crate::vstream::VStreamInfo;
+use crate::HailoDevice;
+
+/// The writing half of a split `HailoDevice`.
+///
+/// # Example
+///
+/// ```rust
+/// use hailors::{HailoDevice, MockBackend, VStreamShape};
+///
+/// let mock = MockBackend::new()
+///     .with_input("model/input_layer1", VStreamShape::new(2, 2, 3))
+///     .with_output("model/output_layer1", VStreamShape::new(1, 1, 4));
+/// let (writer, reader) = HailoDevice::with_backend(mock).split();
+///
+/// let feeder = std::thread::spawn(move || {
+///     for _ in 0..3 {
+///         writer.write_inputs(&[&[0u8; 12]]).unwrap();
+///     }
+///     writer
+/// });
+/// for _ in 0..3 {
+///     reader.read_outputs().unwrap();
+/// }
+/// let device = feeder.join().unwrap().reunite(reader).ok().unwrap();
+/// ```
+pub struct InputWriter {
+    device: Arc<HailoDevice>,
+}
+
+impl InputWriter {
+    /// Returns the input virtual streams of the configured model.
+    pub fn inputs(&self) -> &[VStreamInfo] {
+        self.device.inputs()
+    }
+
+    /// Writes a frame to the input virtual stream at `index`. See `HailoDevice::write_input_at`.
+    pub fn write_input_at(&self, index: usize, frame: &[u8]) -> Result<()> {
+        self.device.write_input_at(index, frame)
+    }
+
+    /// Writes a frame to the input stream at `index`, giving up after `timeout`.
+    /// See `HailoDevice::write_input_at_timeout`.
+    pub fn write_input_at_timeout(&self, index: usize, frame: &[u8], timeout: Duration) -> Result<()> {
+        self.device.write_input_at_timeout(index, frame, timeout)
+    }
+
+    /// Writes one frame to every input virtual stream. See `HailoDevice::write_inputs`.
+    pub fn write_inputs(&self, frames: &[&[u8]]) -> Result<()> {
+        self.device.write_inputs(frames)
+    }
+
+    /// Flushes the input streams so that a partial batch is processed.
+    pub fn flush_inputs(&self) -> Result<()> {
+        self.device.flush_inputs()
+    }
+
+    /// Returns a handle that aborts the streams of the device from any thread.
+    pub fn abort_handle(&self) -> AbortHandle {
+        self.device.abort_handle()
+    }
+
+    /// Joins the writer with the reader it was split from and returns the device.
+    ///
+    /// # Errors
+    ///
+    /// Returns both halves unchanged if they were split from different devices.
+    pub fn reunite(self, reader: OutputReader) -> std::result::Result<HailoDevice, (InputWriter, OutputReader)> {
+        if !Arc::ptr_eq(&self.device, &reader.device) {
+            return Err((self, reader));
+        }
+        drop(reader);
+        Ok(Arc::into_inner(self.device).expect("The writer and reader are the only owners of the device"))
+    }
+}
+
+/// The reading half of a split `HailoDevice`.
+pub struct OutputReader {
+    device: Arc<HailoDevice>,
+}
+
+impl OutputReader {
+    /// Returns the output virtual streams of the configured model.
+    pub fn outputs(&self) -> &[VStreamInfo] {
+        self.device.outputs()
+    }
+
+    /// Allocates caller-owned buffers for one frame of every output stream.
+    pub fn output_buffers(&self) -> OutputBuffers {
+        self.device.output_buffers()
+    }
+
+    /// Reads one raw frame from the output virtual stream at `index`.
+    pub fn read_output_frame(&self, index: usize) -> Result<Vec<u8>> {
+        self.device.read_output_frame(index)
+    }
+
+    /// Reads one raw frame from the output stream at `index`, giving up after `timeout`.
+    /// See `HailoDevice::read_output_frame_timeout`.
+    pub fn read_output_frame_timeout(&self, index: usize, timeout: Duration) -> Result<Vec<u8>> {
+        self.device.read_output_frame_timeout(index, timeout)
+    }
+
+    /// Reads one raw frame from every output virtual stream.
+    pub fn read_outputs(&self) -> Result<Vec<Vec<u8>>> {
+        self.device.read_outputs()
+    }
+
+    /// Reads one frame of every output stream into caller-owned buffers.
+    pub fn read_outputs_into(&self, buffers: &mut OutputBuffers) -> Result<()> {
+        self.device.read_outputs_into(buffers)
+    }
+
+    /// Reads and parses the first output stream. See `HailoDevice::read_output`.
+    pub fn read_output<T: Network>(&self, network_type: &T) -> Result<Vec<T::Output>> {
+        self.device.read_output(network_type)
+    }
+
+    /// Returns a handle that aborts the streams of the device from any thread.
+    pub fn abort_handle(&self) -> AbortHandle {
+        self.device.abort_handle()
+    }
+}
+
+/// A device that worker threads share to submit frames without a global lock.
+///
+/// Clones refer to the same device. Each `infer` call writes its frames while holding only the
+/// submission lock, then waits for its turn to read, so one thread's outputs are never returned
+/// to another while the next frames are already being written.
+///
+/// With a batch size above one, a frame is only processed once its batch is full, so run at
+/// least as many worker threads as the batch size, or call `flush_inputs` after the last frame.
+///
+/// # Example
+///
+/// ```rust
+/// use hailors::{HailoDevice, MockBackend, SharedDevice, VStreamShape};
+///
+/// let mock = MockBackend::new()
+///     .with_input("model/input_layer1", VStreamShape::new(2, 2, 3))
+///     .with_output("model/output_layer1", VStreamShape::new(1, 1, 4));
+/// let device = SharedDevice::new(HailoDevice::with_backend(mock));
+///
+/// let workers: Vec<_> = (0..4)
+///     .map(|_| {
+///         let device = device.clone();
+///         std::thread::spawn(move || device.infer(&[&[0u8; 12]]).unwrap())
+///     })
+///     .collect();
+/// for worker in workers {
+///     assert_eq!(worker.join().unwrap()[0].len(), 16);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct SharedDevice {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    device: HailoDevice,
+    /// Number of submissions written so far; the next submission's place in line.
+    submitted: Mutex<u64>,
+    /// Place in line of the submission whose outputs are read next.
+    turn: Mutex<u64>,
+    /// Signalled whenever `turn` advances.
+    turn_changed: Condvar,
+}
+
+impl SharedDevice {
+    /// Wraps a device for sharing between threads.
+    pub fn new(device: HailoDevice) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                device,
+                submitted: Mutex::new(0),
+                turn: Mutex::new(0),
+                turn_changed: Condvar::new(),
+            }),
+        }
+    }
+
+    /// Returns the input virtual streams of the configured model.
+    pub fn inputs(&self) -> &[VStreamInfo] {
+        self.shared.device.inputs()
+    }
+
+    /// Returns the output virtual streams of the configured model.
+    pub fn outputs(&self) -> &[VStreamInfo] {
+        self.shared.device.outputs()
+    }
+
+    /// Writes one frame per input stream and waits for the outputs of those frames.
+    ///
+    /// # Arguments
+    ///
+    /// * `frames` - One frame per input stream, in the order of `inputs()`.
+    ///
+    /// # Returns
+    ///
+    /// Returns one raw frame per output stream, in the order of `outputs()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the frames do not match the input streams, or if writing or reading
+    /// fails. After a failed write or read the streams may be out of step, as with
+    /// `HailoDevice::infer`; abort and resume the streams before submitting more frames.
+    pub fn infer(&self, frames: &[&[u8]]) -> Result<Vec<Vec<u8>>> {
+        let ticket = {
+            let mut submitted = self.shared.submitted.lock().unwrap_or_else(|err| err.into_inner());
+            self.shared.device.write_inputs(frames)?;
+            *submitted += 1;
+            *submitted - 1
+        };
+
+        let turn = self.shared.turn.lock().unwrap_or_else(|err| err.into_inner());
+        let turn = self
+            .shared
+            .turn_changed
+            .wait_while(turn, |turn| *turn != ticket)
+            .unwrap_or_else(|err| err.into_inner());
+        drop(turn);
+
+        // Hand the turn on even if reading fails or panics, so later submissions are not stuck
+        let _next = NextTurn(&self.shared);
+        self.shared.device.read_outputs()
+    }
+
+    /// Flushes the input streams so that a partial batch is processed.
+    pub fn flush_inputs(&self) -> Result<()> {
+        let _submitted = self.shared.submitted.lock().unwrap_or_else(|err| err.into_inner());
+        self.shared.device.flush_inputs()
+    }
+
+    /// Returns a handle that aborts the streams of the device from any thread.
+    pub fn abort_handle(&self) -> AbortHandle {
+        self.shared.device.abort_handle()
+    }
+
+    /// Returns the device if this is the last clone.
+    pub fn into_inner(self) -> Option<HailoDevice> {
+        Arc::into_inner(self.shared).map(|shared| shared.device)
+    }
+}
+
+/// Advances the read turn when dropped.
+struct NextTurn<'a>(&'a Shared);
+
+impl Drop for NextTurn<'_> {
+    fn drop(&mut self) {
+        *self.0.turn.lock().unwrap_or_else(|err| err.into_inner()) += 1;
+        self.0.turn_changed.notify_all();
+    }
+}
+
+impl HailoDevice {
+    /// Splits the device into an input writer and an output reader that can be moved to
+    /// different threads.
+    pub fn split(self) -> (InputWriter, OutputReader) {
+        let device = Arc::new(self);
+        (
+            InputWriter {
+                device: Arc::clone(&device),
+            },
+            OutputReader { device },
+        )
+    }
+
+    /// Wraps the device in a `SharedDevice` that worker threads can submit frames to.
+    pub fn into_shared(self) -> SharedDevice {
+        SharedDevice::new(self)
+    }
+}
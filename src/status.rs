@@ -1,34 +1,105 @@
+/// Status codes returned by HailoRT (`hailo_status`).
+///
+/// The discriminants match the values in `hailort.h`, so a status received over FFI can be
+/// converted with `HailoStatus::from_i32`.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq)] 
-#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HailoStatus {
-    Success = 0,  // No error
-    Uninitialized = 1,  // No error code initialized
-    InvalidArgument = 2,  // Invalid argument passed
-    OutOfHostMemory = 3,  // Cannot allocate more memory on host
-    Timeout = 4,  // Timeout occurred
-    InsufficientBuffer = 5,  // Buffer is insufficient
-    InvalidOperation = 6,  // Invalid operation
-    NotImplemented = 7,  // Functionality not implemented
-    InternalFailure = 8,  // Unexpected internal failure
-    DataAlignmentFailure = 9,  // Data alignment issue
-    ChunkTooLarge = 10,  // Chunk size exceeds limit
-    CloseFailure = 12,  // Failed to close resource
-    OpenFileFailure = 13,  // Failed to open file
-    FileOperationFailure = 14,  // File operation failed
+    Success = 0,                             // No error
+    Uninitialized = 1,                       // No error code initialized
+    InvalidArgument = 2,                     // Invalid argument passed
+    OutOfHostMemory = 3,                     // Cannot allocate more memory on host
+    Timeout = 4,                             // Timeout occurred
+    InsufficientBuffer = 5,                  // Buffer is insufficient
+    InvalidOperation = 6,                    // Invalid operation
+    NotImplemented = 7,                      // Functionality not implemented
+    InternalFailure = 8,                     // Unexpected internal failure
+    DataAlignmentFailure = 9,                // Data alignment issue
+    ChunkTooLarge = 10,                      // Chunk size exceeds limit
+    InvalidLoggerLevel = 11,                 // Logger level not compiled in
+    CloseFailure = 12,                       // Failed to close resource
+    OpenFileFailure = 13,                    // Failed to open file
+    FileOperationFailure = 14,               // File operation failed
     UnsupportedControlProtocolVersion = 15,  // Unsupported protocol version
-    UnsupportedFirmwareVersion = 16,  // Unsupported firmware version
-    InvalidControlResponse = 17,  // Invalid control response
-    FirmwareControlFailure = 18,  // Firmware control failed
-    NotFound = 61,  // Element not found
-    CommunicationClosed = 62,  // Communication closed
-    StreamAbort = 63,  // Stream aborted
-    DriverNotInstalled = 64,  // Driver not installed or running
-    NotAvailable = 65,  // Component not available
-    Unsupported = 79,  // Operation not supported
+    UnsupportedFirmwareVersion = 16,         // Unsupported firmware version
+    InvalidControlResponse = 17,             // Invalid control response
+    FirmwareControlFailure = 18,             // Firmware control failed
+    EthFailure = 19,                         // Ethernet operation failed
+    EthInterfaceNotFound = 20,               // Ethernet interface not found
+    EthRecvFailure = 21,                     // Ethernet receive failed
+    EthSendFailure = 22,                     // Ethernet send failed
+    InvalidFirmware = 23,                    // Firmware binary is invalid
+    InvalidContextCount = 24,                // Too many contexts
+    InvalidFrame = 25,                       // Result data is partially or fully invalid
+    InvalidHef = 26,                         // Invalid HEF file
+    PcieNotSupportedOnPlatform = 27,         // PCIe not supported on platform
+    InterruptedBySignal = 28,                // Blocking call interrupted by a signal
+    StartVdmaChannelFail = 29,               // Failed to start VDMA channel
+    InvalidKernelBuffer = 30,                // Kernel buffer is invalid
+    UserModeRateLimiterNotSupported = 33,    // User mode rate limiter not supported
+    RateLimitMaximumBandwidthError = 34,     // Rate limit exceeds maximum bandwidth
+    AnsiToUtf16ConversionFailed = 35,        // ANSI to UTF-16 conversion failed
+    Utf16ToAnsiConversionFailed = 36,        // UTF-16 to ANSI conversion failed
+    UnexpectedInterfaceInfoFailure = 37,     // Failed to retrieve interface info
+    UnexpectedArpTableFailure = 38,          // Failed to retrieve ARP table
+    MacAddressNotFound = 39,                 // MAC address not found in ARP table
+    NoIpv4InterfacesFound = 40,              // No interface with an IPv4 address
+    ShutdownEventSignaled = 41,              // Shutdown event signaled
+    ThreadAlreadyActivated = 42,             // Thread already activated
+    ThreadNotActivated = 43,                 // Thread not activated
+    ThreadNotJoinable = 44,                  // Thread not joinable
+    AtrTablesConfValidationFail = 45,        // ATR tables configuration validation failed
+    ControlEventCreateFail = 46,             // Failed to create control event
+    ReadEventFail = 47,                      // Failed to read event
+    DriverFail = 48,                         // Driver operation failed
+    InvalidFirmwareMagic = 49,               // Invalid firmware magic
+    InvalidFirmwareCodeSize = 50,            // Invalid firmware code size
+    InvalidKeyCertificateSize = 51,          // Invalid key certificate size
+    InvalidContentCertificateSize = 52,      // Invalid content certificate size
+    MismatchingFirmwareBufferSizes = 53,     // Mismatching firmware buffer sizes
+    InvalidFirmwareCpuId = 54,               // Invalid firmware CPU ID
+    ControlResponseMd5Mismatch = 55,         // Control response MD5 mismatch
+    GetControlResponseFail = 56,             // Failed to get control response
+    GetD2hEventMessageFail = 57,             // Failed to get D2H event message
+    MutexInitFail = 58,                      // Failed to initialize mutex
+    OutOfDescriptors = 59,                   // Not enough descriptors
+    UnsupportedOpcode = 60,                  // Unsupported opcode sent to device
+    NotFound = 61,                           // Element not found
+    CommunicationClosed = 62,                // Communication closed
+    StreamAbort = 63,                        // Stream aborted
+    DriverNotInstalled = 64,                 // Driver not installed or running
+    NotAvailable = 65,                       // Component not available
+    TrafficControlFailure = 66,              // Traffic control failed
+    InvalidSecondStage = 67,                 // Second stage binary is invalid
+    InvalidPipeline = 68,                    // Pipeline is invalid
+    NetworkGroupNotActivated = 69,           // Network group not activated
+    VStreamPipelineNotActivated = 70,        // VStream pipeline not activated
+    OutOfFirmwareMemory = 71,                // Not enough memory on firmware
+    StreamNotActivated = 72,                 // Stream not activated
+    DeviceInUse = 73,                        // Device already in use
+    OutOfPhysicalDevices = 74,               // Not enough physical devices
+    InvalidDeviceArchitecture = 75,          // Invalid device architecture
+    InvalidDriverVersion = 76,               // Driver version not supported
+    RpcFailed = 77,                          // RPC call failed
+    InvalidServiceVersion = 78,              // Invalid service version
+    Unsupported = 79,                        // Operation not supported
+    NmsBurstInvalidData = 80,                // Invalid data in NMS burst
+    OutOfHostCmaMemory = 81,                 // Cannot allocate more CMA memory on host
+    QueueIsFull = 82,                        // Queue is full
+    DmaMappingAlreadyExists = 83,            // DMA mapping already exists
+    CantMeetBufferRequirements = 84,         // Buffer requirements cannot be met
+    DriverInvalidResponse = 85,              // Driver returned an invalid response
+    DriverInvalidIoctl = 86,                 // Driver cannot handle ioctl
+    DriverTimeout = 87,                      // Driver operation timed out
+    DriverInterrupted = 88,                  // Driver operation interrupted
+    ConnectionRefused = 89,                  // Connection refused
+    DriverWaitCanceled = 90,                 // Driver operation canceled
 }
-#[allow(dead_code)]
+
 impl HailoStatus {
+    /// Converts a raw `hailo_status` value into a `HailoStatus`.
+    ///
+    /// Unknown values map to `HailoStatus::InternalFailure`.
     pub fn from_i32(value: i32) -> Self {
         match value {
             0 => HailoStatus::Success,
@@ -42,6 +113,7 @@ impl HailoStatus {
             8 => HailoStatus::InternalFailure,
             9 => HailoStatus::DataAlignmentFailure,
             10 => HailoStatus::ChunkTooLarge,
+            11 => HailoStatus::InvalidLoggerLevel,
             12 => HailoStatus::CloseFailure,
             13 => HailoStatus::OpenFileFailure,
             14 => HailoStatus::FileOperationFailure,
@@ -49,15 +121,84 @@ impl HailoStatus {
             16 => HailoStatus::UnsupportedFirmwareVersion,
             17 => HailoStatus::InvalidControlResponse,
             18 => HailoStatus::FirmwareControlFailure,
+            19 => HailoStatus::EthFailure,
+            20 => HailoStatus::EthInterfaceNotFound,
+            21 => HailoStatus::EthRecvFailure,
+            22 => HailoStatus::EthSendFailure,
+            23 => HailoStatus::InvalidFirmware,
+            24 => HailoStatus::InvalidContextCount,
+            25 => HailoStatus::InvalidFrame,
+            26 => HailoStatus::InvalidHef,
+            27 => HailoStatus::PcieNotSupportedOnPlatform,
+            28 => HailoStatus::InterruptedBySignal,
+            29 => HailoStatus::StartVdmaChannelFail,
+            30 => HailoStatus::InvalidKernelBuffer,
+            33 => HailoStatus::UserModeRateLimiterNotSupported,
+            34 => HailoStatus::RateLimitMaximumBandwidthError,
+            35 => HailoStatus::AnsiToUtf16ConversionFailed,
+            36 => HailoStatus::Utf16ToAnsiConversionFailed,
+            37 => HailoStatus::UnexpectedInterfaceInfoFailure,
+            38 => HailoStatus::UnexpectedArpTableFailure,
+            39 => HailoStatus::MacAddressNotFound,
+            40 => HailoStatus::NoIpv4InterfacesFound,
+            41 => HailoStatus::ShutdownEventSignaled,
+            42 => HailoStatus::ThreadAlreadyActivated,
+            43 => HailoStatus::ThreadNotActivated,
+            44 => HailoStatus::ThreadNotJoinable,
+            45 => HailoStatus::AtrTablesConfValidationFail,
+            46 => HailoStatus::ControlEventCreateFail,
+            47 => HailoStatus::ReadEventFail,
+            48 => HailoStatus::DriverFail,
+            49 => HailoStatus::InvalidFirmwareMagic,
+            50 => HailoStatus::InvalidFirmwareCodeSize,
+            51 => HailoStatus::InvalidKeyCertificateSize,
+            52 => HailoStatus::InvalidContentCertificateSize,
+            53 => HailoStatus::MismatchingFirmwareBufferSizes,
+            54 => HailoStatus::InvalidFirmwareCpuId,
+            55 => HailoStatus::ControlResponseMd5Mismatch,
+            56 => HailoStatus::GetControlResponseFail,
+            57 => HailoStatus::GetD2hEventMessageFail,
+            58 => HailoStatus::MutexInitFail,
+            59 => HailoStatus::OutOfDescriptors,
+            60 => HailoStatus::UnsupportedOpcode,
             61 => HailoStatus::NotFound,
             62 => HailoStatus::CommunicationClosed,
             63 => HailoStatus::StreamAbort,
             64 => HailoStatus::DriverNotInstalled,
             65 => HailoStatus::NotAvailable,
+            66 => HailoStatus::TrafficControlFailure,
+            67 => HailoStatus::InvalidSecondStage,
+            68 => HailoStatus::InvalidPipeline,
+            69 => HailoStatus::NetworkGroupNotActivated,
+            70 => HailoStatus::VStreamPipelineNotActivated,
+            71 => HailoStatus::OutOfFirmwareMemory,
+            72 => HailoStatus::StreamNotActivated,
+            73 => HailoStatus::DeviceInUse,
+            74 => HailoStatus::OutOfPhysicalDevices,
+            75 => HailoStatus::InvalidDeviceArchitecture,
+            76 => HailoStatus::InvalidDriverVersion,
+            77 => HailoStatus::RpcFailed,
+            78 => HailoStatus::InvalidServiceVersion,
             79 => HailoStatus::Unsupported,
+            80 => HailoStatus::NmsBurstInvalidData,
+            81 => HailoStatus::OutOfHostCmaMemory,
+            82 => HailoStatus::QueueIsFull,
+            83 => HailoStatus::DmaMappingAlreadyExists,
+            84 => HailoStatus::CantMeetBufferRequirements,
+            85 => HailoStatus::DriverInvalidResponse,
+            86 => HailoStatus::DriverInvalidIoctl,
+            87 => HailoStatus::DriverTimeout,
+            88 => HailoStatus::DriverInterrupted,
+            89 => HailoStatus::ConnectionRefused,
+            90 => HailoStatus::DriverWaitCanceled,
             _ => HailoStatus::InternalFailure,  // Default case for unknown statuses
         }
     }
+
+    /// Returns `true` if the status is `HailoStatus::Success`.
+    pub fn is_success(&self) -> bool {
+        *self == HailoStatus::Success
+    }
 }
 
 impl std::fmt::Display for HailoStatus {
@@ -74,6 +215,7 @@ impl std::fmt::Display for HailoStatus {
             HailoStatus::InternalFailure => write!(f, "Internal Failure"),
             HailoStatus::DataAlignmentFailure => write!(f, "Data Alignment Failure"),
             HailoStatus::ChunkTooLarge => write!(f, "Chunk Too Large"),
+            HailoStatus::InvalidLoggerLevel => write!(f, "Invalid Logger Level"),
             HailoStatus::CloseFailure => write!(f, "Close Failure"),
             HailoStatus::OpenFileFailure => write!(f, "Open File Failure"),
             HailoStatus::FileOperationFailure => write!(f, "File Operation Failure"),
@@ -81,13 +223,76 @@ impl std::fmt::Display for HailoStatus {
             HailoStatus::UnsupportedFirmwareVersion => write!(f, "Unsupported Firmware Version"),
             HailoStatus::InvalidControlResponse => write!(f, "Invalid Control Response"),
             HailoStatus::FirmwareControlFailure => write!(f, "Firmware Control Failure"),
+            HailoStatus::EthFailure => write!(f, "Ethernet Failure"),
+            HailoStatus::EthInterfaceNotFound => write!(f, "Ethernet Interface Not Found"),
+            HailoStatus::EthRecvFailure => write!(f, "Ethernet Receive Failure"),
+            HailoStatus::EthSendFailure => write!(f, "Ethernet Send Failure"),
+            HailoStatus::InvalidFirmware => write!(f, "Invalid Firmware"),
+            HailoStatus::InvalidContextCount => write!(f, "Invalid Context Count"),
+            HailoStatus::InvalidFrame => write!(f, "Invalid Frame"),
+            HailoStatus::InvalidHef => write!(f, "Invalid HEF"),
+            HailoStatus::PcieNotSupportedOnPlatform => write!(f, "PCIe Not Supported on Platform"),
+            HailoStatus::InterruptedBySignal => write!(f, "Interrupted by Signal"),
+            HailoStatus::StartVdmaChannelFail => write!(f, "Start VDMA Channel Failure"),
+            HailoStatus::InvalidKernelBuffer => write!(f, "Invalid Kernel Buffer"),
+            HailoStatus::UserModeRateLimiterNotSupported => write!(f, "User Mode Rate Limiter Not Supported"),
+            HailoStatus::RateLimitMaximumBandwidthError => write!(f, "Rate Limit Maximum Bandwidth Error"),
+            HailoStatus::AnsiToUtf16ConversionFailed => write!(f, "ANSI to UTF-16 Conversion Failed"),
+            HailoStatus::Utf16ToAnsiConversionFailed => write!(f, "UTF-16 to ANSI Conversion Failed"),
+            HailoStatus::UnexpectedInterfaceInfoFailure => write!(f, "Unexpected Interface Info Failure"),
+            HailoStatus::UnexpectedArpTableFailure => write!(f, "Unexpected ARP Table Failure"),
+            HailoStatus::MacAddressNotFound => write!(f, "MAC Address Not Found"),
+            HailoStatus::NoIpv4InterfacesFound => write!(f, "No IPv4 Interfaces Found"),
+            HailoStatus::ShutdownEventSignaled => write!(f, "Shutdown Event Signaled"),
+            HailoStatus::ThreadAlreadyActivated => write!(f, "Thread Already Activated"),
+            HailoStatus::ThreadNotActivated => write!(f, "Thread Not Activated"),
+            HailoStatus::ThreadNotJoinable => write!(f, "Thread Not Joinable"),
+            HailoStatus::AtrTablesConfValidationFail => write!(f, "ATR Tables Configuration Validation Failure"),
+            HailoStatus::ControlEventCreateFail => write!(f, "Control Event Create Failure"),
+            HailoStatus::ReadEventFail => write!(f, "Read Event Failure"),
+            HailoStatus::DriverFail => write!(f, "Driver Failure"),
+            HailoStatus::InvalidFirmwareMagic => write!(f, "Invalid Firmware Magic"),
+            HailoStatus::InvalidFirmwareCodeSize => write!(f, "Invalid Firmware Code Size"),
+            HailoStatus::InvalidKeyCertificateSize => write!(f, "Invalid Key Certificate Size"),
+            HailoStatus::InvalidContentCertificateSize => write!(f, "Invalid Content Certificate Size"),
+            HailoStatus::MismatchingFirmwareBufferSizes => write!(f, "Mismatching Firmware Buffer Sizes"),
+            HailoStatus::InvalidFirmwareCpuId => write!(f, "Invalid Firmware CPU ID"),
+            HailoStatus::ControlResponseMd5Mismatch => write!(f, "Control Response MD5 Mismatch"),
+            HailoStatus::GetControlResponseFail => write!(f, "Get Control Response Failure"),
+            HailoStatus::GetD2hEventMessageFail => write!(f, "Get D2H Event Message Failure"),
+            HailoStatus::MutexInitFail => write!(f, "Mutex Init Failure"),
+            HailoStatus::OutOfDescriptors => write!(f, "Out of Descriptors"),
+            HailoStatus::UnsupportedOpcode => write!(f, "Unsupported Opcode"),
             HailoStatus::NotFound => write!(f, "Not Found"),
             HailoStatus::CommunicationClosed => write!(f, "Communication Closed"),
             HailoStatus::StreamAbort => write!(f, "Stream Abort"),
             HailoStatus::DriverNotInstalled => write!(f, "Driver Not Installed"),
             HailoStatus::NotAvailable => write!(f, "Not Available"),
+            HailoStatus::TrafficControlFailure => write!(f, "Traffic Control Failure"),
+            HailoStatus::InvalidSecondStage => write!(f, "Invalid Second Stage"),
+            HailoStatus::InvalidPipeline => write!(f, "Invalid Pipeline"),
+            HailoStatus::NetworkGroupNotActivated => write!(f, "Network Group Not Activated"),
+            HailoStatus::VStreamPipelineNotActivated => write!(f, "VStream Pipeline Not Activated"),
+            HailoStatus::OutOfFirmwareMemory => write!(f, "Out of Firmware Memory"),
+            HailoStatus::StreamNotActivated => write!(f, "Stream Not Activated"),
+            HailoStatus::DeviceInUse => write!(f, "Device In Use"),
+            HailoStatus::OutOfPhysicalDevices => write!(f, "Out of Physical Devices"),
+            HailoStatus::InvalidDeviceArchitecture => write!(f, "Invalid Device Architecture"),
+            HailoStatus::InvalidDriverVersion => write!(f, "Invalid Driver Version"),
+            HailoStatus::RpcFailed => write!(f, "RPC Failed"),
+            HailoStatus::InvalidServiceVersion => write!(f, "Invalid Service Version"),
             HailoStatus::Unsupported => write!(f, "Unsupported"),
+            HailoStatus::NmsBurstInvalidData => write!(f, "NMS Burst Invalid Data"),
+            HailoStatus::OutOfHostCmaMemory => write!(f, "Out of Host CMA Memory"),
+            HailoStatus::QueueIsFull => write!(f, "Queue Is Full"),
+            HailoStatus::DmaMappingAlreadyExists => write!(f, "DMA Mapping Already Exists"),
+            HailoStatus::CantMeetBufferRequirements => write!(f, "Cannot Meet Buffer Requirements"),
+            HailoStatus::DriverInvalidResponse => write!(f, "Driver Invalid Response"),
+            HailoStatus::DriverInvalidIoctl => write!(f, "Driver Invalid Ioctl"),
+            HailoStatus::DriverTimeout => write!(f, "Driver Timeout"),
+            HailoStatus::DriverInterrupted => write!(f, "Driver Interrupted"),
+            HailoStatus::ConnectionRefused => write!(f, "Connection Refused"),
+            HailoStatus::DriverWaitCanceled => write!(f, "Driver Wait Canceled"),
         }
     }
 }
-
@@ -0,0 +1,341 @@
+use std::borrow::Cow;
+use std::path::Path;
+
+use crate::error::{HailorsError, Result};
+use crate::quant;
+use crate::vstream::{FormatOrder, FormatType, NmsShape, QuantInfo, VStreamInfo, VStreamShape};
+
+/// A frame of a stream together with the metadata needed to interpret it.
+///
+/// A tensor keeps the bytes exactly as HailoRT reads or writes them, borrowed or owned, and
+/// describes them with the stream's shape, data type, format order, strides and quantization
+/// parameters. Elements are addressed by height, width and feature, whatever the order of the
+/// frame, so parsers and preprocessing do not need to compute offsets by hand.
+///
+/// # Example
+///
+/// ```rust
+/// use hailors::{FormatOrder, Tensor, VStreamShape};
+///
+/// // Two pixels stored as planes of three features
+/// let tensor = Tensor::from_values(VStreamShape::new(1, 2, 3), &[1u8, 2, 3, 4, 5, 6]).with_order(FormatOrder::Nchw);
+/// assert_eq!(tensor.get(0, 1, 0), Some(2.0));
+/// assert_eq!(tensor.get(0, 0, 2), Some(5.0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tensor<'a> {
+    /// Name of the stream the tensor belongs to, if any.
+    pub name: String,
+    /// Data type of the elements.
+    pub dtype: FormatType,
+    /// Frame shape. For NMS streams this is the flattened frame.
+    pub shape: VStreamShape,
+    /// Memory layout of the elements.
+    pub order: FormatOrder,
+    /// Distance in elements between neighbours along the height, width and features, or `None`
+    /// for orders without such a layout, such as NMS and YUV frames.
+    pub strides: Option<[usize; 3]>,
+    /// Quantization parameters for UINT8 and UINT16 elements.
+    pub quant: QuantInfo,
+    /// NMS parameters, present only for NMS streams.
+    pub nms: Option<NmsShape>,
+    /// The frame, in native byte order.
+    pub data: Cow<'a, [u8]>,
+}
+
+/// An output frame owned by its tensor, as returned by `HailoDevice::read_raw_output`.
+pub type RawTensor = Tensor<'static>;
+
+/// Element types a tensor can be built from.
+pub trait Element: Copy {
+    /// Data type of the element.
+    const FORMAT_TYPE: FormatType;
+
+    /// Appends the element to `bytes` in native byte order.
+    fn extend_ne_bytes(self, bytes: &mut Vec<u8>);
+}
+
+impl Element for u8 {
+    const FORMAT_TYPE: FormatType = FormatType::Uint8;
+
+    fn extend_ne_bytes(self, bytes: &mut Vec<u8>) {
+        bytes.push(self);
+    }
+}
+
+impl Element for u16 {
+    const FORMAT_TYPE: FormatType = FormatType::Uint16;
+
+    fn extend_ne_bytes(self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_ne_bytes());
+    }
+}
+
+impl Element for f32 {
+    const FORMAT_TYPE: FormatType = FormatType::Float32;
+
+    fn extend_ne_bytes(self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_ne_bytes());
+    }
+}
+
+/// Returns the strides of a frame of the given shape and order, or `None` if the order does not
+/// store elements by height, width and feature.
+///
+/// `FormatOrder::Auto` is read as NHWC, the order HailoRT uses on the host by default.
+fn layout_strides(shape: VStreamShape, order: FormatOrder) -> Option<[usize; 3]> {
+    let (height, width, features) = (shape.height as usize, shape.width as usize, shape.features as usize);
+    match order {
+        FormatOrder::Auto | FormatOrder::Nhwc | FormatOrder::Nhw | FormatOrder::Nc | FormatOrder::Rgb888 => {
+            Some([width * features, features, 1])
+        }
+        FormatOrder::Nhcw => Some([features * width, 1, width]),
+        FormatOrder::Nchw => Some([width, 1, height * width]),
+        _ => None,
+    }
+}
+
+impl<'a> Tensor<'a> {
+    /// Creates a tensor from a frame of the stream described by `info`.
+    ///
+    /// The frame may be borrowed, e.g. an image to write, or owned, e.g. a frame read from the
+    /// device.
+    pub fn new(info: &VStreamInfo, data: impl Into<Cow<'a, [u8]>>) -> Self {
+        Self {
+            name: info.name.clone(),
+            dtype: info.format.format_type,
+            shape: info.shape,
+            order: info.format.order,
+            strides: layout_strides(info.shape, info.format.order),
+            quant: info.quant,
+            nms: info.nms,
+            data: data.into(),
+        }
+    }
+
+    /// Creates an unnamed NHWC tensor that owns a copy of `values`.
+    ///
+    /// The tensor has identity quantization; set it with `with_quant` for quantized values.
+    pub fn from_values<T: Element>(shape: VStreamShape, values: &[T]) -> Tensor<'static> {
+        let mut data = Vec::with_capacity(std::mem::size_of_val(values));
+        for &value in values {
+            value.extend_ne_bytes(&mut data);
+        }
+        Tensor {
+            name: String::new(),
+            dtype: T::FORMAT_TYPE,
+            shape,
+            order: FormatOrder::Nhwc,
+            strides: layout_strides(shape, FormatOrder::Nhwc),
+            quant: QuantInfo::default(),
+            nms: None,
+            data: Cow::Owned(data),
+        }
+    }
+
+    /// Sets the format order of the frame, e.g. `FormatOrder::Nchw` for planar images, and
+    /// recomputes the strides.
+    pub fn with_order(mut self, order: FormatOrder) -> Self {
+        self.order = order;
+        self.strides = layout_strides(self.shape, order);
+        self
+    }
+
+    /// Sets the quantization parameters of UINT8 and UINT16 elements.
+    pub fn with_quant(mut self, quant: QuantInfo) -> Self {
+        self.quant = quant;
+        self
+    }
+
+    /// Returns a tensor that owns its frame, copying it if it is borrowed.
+    pub fn into_owned(self) -> Tensor<'static> {
+        Tensor {
+            name: self.name,
+            dtype: self.dtype,
+            shape: self.shape,
+            order: self.order,
+            strides: self.strides,
+            quant: self.quant,
+            nms: self.nms,
+            data: Cow::Owned(self.data.into_owned()),
+        }
+    }
+
+    /// Returns the raw bytes of the frame.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the number of elements in the frame.
+    pub fn len(&self) -> usize {
+        self.data.len() / self.dtype.element_size()
+    }
+
+    /// Returns `true` if the frame holds no elements.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the index of the element at the given height, width and feature, or `None` if
+    /// the position is outside the shape or the order has no strides.
+    pub fn offset(&self, h: usize, w: usize, c: usize) -> Option<usize> {
+        let [h_stride, w_stride, c_stride] = self.strides?;
+        let shape = self.shape;
+        if h >= shape.height as usize || w >= shape.width as usize || c >= shape.features as usize {
+            return None;
+        }
+        Some(h * h_stride + w * w_stride + c * c_stride)
+    }
+
+    /// Returns the element at the given height, width and feature as a FLOAT32 value,
+    /// dequantizing UINT8 and UINT16 elements.
+    ///
+    /// Returns `None` if the position is outside the shape or the frame, or the order has no
+    /// strides.
+    pub fn get(&self, h: usize, w: usize, c: usize) -> Option<f32> {
+        let size = self.dtype.element_size();
+        let start = self.offset(h, w, c)? * size;
+        let bytes = self.data.get(start..start + size)?;
+        let (zero_point, scale) = (self.quant.zero_point, self.quant.scale);
+        Some(match self.dtype.host_type() {
+            FormatType::Auto | FormatType::Uint8 => (f32::from(bytes[0]) - zero_point) * scale,
+            FormatType::Uint16 => (f32::from(u16::from_ne_bytes([bytes[0], bytes[1]])) - zero_point) * scale,
+            FormatType::Float32 => f32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        })
+    }
+
+    /// Returns the elements as FLOAT32 values in memory order, dequantizing UINT8 and UINT16
+    /// elements.
+    pub fn to_f32(&self) -> Vec<f32> {
+        let mut values = Vec::with_capacity(self.len());
+        quant::dequantize(self.dtype, self.quant, &self.data, &mut values);
+        values
+    }
+
+    /// Returns the tensor as a frame of the stream described by `info`.
+    ///
+    /// The frame is borrowed if the tensor already has the stream's data type, order and
+    /// quantization. Otherwise the elements are reordered and converted, going through FLOAT32,
+    /// so an NCHW FLOAT32 image can be written to an NHWC UINT8 stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HailorsError::ShapeMismatch` if the shapes differ, `HailorsError::FrameSizeMismatch`
+    /// if the frame does not hold the whole shape, or `HailorsError::UnsupportedLayout` if a
+    /// conversion is needed but either order has no strides.
+    pub fn to_frame(&self, info: &VStreamInfo) -> Result<Cow<'_, [u8]>> {
+        if self.shape != info.shape {
+            return Err(HailorsError::ShapeMismatch {
+                stream: info.name.clone(),
+                expected: info.shape,
+                actual: self.shape,
+            });
+        }
+
+        let target_strides = layout_strides(info.shape, info.format.order);
+        let dtype = self.dtype.host_type();
+        let same_type = dtype == info.format.format_type.host_type()
+            && (dtype == FormatType::Float32
+                || (self.quant.zero_point == info.quant.zero_point && self.quant.scale == info.quant.scale));
+        let same_order = self.order == info.format.order || (self.strides.is_some() && self.strides == target_strides);
+        if same_type && same_order {
+            return Ok(Cow::Borrowed(&self.data));
+        }
+
+        let (Some(_), Some([h_stride, w_stride, c_stride])) = (self.strides, target_strides) else {
+            let order = if self.strides.is_none() { self.order } else { info.format.order };
+            return Err(HailorsError::UnsupportedLayout(order));
+        };
+        let count = self.shape.element_count();
+        if self.data.len() != count * self.dtype.element_size() {
+            return Err(HailorsError::FrameSizeMismatch {
+                stream: info.name.clone(),
+                expected: count * self.dtype.element_size(),
+                actual: self.data.len(),
+            });
+        }
+
+        let mut values = vec![0.0; count];
+        for h in 0..self.shape.height as usize {
+            for w in 0..self.shape.width as usize {
+                for c in 0..self.shape.features as usize {
+                    values[h * h_stride + w * w_stride + c * c_stride] = self.get(h, w, c).unwrap_or_default();
+                }
+            }
+        }
+        let mut frame = Vec::with_capacity(info.frame_size);
+        quant::quantize(info.format.format_type, info.quant, &values, &mut frame);
+        Ok(Cow::Owned(frame))
+    }
+
+    /// Writes the raw frame to a file.
+    ///
+    /// The dump can be replayed with `MockBackend::push_output_file`.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        std::fs::write(path, &self.data)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl Tensor<'_> {
+    /// Returns the elements as a FLOAT32 array indexed by `[h, w, c]`, dequantizing UINT8 and
+    /// UINT16 elements.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HailorsError::UnsupportedLayout` if the order has no strides, or
+    /// `HailorsError::FrameSizeMismatch` if the frame does not hold the whole shape.
+    pub fn to_array(&self) -> Result<ndarray::Array3<f32>> {
+        use ndarray::ShapeBuilder;
+
+        let [h_stride, w_stride, c_stride] = self.strides.ok_or(HailorsError::UnsupportedLayout(self.order))?;
+        let shape = (self.shape.height as usize, self.shape.width as usize, self.shape.features as usize);
+        let values = self.to_f32();
+        let actual = values.len();
+        ndarray::Array3::from_shape_vec(shape.strides((h_stride, w_stride, c_stride)), values).map_err(|_| {
+            HailorsError::FrameSizeMismatch {
+                stream: self.name.clone(),
+                expected: self.shape.element_count() * self.dtype.element_size(),
+                actual: actual * self.dtype.element_size(),
+            }
+        })
+    }
+
+    /// Creates an unnamed NHWC tensor from an array indexed by `[h, w, c]`.
+    pub fn from_array<T, S>(array: &ndarray::ArrayBase<S, ndarray::Ix3>) -> Tensor<'static>
+    where
+        T: Element,
+        S: ndarray::Data<Elem = T>,
+    {
+        let (height, width, features) = array.dim();
+        let shape = VStreamShape::new(height as u32, width as u32, features as u32);
+        // `iter` visits the elements in logical order, which is NHWC whatever the memory layout
+        let values: Vec<T> = array.iter().copied().collect();
+        Tensor::from_values(shape, &values)
+    }
+}
+
+impl std::fmt::Display for Tensor<'_> {
+    /// Summarizes the tensor, e.g. `net/scores: UINT8 NHWC 1x1x80 (zero point 0, scale 0.5), 80 bytes`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.name.is_empty() {
+            write!(f, "{}: ", self.name)?;
+        }
+        write!(
+            f,
+            "{} {} {}x{}x{}",
+            self.dtype, self.order, self.shape.height, self.shape.width, self.shape.features
+        )?;
+        if let Some(nms) = self.nms {
+            write!(
+                f,
+                " (NMS {} classes, {} boxes per class)",
+                nms.number_of_classes, nms.max_bboxes_per_class
+            )?;
+        } else if matches!(self.dtype, FormatType::Uint8 | FormatType::Uint16) {
+            write!(f, " (zero point {}, scale {})", self.quant.zero_point, self.quant.scale)?;
+        }
+        write!(f, ", {} bytes", self.data.len())
+    }
+}
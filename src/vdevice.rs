@@ -0,0 +1,185 @@
+use std::ffi::{c_char, c_void, CString};
+use std::ptr;
+use std::sync::Arc;
+
+use crate::config::DeviceConfig;
+use crate::error::{check_status, HailorsError, Operation, Result};
+use crate::ffi::{self, VDeviceHandle};
+use crate::hailort::HailoRtBackend;
+use crate::logging;
+use crate::HailoDevice;
+
+/// How a virtual device shares its time between configured network groups
+/// (`hailo_scheduling_algorithm_t`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SchedulingAlgorithm {
+    /// No model scheduler; network groups are switched with `HailoDevice::activate`.
+    None,
+    /// The model scheduler switches between network groups in turn as frames arrive.
+    #[default]
+    RoundRobin,
+}
+
+impl SchedulingAlgorithm {
+    /// Returns the raw `hailo_scheduling_algorithm_t` value of this algorithm.
+    pub fn as_u32(&self) -> u32 {
+        match self {
+            SchedulingAlgorithm::None => 0,
+            SchedulingAlgorithm::RoundRobin => 1,
+        }
+    }
+}
+
+/// Parameters for creating a virtual device.
+///
+/// The defaults use any one available device with the model scheduler enabled.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VDeviceParams {
+    /// IDs of the physical devices to use, as listed by `hailors::devices()`. Empty selects any
+    /// available devices.
+    pub device_ids: Vec<String>,
+    /// Number of physical devices to use when no device IDs are given; `None` selects the
+    /// HailoRT default of one device.
+    pub device_count: Option<u32>,
+    /// How the device shares its time between network groups.
+    pub scheduling_algorithm: SchedulingAlgorithm,
+    /// Group ID under which processes share the virtual device; `None` selects the HailoRT
+    /// default group.
+    pub group_id: Option<String>,
+}
+
+impl VDeviceParams {
+    /// Creates the default parameters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pins the virtual device to the physical devices with the given IDs.
+    pub fn with_device_ids<S: AsRef<str>>(mut self, device_ids: &[S]) -> Self {
+        self.device_ids = device_ids.iter().map(|id| id.as_ref().to_string()).collect();
+        self
+    }
+
+    /// Sets the number of physical devices to use when no device IDs are given.
+    pub fn with_device_count(mut self, device_count: u32) -> Self {
+        self.device_count = Some(device_count);
+        self
+    }
+
+    /// Sets the scheduling algorithm.
+    pub fn with_scheduling_algorithm(mut self, scheduling_algorithm: SchedulingAlgorithm) -> Self {
+        self.scheduling_algorithm = scheduling_algorithm;
+        self
+    }
+
+    /// Sets the group ID under which processes share the virtual device.
+    pub fn with_group_id(mut self, group_id: &str) -> Self {
+        self.group_id = Some(group_id.to_string());
+        self
+    }
+}
+
+/// A Hailo virtual device that one or more network groups can be configured on.
+///
+/// `HailoDevice::new` creates a virtual device for a single network group. To run several
+/// network groups side by side, such as a detector and a classifier compiled into one HEF,
+/// configure each of them on the same `VDevice`; HailoRT's model scheduler then switches between
+/// them as frames arrive. The virtual device is released when the last handle and the last
+/// device configured on it are dropped.
+///
+/// # Example
+///
+/// ```no_run
+/// use hailors::{DeviceConfig, VDevice};
+///
+/// let vdevice = VDevice::new().expect("Failed to create VDevice");
+/// let detector = vdevice
+///     .configure("./hef/pipeline.hef", &DeviceConfig::new().with_network_group("yolov8s"))
+///     .expect("Failed to configure detector");
+/// let classifier = vdevice
+///     .configure("./hef/pipeline.hef", &DeviceConfig::new().with_network_group("resnet_v1_18"))
+///     .expect("Failed to configure classifier");
+/// ```
+#[derive(Clone)]
+pub struct VDevice {
+    handle: Arc<VDeviceHandle>,
+}
+
+impl VDevice {
+    /// Creates a virtual device with the default parameters, which enable the model scheduler.
+    pub fn new() -> Result<Self> {
+        logging::install();
+        let _span = tracing::debug_span!("create_vdevice").entered();
+        let mut device_handle: *mut c_void = ptr::null_mut();
+        let status = unsafe { ffi::hailors_create_vdevice(&mut device_handle) };
+        let handle = VDeviceHandle(device_handle);
+        check_status(status, Operation::CreateVDevice)?;
+        Ok(Self {
+            handle: Arc::new(handle),
+        })
+    }
+
+    /// Creates a virtual device with the given parameters.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hailors::{VDevice, VDeviceParams};
+    ///
+    /// // Pin the virtual device to the second Hailo-8 module
+    /// let params = VDeviceParams::new().with_device_ids(&["0000:02:00.0"]);
+    /// let vdevice = VDevice::with_params(&params).expect("Failed to create VDevice");
+    /// ```
+    pub fn with_params(params: &VDeviceParams) -> Result<Self> {
+        let device_ids = params
+            .device_ids
+            .iter()
+            .map(|id| {
+                CString::new(id.as_str())
+                    .map_err(|_| HailorsError::InvalidArgument(format!("Device ID contains a nul byte: {}", id)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let device_id_ptrs: Vec<*const c_char> = device_ids.iter().map(|id| id.as_ptr()).collect();
+        let group_id = params
+            .group_id
+            .as_deref()
+            .map(|group_id| {
+                CString::new(group_id)
+                    .map_err(|_| HailorsError::InvalidArgument(format!("Group ID contains a nul byte: {}", group_id)))
+            })
+            .transpose()?;
+
+        let raw_params = ffi::RawVDeviceParams {
+            device_ids: if device_id_ptrs.is_empty() { ptr::null() } else { device_id_ptrs.as_ptr() },
+            device_id_count: device_id_ptrs.len(),
+            device_count: params.device_count.unwrap_or(0),
+            scheduling_algorithm: params.scheduling_algorithm.as_u32(),
+            group_id: group_id.as_ref().map_or(ptr::null(), |group_id| group_id.as_ptr()),
+        };
+
+        logging::install();
+        let _span = tracing::debug_span!("create_vdevice", device_ids = ?params.device_ids).entered();
+        let mut device_handle: *mut c_void = ptr::null_mut();
+        let status = unsafe { ffi::hailors_create_vdevice_with_params(&raw_params, &mut device_handle) };
+        let handle = VDeviceHandle(device_handle);
+        check_status(status, Operation::CreateVDevice)?;
+        Ok(Self {
+            handle: Arc::new(handle),
+        })
+    }
+
+    /// Configures a network group of a HEF on this virtual device.
+    ///
+    /// # Arguments
+    ///
+    /// * `hef_path` - Path to the Hailo Execution File (HEF).
+    /// * `config` - Network group selection and vstream parameters.
+    pub fn configure(&self, hef_path: &str, config: &DeviceConfig) -> Result<HailoDevice> {
+        Ok(HailoDevice::with_backend(HailoRtBackend::configure(self, hef_path, config)?))
+    }
+
+    /// Returns the shared handle of the virtual device.
+    pub(crate) fn handle(&self) -> &Arc<VDeviceHandle> {
+        &self.handle
+    }
+}
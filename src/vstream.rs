@@ -0,0 +1,328 @@
+use serde::Serialize;
+
+/// Shape of a single virtual stream frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct VStreamShape {
+    /// Frame height.
+    pub height: u32,
+    /// Frame width.
+    pub width: u32,
+    /// Number of features (channels) per pixel.
+    pub features: u32,
+}
+
+impl VStreamShape {
+    /// Creates a new shape from its height, width and features.
+    pub fn new(height: u32, width: u32, features: u32) -> Self {
+        Self { height, width, features }
+    }
+
+    /// Returns the number of elements in one frame of this shape.
+    pub fn element_count(&self) -> usize {
+        self.height as usize * self.width as usize * self.features as usize
+    }
+}
+
+/// Data type of the elements in a virtual stream buffer (`hailo_format_type_t`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatType {
+    /// Chosen automatically by HailoRT.
+    Auto,
+    /// Unsigned 8-bit integers.
+    Uint8,
+    /// Unsigned 16-bit integers.
+    Uint16,
+    /// 32-bit floats.
+    Float32,
+}
+
+impl FormatType {
+    /// Converts a raw `hailo_format_type_t` value into a `FormatType`.
+    ///
+    /// Unknown values map to `FormatType::Auto`.
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            1 => FormatType::Uint8,
+            2 => FormatType::Uint16,
+            3 => FormatType::Float32,
+            _ => FormatType::Auto,
+        }
+    }
+
+    /// Returns the raw `hailo_format_type_t` value of this format type.
+    pub fn as_u32(&self) -> u32 {
+        match self {
+            FormatType::Auto => 0,
+            FormatType::Uint8 => 1,
+            FormatType::Uint16 => 2,
+            FormatType::Float32 => 3,
+        }
+    }
+
+    /// Returns the type elements of this format are stored as on the host.
+    ///
+    /// `FormatType::Auto` is read as UINT8, the type HailoRT chooses for quantized streams. Frame
+    /// sizes, tensors and conversions all follow this reading, so they agree on Auto streams.
+    pub fn host_type(&self) -> FormatType {
+        match self {
+            FormatType::Auto => FormatType::Uint8,
+            format_type => *format_type,
+        }
+    }
+
+    /// Returns the size of one element in bytes, reading `FormatType::Auto` as in `host_type`.
+    pub fn element_size(&self) -> usize {
+        match self.host_type() {
+            FormatType::Auto | FormatType::Uint8 => 1,
+            FormatType::Uint16 => 2,
+            FormatType::Float32 => 4,
+        }
+    }
+}
+
+impl std::fmt::Display for FormatType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FormatType::Auto => write!(f, "AUTO"),
+            FormatType::Uint8 => write!(f, "UINT8"),
+            FormatType::Uint16 => write!(f, "UINT16"),
+            FormatType::Float32 => write!(f, "FLOAT32"),
+        }
+    }
+}
+
+/// Memory layout of a virtual stream buffer (`hailo_format_order_t`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatOrder {
+    /// Chosen automatically by HailoRT.
+    Auto,
+    /// Height, width, features.
+    Nhwc,
+    /// Height, features, width.
+    Nhcw,
+    /// Features, columns, rows (Hailo internal).
+    Fcr,
+    /// Features in groups of 8, columns, rows (Hailo internal).
+    F8cr,
+    /// Height, width (single feature).
+    Nhw,
+    /// Features only.
+    Nc,
+    /// Bayer RGB.
+    BayerRgb,
+    /// 12-bit Bayer RGB.
+    Bayer12BitRgb,
+    /// Hailo NMS output: per class, a box count followed by the boxes.
+    HailoNms,
+    /// Packed RGB888.
+    Rgb888,
+    /// Features, height, width.
+    Nchw,
+    /// YUY2.
+    Yuy2,
+    /// NV12.
+    Nv12,
+    /// NV21.
+    Nv21,
+    /// Hailo YYUV.
+    HailoYyuv,
+    /// Hailo YYVU.
+    HailoYyvu,
+    /// RGB with a padding byte.
+    Rgb4,
+    /// I420.
+    I420,
+    /// Hailo YYYYUV.
+    HailoYyyyuv,
+    /// Hailo NMS output with byte masks (instance segmentation).
+    HailoNmsWithByteMask,
+    /// An order this version of hailors does not know about.
+    Unknown(u32),
+}
+
+impl FormatOrder {
+    /// Converts a raw `hailo_format_order_t` value into a `FormatOrder`.
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            0 => FormatOrder::Auto,
+            1 => FormatOrder::Nhwc,
+            2 => FormatOrder::Nhcw,
+            3 => FormatOrder::Fcr,
+            4 => FormatOrder::F8cr,
+            5 => FormatOrder::Nhw,
+            6 => FormatOrder::Nc,
+            7 => FormatOrder::BayerRgb,
+            8 => FormatOrder::Bayer12BitRgb,
+            9 => FormatOrder::HailoNms,
+            10 => FormatOrder::Rgb888,
+            11 => FormatOrder::Nchw,
+            12 => FormatOrder::Yuy2,
+            13 => FormatOrder::Nv12,
+            14 => FormatOrder::Nv21,
+            15 => FormatOrder::HailoYyuv,
+            16 => FormatOrder::HailoYyvu,
+            17 => FormatOrder::Rgb4,
+            18 => FormatOrder::I420,
+            19 => FormatOrder::HailoYyyyuv,
+            20 => FormatOrder::HailoNmsWithByteMask,
+            other => FormatOrder::Unknown(other),
+        }
+    }
+
+    /// Returns `true` for the NMS output orders.
+    pub fn is_nms(&self) -> bool {
+        matches!(self, FormatOrder::HailoNms | FormatOrder::HailoNmsWithByteMask)
+    }
+}
+
+impl std::fmt::Display for FormatOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FormatOrder::Auto => write!(f, "AUTO"),
+            FormatOrder::Nhwc => write!(f, "NHWC"),
+            FormatOrder::Nhcw => write!(f, "NHCW"),
+            FormatOrder::Fcr => write!(f, "FCR"),
+            FormatOrder::F8cr => write!(f, "F8CR"),
+            FormatOrder::Nhw => write!(f, "NHW"),
+            FormatOrder::Nc => write!(f, "NC"),
+            FormatOrder::BayerRgb => write!(f, "BAYER_RGB"),
+            FormatOrder::Bayer12BitRgb => write!(f, "12_BIT_BAYER_RGB"),
+            FormatOrder::HailoNms => write!(f, "HAILO_NMS"),
+            FormatOrder::Rgb888 => write!(f, "RGB888"),
+            FormatOrder::Nchw => write!(f, "NCHW"),
+            FormatOrder::Yuy2 => write!(f, "YUY2"),
+            FormatOrder::Nv12 => write!(f, "NV12"),
+            FormatOrder::Nv21 => write!(f, "NV21"),
+            FormatOrder::HailoYyuv => write!(f, "HAILO_YYUV"),
+            FormatOrder::HailoYyvu => write!(f, "HAILO_YYVU"),
+            FormatOrder::Rgb4 => write!(f, "RGB4"),
+            FormatOrder::I420 => write!(f, "I420"),
+            FormatOrder::HailoYyyyuv => write!(f, "HAILO_YYYYUV"),
+            FormatOrder::HailoNmsWithByteMask => write!(f, "HAILO_NMS_WITH_BYTE_MASK"),
+            FormatOrder::Unknown(value) => write!(f, "UNKNOWN({})", value),
+        }
+    }
+}
+
+/// Host-side buffer format of a virtual stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VStreamFormat {
+    /// Element data type.
+    pub format_type: FormatType,
+    /// Memory layout.
+    pub order: FormatOrder,
+}
+
+/// Quantization parameters of a virtual stream (`hailo_quant_info_t`).
+///
+/// A quantized value `q` maps to `(q - zero_point) * scale`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct QuantInfo {
+    /// Zero point.
+    pub zero_point: f32,
+    /// Scale.
+    pub scale: f32,
+    /// Minimum representable dequantized value.
+    pub limvals_min: f32,
+    /// Maximum representable dequantized value.
+    pub limvals_max: f32,
+}
+
+impl Default for QuantInfo {
+    /// Identity quantization: values are passed through unchanged.
+    fn default() -> Self {
+        Self {
+            zero_point: 0.0,
+            scale: 1.0,
+            limvals_min: f32::MIN,
+            limvals_max: f32::MAX,
+        }
+    }
+}
+
+/// NMS parameters of an NMS output stream (`hailo_nms_shape_t`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NmsShape {
+    /// Number of detection classes.
+    pub number_of_classes: u32,
+    /// Maximum number of bounding boxes per class.
+    pub max_bboxes_per_class: u32,
+}
+
+impl NmsShape {
+    /// Returns the size of one FLOAT32 NMS frame in bytes.
+    ///
+    /// Each class holds a box count followed by up to `max_bboxes_per_class` boxes of
+    /// five values (four coordinates and a score).
+    pub fn float32_frame_size(&self) -> usize {
+        self.number_of_classes as usize * (1 + self.max_bboxes_per_class as usize * 5) * 4
+    }
+}
+
+/// Describes one input or output virtual stream of a configured model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VStreamInfo {
+    /// Name of the virtual stream as defined in the HEF.
+    pub name: String,
+    /// Size of one frame in bytes.
+    pub frame_size: usize,
+    /// Frame shape. For NMS streams this is the flattened frame.
+    pub shape: VStreamShape,
+    /// Host-side buffer format.
+    pub format: VStreamFormat,
+    /// Quantization parameters.
+    pub quant: QuantInfo,
+    /// NMS parameters, present only for NMS output streams.
+    pub nms: Option<NmsShape>,
+}
+
+impl VStreamInfo {
+    /// Creates stream information for a dense stream with the given shape and format.
+    ///
+    /// The frame size is derived from the shape and the element size of the format type;
+    /// `FormatType::Auto` is treated as UINT8.
+    pub fn new(name: &str, shape: VStreamShape, format: VStreamFormat) -> Self {
+        Self {
+            name: name.to_string(),
+            frame_size: shape.element_count() * format.format_type.element_size(),
+            shape,
+            format,
+            quant: QuantInfo::default(),
+            nms: None,
+        }
+    }
+
+    /// Creates stream information for a FLOAT32 NMS output stream.
+    pub fn nms(name: &str, nms: NmsShape) -> Self {
+        let frame_size = nms.float32_frame_size();
+        Self {
+            name: name.to_string(),
+            frame_size,
+            shape: VStreamShape::new(1, 1, (frame_size / 4) as u32),
+            format: VStreamFormat {
+                format_type: FormatType::Float32,
+                order: FormatOrder::HailoNms,
+            },
+            quant: QuantInfo::default(),
+            nms: Some(nms),
+        }
+    }
+}
+
+/// Direction of a virtual stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamDirection {
+    /// Host-to-device stream.
+    Input,
+    /// Device-to-host stream.
+    Output,
+}
+
+impl std::fmt::Display for StreamDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StreamDirection::Input => write!(f, "input"),
+            StreamDirection::Output => write!(f, "output"),
+        }
+    }
+}
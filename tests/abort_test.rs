@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use hailors::{HailoDevice, HailoStatus, HailorsError, MockBackend, Operation, VStreamShape};
+
+    fn mock() -> MockBackend {
+        MockBackend::new()
+            .with_input("net/input", VStreamShape::new(1, 1, 2))
+            .with_output("net/output", VStreamShape::new(1, 1, 1))
+    }
+
+    #[test]
+    fn test_timeout_and_abort_errors() {
+        let timeout = HailorsError::Timeout {
+            operation: Operation::ReadOutput,
+            stream: Some("net/output".to_string()),
+        };
+        assert_eq!(timeout.status(), Some(HailoStatus::Timeout));
+        assert_eq!(timeout.to_string(), "Timed out trying to read output frame, stream: net/output");
+
+        let aborted = HailorsError::StreamAborted {
+            operation: Operation::WriteInput,
+            stream: None,
+        };
+        assert_eq!(aborted.status(), Some(HailoStatus::StreamAbort));
+        assert_eq!(aborted.to_string(), "Failed to write input frame: streams were aborted");
+    }
+
+    #[test]
+    fn test_abort_and_resume() {
+        let mock = mock();
+        mock.push_output_f32(0, &[1.0]).unwrap();
+        let device = HailoDevice::with_backend(mock.clone());
+
+        device.abort_handle().abort().unwrap();
+        assert!(mock.is_aborted());
+        assert!(matches!(
+            device.write_input(&[0, 0]),
+            Err(HailorsError::StreamAborted { operation: Operation::WriteInput, .. })
+        ));
+        assert!(matches!(
+            device.read_output_frame(0),
+            Err(HailorsError::StreamAborted { operation: Operation::ReadOutput, .. })
+        ));
+
+        device.resume().unwrap();
+        device.write_input(&[0, 0]).unwrap();
+        assert_eq!(device.read_output_frame(0).unwrap(), 1.0f32.to_ne_bytes().to_vec());
+    }
+
+    #[test]
+    fn test_read_timeout() {
+        let slow = mock().with_latency(Duration::from_secs(5));
+        slow.push_output_f32(0, &[1.0]).unwrap();
+        let device = HailoDevice::with_backend(slow);
+
+        let start = Instant::now();
+        let err = device.read_output_frame_timeout(0, Duration::from_millis(10)).unwrap_err();
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(matches!(err, HailorsError::Timeout { operation: Operation::ReadOutput, .. }));
+        assert_eq!(err.status(), Some(HailoStatus::Timeout));
+
+        let device = HailoDevice::with_backend(mock().with_latency(Duration::from_millis(1)));
+        assert_eq!(device.read_outputs_timeout(Duration::from_secs(1)).unwrap().len(), 1);
+
+        // A timeout discards the frames queued in the stream
+        let slow = mock().with_latency(Duration::from_millis(50));
+        slow.push_output_f32(0, &[1.0]).unwrap();
+        slow.push_output_f32(0, &[2.0]).unwrap();
+        let device = HailoDevice::with_backend(slow);
+        assert!(device.read_output_frame_timeout(0, Duration::from_millis(10)).is_err());
+        let frame = device.read_output_frame_timeout(0, Duration::from_secs(1)).unwrap();
+        assert_eq!(frame, 0.0f32.to_ne_bytes().to_vec());
+    }
+
+    #[test]
+    fn test_watchdog_recovers_pipeline() {
+        let mock = mock().with_latency(Duration::from_millis(100));
+        let mut pipeline = HailoDevice::with_backend(mock).into_pipeline(2);
+        let handle = pipeline.abort_handle();
+
+        pipeline.send(vec![vec![1, 2]]).unwrap();
+        handle.abort().unwrap();
+        assert!(matches!(pipeline.recv(), Some(Err(HailorsError::StreamAborted { .. }))));
+
+        let device = pipeline.shutdown();
+        device.resume().unwrap();
+        device.write_input(&[1, 2]).unwrap();
+        assert_eq!(device.read_outputs().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,91 @@
+#[cfg(all(test, feature = "async"))]
+mod tests {
+    use std::sync::Arc;
+
+    use futures::StreamExt;
+    use hailors::{network::YoloDetection, HailoDevice, HailorsError, MockBackend, NmsShape, VStreamInfo, VStreamShape};
+
+    fn echo_mock() -> MockBackend {
+        MockBackend::new()
+            .with_input("net/input", VStreamShape::new(1, 1, 4))
+            .with_output("net/output", VStreamShape::new(1, 1, 1))
+    }
+
+    #[tokio::test]
+    async fn test_infer_async() {
+        let mock = echo_mock();
+        mock.push_output_f32(0, &[1.5]).unwrap();
+        let device = HailoDevice::with_backend(mock.clone()).into_async();
+
+        let outputs = device.infer_async(vec![vec![1, 2, 3, 4]]).await.unwrap();
+
+        assert_eq!(outputs, vec![1.5_f32.to_ne_bytes().to_vec()]);
+        assert_eq!(mock.written_inputs(0), vec![vec![1, 2, 3, 4]]);
+    }
+
+    #[tokio::test]
+    async fn test_infer_async_reports_errors() {
+        let device = HailoDevice::with_backend(echo_mock()).into_async();
+
+        let result = device.infer_async(vec![vec![1, 2]]).await;
+
+        assert!(matches!(result, Err(HailorsError::FrameSizeMismatch { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_infer_parsed_async() {
+        let nms = NmsShape {
+            number_of_classes: 2,
+            max_bboxes_per_class: 1,
+        };
+        let mock = MockBackend::new()
+            .with_input("yolo/input", VStreamShape::new(1, 1, 3))
+            .with_output_info(VStreamInfo::nms("yolo/nms", nms));
+        mock.push_output_f32(0, &[0.0, 1.0, 0.1, 0.2, 0.3, 0.4, 0.9]).unwrap();
+        let device = HailoDevice::with_backend(mock).into_async();
+        let network = Arc::new(YoloDetection::from_vstream_info(&device.outputs()[0], 0.5).unwrap());
+
+        let detections = device.infer_parsed_async(vec![0; 3], network).await.unwrap();
+
+        assert_eq!(detections.len(), 1);
+        assert_eq!(detections[0].class_id, 1);
+    }
+
+    #[tokio::test]
+    async fn test_infer_stream_keeps_order() {
+        let mock = echo_mock();
+        for value in 0..5 {
+            mock.push_output_f32(0, &[value as f32]).unwrap();
+        }
+        let device = HailoDevice::with_backend(mock.clone()).into_async();
+
+        let inputs = futures::stream::iter((0..5u8).map(|value| vec![vec![value; 4]]));
+        let results: Vec<_> = device.infer_stream(inputs, 2).collect().await;
+
+        let values: Vec<f32> = results
+            .into_iter()
+            .map(|outputs| f32::from_ne_bytes(outputs.unwrap()[0].clone().try_into().unwrap()))
+            .collect();
+        assert_eq!(values, vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(mock.written_inputs(0).len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_request_still_completes() {
+        let mock = echo_mock();
+        mock.push_output_f32(0, &[1.0]).unwrap();
+        mock.push_output_f32(0, &[2.0]).unwrap();
+        let device = HailoDevice::with_backend(mock.clone()).into_async();
+
+        // The first request is dropped before it is awaited, but it still consumes its output,
+        // so the second request receives its own result.
+        drop(device.infer_async(vec![vec![0; 4]]));
+        let outputs = device.infer_async(vec![vec![1; 4]]).await.unwrap();
+
+        assert_eq!(outputs, vec![2.0_f32.to_ne_bytes().to_vec()]);
+        assert_eq!(mock.written_inputs(0).len(), 2);
+
+        let device = device.into_inner();
+        assert_eq!(device.inputs()[0].name, "net/input");
+    }
+}
@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use hailors::bench::{Bench, BenchConfig, TimingStats};
+    use hailors::{HailoDevice, MockBackend, VStreamShape};
+
+    fn device() -> HailoDevice {
+        let mock = MockBackend::new()
+            .with_input("net/input", VStreamShape::new(1, 1, 4))
+            .with_output("net/output", VStreamShape::new(1, 1, 2))
+            .with_latency(Duration::from_micros(200))
+            .without_input_recording();
+        HailoDevice::with_backend(mock)
+    }
+
+    #[test]
+    fn test_timing_stats_percentiles() {
+        let durations: Vec<Duration> = (1..=100).map(Duration::from_millis).collect();
+        let stats = TimingStats::from_durations(&durations);
+
+        assert_eq!(stats.min_ms, 1.0);
+        assert_eq!(stats.p50_ms, 50.0);
+        assert_eq!(stats.p90_ms, 90.0);
+        assert_eq!(stats.p99_ms, 99.0);
+        assert_eq!(stats.max_ms, 100.0);
+        assert_eq!(stats.mean_ms, 50.5);
+        assert_eq!(TimingStats::from_durations(&[]), TimingStats::default());
+    }
+
+    #[test]
+    fn test_bench_runs_frame_count() {
+        let device = device();
+        let mut preprocessed = 0;
+        let mut postprocessed = 0;
+        let report = Bench::new(BenchConfig::new().with_frames(20).with_warmup_frames(2).with_queue_depth(3))
+            .with_preprocess(|_| {
+                preprocessed += 1;
+                vec![vec![1u8; 4]]
+            })
+            .with_postprocess(|outputs| {
+                assert_eq!(outputs[0].len(), 8);
+                postprocessed += 1;
+            })
+            .run(&device)
+            .unwrap();
+
+        assert_eq!(report.frames, 20);
+        assert_eq!(preprocessed, 22);
+        assert_eq!(postprocessed, 22);
+        assert!(report.fps > 0.0);
+        assert!(report.latency.p50_ms >= 0.2);
+        assert_eq!(report.queue.capacity, 3);
+        assert!(report.queue.max <= 3);
+    }
+
+    #[test]
+    fn test_bench_stops_after_duration() {
+        let device = device();
+        let config = BenchConfig::new()
+            .with_duration(Duration::from_millis(50))
+            .with_warmup_frames(0);
+        let report = hailors::bench::run(&device, &config).unwrap();
+
+        assert!(report.frames > 0);
+        assert!(report.elapsed_secs < 1.0);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["frames"], report.frames);
+        assert!(json["latency"]["p99_ms"].is_number());
+    }
+
+    #[test]
+    fn test_bench_rejects_wrong_input_size() {
+        let device = device();
+        let result = Bench::new(BenchConfig::new().with_frames(5))
+            .with_preprocess(|_| vec![vec![0u8; 3]])
+            .run(&device);
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use hailors::{
+        buffer::BUFFER_ALIGNMENT, AlignedBuffer, HailoDevice, HailorsError, MockBackend, OutputBuffers, VStreamShape,
+    };
+
+    /// Passes the dequantized values through unchanged.
+    struct Identity;
+
+    impl hailors::Network for Identity {
+        type Output = f32;
+
+        fn parse_output(&self, output_data: &[f32]) -> Vec<f32> {
+            output_data.to_vec()
+        }
+    }
+
+    fn mock() -> MockBackend {
+        MockBackend::new()
+            .with_input("net/input", VStreamShape::new(1, 1, 1))
+            .with_output("net/scores", VStreamShape::new(1, 1, 2))
+            .with_output("net/boxes", VStreamShape::new(1, 1, 4))
+    }
+
+    #[test]
+    fn test_aligned_buffer() {
+        let mut buffer = AlignedBuffer::zeroed(100);
+        assert_eq!(buffer.len(), 100);
+        assert_eq!(buffer.as_ptr() as usize % BUFFER_ALIGNMENT, 0);
+        assert!(buffer.iter().all(|&byte| byte == 0));
+
+        buffer[0] = 7;
+        assert_eq!(buffer.clone()[0], 7);
+        assert!(AlignedBuffer::zeroed(0).is_empty());
+    }
+
+    #[test]
+    fn test_read_output_into_reuses_buffers() {
+        let mock = mock();
+        mock.push_output_f32(0, &[1.0, 2.0]).unwrap();
+        mock.push_output_f32(0, &[3.0, 4.0]).unwrap();
+        let device = HailoDevice::with_backend(mock);
+
+        let mut buffers = device.output_buffers();
+        let mut results = Vec::new();
+        device.read_output_into(&mut buffers, &Identity, &mut results).unwrap();
+        assert_eq!(results, vec![1.0, 2.0]);
+        let frame_ptr = buffers.frame(0).as_ptr();
+        let values_ptr = buffers.values(0).as_ptr();
+        let results_ptr = results.as_ptr();
+
+        device.read_output_into(&mut buffers, &Identity, &mut results).unwrap();
+        assert_eq!(results, vec![3.0, 4.0]);
+        assert_eq!(buffers.frame(0).as_ptr(), frame_ptr);
+        assert_eq!(buffers.values(0).as_ptr(), values_ptr);
+        assert_eq!(results.as_ptr(), results_ptr);
+    }
+
+    #[test]
+    fn test_read_outputs_into_rejects_foreign_buffers() {
+        let device = HailoDevice::with_backend(mock());
+        let mut buffers = OutputBuffers::new(&device.outputs()[..1]);
+
+        assert!(matches!(
+            device.read_outputs_into(&mut buffers),
+            Err(HailorsError::StreamCountMismatch { expected: 2, actual: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_buffer_pool_recycles_buffers() {
+        let device = HailoDevice::with_backend(mock());
+        let pool = device.buffer_pool(1);
+        assert_eq!(pool.available(), 1);
+
+        let mut first = pool.acquire();
+        let second = pool.acquire();
+        assert_eq!(pool.available(), 0);
+        device.read_outputs_into(&mut first).unwrap();
+        assert_eq!(first.frame(1).len(), 16);
+
+        drop(first);
+        drop(second);
+        assert_eq!(pool.available(), 2);
+    }
+}
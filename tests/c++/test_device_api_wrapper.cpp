@@ -197,6 +197,36 @@ TEST_F(HailoTestSuite, CreateAndReleaseVDevice) {
     ASSERT_NE(vdevice_handle, nullptr);
 }
 
+TEST_F(HailoTestSuite, ReadDeviceHealth) {
+    hailo_device_handle devices[4];
+    size_t count = 0;
+
+    hailo_status status = hailors_get_physical_devices(vdevice_handle, devices, 4, &count);
+    ASSERT_EQ(status, HAILO_SUCCESS);
+    ASSERT_GT(count, 0) << "VDevice should have at least one physical device.";
+
+    hailors_device_identity identity;
+    status = hailors_identify_device(devices[0], &identity);
+    ASSERT_EQ(status, HAILO_SUCCESS);
+    ASSERT_GT(strlen(identity.device_id), 0) << "Device ID should not be empty.";
+
+    hailors_temperature_info temperature;
+    status = hailors_get_chip_temperature(devices[0], &temperature);
+    ASSERT_EQ(status, HAILO_SUCCESS);
+    ASSERT_GT(temperature.ts0_temperature, 0.0f);
+}
+
+TEST_F(HailoTestSuite, ListNetworkGroups) {
+    const char* hef_path = "./hef/yolov8s_h8.hef";
+    hailors_network_group_name names[8];
+    size_t count = 0;
+
+    hailo_status status = hailors_get_network_group_names(hef_path, names, 8, &count);
+    ASSERT_EQ(status, HAILO_SUCCESS);
+    ASSERT_GT(count, 0) << "HEF should contain at least one network group.";
+    ASSERT_GT(strlen(names[0].name), 0) << "Network group name should not be empty.";
+}
+
 TEST_F(HailoTestSuite, ConfigureNetworkGroup) {
     const char* hef_path = "./hef/yolov8s_h8.hef";
     hailo_network_group_handle network_group_handle = nullptr;
@@ -211,6 +241,7 @@ TEST_F(HailoTestSuite, ConfigureNetworkGroup) {
     hailo_status status = hailors_configure_hef(
         vdevice_handle,
         hef_path,
+        nullptr,           // Default vstream parameters
         &network_group_handle,
         &input_vstreams,   // Pass pointer to the input vstreams
         &input_count,      // Pass pointer to the input count
@@ -222,6 +253,11 @@ TEST_F(HailoTestSuite, ConfigureNetworkGroup) {
     ASSERT_EQ(status, HAILO_SUCCESS);
     ASSERT_GT(input_frame_size, 0) << "Input frame size should be greater than 0.";
     ASSERT_GT(output_frame_size, 0) << "Output frame size should be greater than 0.";
+
+    // Release in reverse order of creation
+    ASSERT_EQ(hailors_release_input_vstreams(input_vstreams, input_count), HAILO_SUCCESS);
+    ASSERT_EQ(hailors_release_output_vstreams(output_vstreams, output_count), HAILO_SUCCESS);
+    ASSERT_EQ(hailors_release_network_group(network_group_handle), HAILO_SUCCESS);
 }
 
 TEST_F(HailoTestSuite, PerformInference) {
@@ -240,6 +276,7 @@ TEST_F(HailoTestSuite, PerformInference) {
     hailo_status status = hailors_configure_hef(
         vdevice_handle,
         hef_path,
+        nullptr,           // Default vstream parameters
         &network_group_handle,
         &input_vstreams,   // Pass pointer to the input vstreams
         &input_count,      // Pass pointer to the input count
@@ -273,6 +310,11 @@ TEST_F(HailoTestSuite, PerformInference) {
 
 
     ASSERT_TRUE(detections[0].class_id == 16);
+
+    // Release in reverse order of creation
+    ASSERT_EQ(hailors_release_input_vstreams(input_vstreams, input_count), HAILO_SUCCESS);
+    ASSERT_EQ(hailors_release_output_vstreams(output_vstreams, output_count), HAILO_SUCCESS);
+    ASSERT_EQ(hailors_release_network_group(network_group_handle), HAILO_SUCCESS);
 } 
 
 
@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+    use hailors::{DeviceArchitecture, DeviceConfig, HailoRtVersion, HailorsError, Hef, HefArchitecture};
+
+    fn hef(architecture: HefArchitecture, sdk_version: Option<&str>) -> Hef {
+        Hef {
+            version: 3,
+            architecture,
+            sdk_version: sdk_version.map(str::to_string),
+            network_groups: Vec::new(),
+        }
+    }
+
+    const HAILORT_4_19: HailoRtVersion = HailoRtVersion {
+        major: 4,
+        minor: 19,
+        revision: 0,
+    };
+
+    #[test]
+    fn test_architecture_compatibility() {
+        use DeviceArchitecture as Device;
+        use HefArchitecture as Compiled;
+
+        assert!(Compiled::Hailo8.runs_on(Device::Hailo8));
+        assert!(Compiled::Hailo8R.runs_on(Device::Hailo8));
+        assert!(Compiled::Hailo8L.runs_on(Device::Hailo8));
+        assert!(!Compiled::Hailo8.runs_on(Device::Hailo8L));
+        assert!(Compiled::Hailo8L.runs_on(Device::Hailo8L));
+        assert!(!Compiled::Hailo15H.runs_on(Device::Hailo8));
+        assert!(!Compiled::Hailo8.runs_on(Device::Hailo15M));
+        assert!(Compiled::Hailo15H.runs_on(Device::Hailo10H));
+
+        // Unknown architectures are left for HailoRT to judge
+        assert!(Compiled::Unknown(42).runs_on(Device::Hailo8L));
+        assert!(Compiled::Hailo8.runs_on(Device::Unknown(42)));
+    }
+
+    #[test]
+    fn test_architecture_mismatch_names_needed_hef() {
+        let err = hef(HefArchitecture::Hailo8, None)
+            .check_architecture("0000:01:00.0", DeviceArchitecture::Hailo8L)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            HailorsError::ArchitectureMismatch {
+                hef_architecture: HefArchitecture::Hailo8,
+                device_architecture: DeviceArchitecture::Hailo8L,
+                ..
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "HEF is compiled for HAILO8 but device 0000:01:00.0 is HAILO8L; use a HEF compiled for HAILO8L"
+        );
+        assert!(hef(HefArchitecture::Hailo8L, None)
+            .check_architecture("0000:01:00.0", DeviceArchitecture::Hailo8)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_hailort_version_compatibility() {
+        assert_eq!(HAILORT_4_19.newest_compiler(), Some((3, 29)));
+        assert!(hef(HefArchitecture::Hailo8, Some("3.29.0"))
+            .check_hailort_version(HAILORT_4_19)
+            .is_ok());
+        assert!(hef(HefArchitecture::Hailo8, Some("3.27.1"))
+            .check_hailort_version(HAILORT_4_19)
+            .is_ok());
+        assert!(hef(HefArchitecture::Hailo8, None).check_hailort_version(HAILORT_4_19).is_ok());
+        assert!(hef(HefArchitecture::Hailo8, Some("unreleased"))
+            .check_hailort_version(HAILORT_4_19)
+            .is_ok());
+
+        let err = hef(HefArchitecture::Hailo8, Some("3.30.0"))
+            .check_hailort_version(HAILORT_4_19)
+            .unwrap_err();
+        assert!(matches!(err, HailorsError::UnsupportedHefVersion { .. }));
+        assert_eq!(
+            err.to_string(),
+            "HEF was compiled with Dataflow Compiler 3.30.0, which HailoRT 4.19.0 does not support; \
+             upgrade HailoRT or recompile the HEF with Dataflow Compiler 3.29 or older"
+        );
+    }
+
+    #[test]
+    fn test_check_enabled_by_default() {
+        assert!(DeviceConfig::new().check_compatibility);
+        assert!(!DeviceConfig::new().with_compatibility_check(false).check_compatibility);
+    }
+}
@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use hailors::{DeviceConfig, FormatType, StreamConfig};
+
+    #[test]
+    fn test_default_config() {
+        let config = DeviceConfig::default();
+
+        assert_eq!(config.input.format_type, FormatType::Auto);
+        assert_eq!(config.output.format_type, FormatType::Float32);
+        assert!(!config.output.quantized);
+        assert_eq!(config.input.timeout, None);
+        assert_eq!(config.output.queue_size, None);
+        assert!(config.input_streams.is_empty() && config.output_streams.is_empty());
+        assert_eq!(config.batch_size, None);
+        assert_eq!(config.network_group, None);
+    }
+
+    #[test]
+    fn test_config_builder() {
+        let boxes = StreamConfig::new(FormatType::Float32).with_queue_size(8);
+        let config = DeviceConfig::new()
+            .with_input_format(FormatType::Uint8)
+            .with_output_format(FormatType::Uint16)
+            .with_quantized_outputs(true)
+            .with_timeout(Duration::from_millis(500))
+            .with_queue_size(4)
+            .with_batch_size(8)
+            .with_network_group("yolov8s")
+            .with_output_stream("net/boxes", boxes);
+
+        assert_eq!(config.input.format_type, FormatType::Uint8);
+        assert_eq!(config.input.timeout, Some(Duration::from_millis(500)));
+        assert_eq!(config.input.queue_size, Some(4));
+        assert_eq!(config.output.format_type, FormatType::Uint16);
+        assert!(config.output.quantized);
+        assert_eq!(config.output.timeout, Some(Duration::from_millis(500)));
+        assert_eq!(config.output_streams, vec![("net/boxes".to_string(), boxes)]);
+        assert_eq!(config.batch_size, Some(8));
+        assert_eq!(config.network_group.as_deref(), Some("yolov8s"));
+    }
+}
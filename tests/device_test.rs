@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod tests {
+    use hailors::{DeviceArchitecture, DeviceConfig, PcieAddress, SchedulingAlgorithm, VDeviceParams};
+
+    #[test]
+    fn test_pcie_address_display() {
+        let address = PcieAddress {
+            domain: 0,
+            bus: 1,
+            device: 0,
+            func: 0,
+        };
+
+        assert_eq!(address.to_string(), "0000:01:00.0");
+    }
+
+    #[test]
+    fn test_device_architecture_from_u32() {
+        assert_eq!(DeviceArchitecture::from_u32(1), DeviceArchitecture::Hailo8);
+        assert_eq!(DeviceArchitecture::from_u32(2), DeviceArchitecture::Hailo8L);
+        assert_eq!(DeviceArchitecture::from_u32(99), DeviceArchitecture::Unknown(99));
+        assert_eq!(DeviceArchitecture::Hailo8L.to_string(), "HAILO8L");
+    }
+
+    #[test]
+    fn test_vdevice_params() {
+        let defaults = VDeviceParams::default();
+        assert!(defaults.device_ids.is_empty());
+        assert_eq!(defaults.scheduling_algorithm, SchedulingAlgorithm::RoundRobin);
+
+        let params = VDeviceParams::new()
+            .with_device_ids(&["0000:01:00.0", "0000:02:00.0"])
+            .with_scheduling_algorithm(SchedulingAlgorithm::None)
+            .with_group_id("detectors");
+        assert_eq!(params.device_ids, vec!["0000:01:00.0", "0000:02:00.0"]);
+        assert_eq!(params.scheduling_algorithm.as_u32(), 0);
+        assert_eq!(params.group_id.as_deref(), Some("detectors"));
+
+        let config = DeviceConfig::new().with_vdevice_params(params.clone());
+        assert_eq!(config.vdevice, params);
+    }
+}
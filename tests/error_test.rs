@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use hailors::{
+        HailoDevice, HailoStatus, HailorsError, MockBackend, Operation, StreamDirection, VStreamShape,
+    };
+
+    fn device() -> HailoDevice {
+        HailoDevice::with_backend(
+            MockBackend::new()
+                .with_input("net/input", VStreamShape::new(1, 1, 4))
+                .with_output("net/output", VStreamShape::new(1, 1, 1)),
+        )
+    }
+
+    #[test]
+    fn test_status_from_i32() {
+        assert_eq!(HailoStatus::from_i32(0), HailoStatus::Success);
+        assert_eq!(HailoStatus::from_i32(4), HailoStatus::Timeout);
+        assert_eq!(HailoStatus::from_i32(13), HailoStatus::OpenFileFailure);
+        assert_eq!(HailoStatus::from_i32(63), HailoStatus::StreamAbort);
+        assert_eq!(HailoStatus::from_i32(64), HailoStatus::DriverNotInstalled);
+        assert_eq!(HailoStatus::from_i32(-1), HailoStatus::InternalFailure);
+        assert!(HailoStatus::Success.is_success());
+        assert!(!HailoStatus::Timeout.is_success());
+    }
+
+    #[test]
+    fn test_hailo_error_display() {
+        let err = HailorsError::Hailo {
+            operation: Operation::ConfigureHef,
+            status: HailoStatus::OpenFileFailure,
+            hef_path: Some("./hef/missing.hef".to_string()),
+            stream: None,
+        };
+
+        assert_eq!(err.status(), Some(HailoStatus::OpenFileFailure));
+        assert_eq!(
+            err.to_string(),
+            "Failed to configure HEF: Open File Failure (13), HEF: ./hef/missing.hef"
+        );
+    }
+
+    #[test]
+    fn test_stream_errors() {
+        let device = device();
+
+        assert!(matches!(
+            device.write_input(&[0u8; 3]),
+            Err(HailorsError::FrameSizeMismatch { expected: 4, actual: 3, .. })
+        ));
+        assert!(matches!(
+            device.write_input_at(1, &[0u8; 4]),
+            Err(HailorsError::StreamIndexOutOfRange { direction: StreamDirection::Input, index: 1, count: 1 })
+        ));
+        assert!(matches!(
+            device.output_index("net/missing"),
+            Err(HailorsError::StreamNotFound { direction: StreamDirection::Output, .. })
+        ));
+        assert!(matches!(
+            device.write_inputs(&[&[0u8; 4], &[0u8; 4]]),
+            Err(HailorsError::StreamCountMismatch { expected: 1, actual: 2, .. })
+        ));
+        assert_eq!(device.read_output_frame(2).unwrap_err().status(), None);
+    }
+}
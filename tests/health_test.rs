@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+    use hailors::{
+        ChipTemperature, DeviceArchitecture, DeviceHealth, DeviceInfo, FirmwareVersion, HailoDevice, HailorsError,
+        MockBackend, PowerMeasurement, VStreamShape,
+    };
+
+    #[test]
+    fn test_firmware_version_display() {
+        let version = FirmwareVersion {
+            major: 4,
+            minor: 19,
+            revision: 0,
+        };
+
+        assert_eq!(version.to_string(), "4.19.0");
+    }
+
+    #[test]
+    fn test_power_measurement_from_samples() {
+        let power = PowerMeasurement::from_samples(&[1.0, 2.0, 3.0]).unwrap();
+        assert_eq!(power.samples, 3);
+        assert_eq!(power.average_watts, 2.0);
+        assert_eq!(power.min_watts, 1.0);
+        assert_eq!(power.max_watts, 3.0);
+
+        assert!(PowerMeasurement::from_samples(&[]).is_none());
+    }
+
+    #[test]
+    fn test_health_serializes_to_json() {
+        let info = DeviceInfo {
+            device_id: "0000:01:00.0".to_string(),
+            board_name: "Hailo-8L".to_string(),
+            serial_number: "HLDDLBB241600001".to_string(),
+            part_number: "HM21LB1C2LAE".to_string(),
+            product_name: "HAILO-8L AI ACC M.2 B+M KEY MODULE EXT TMP".to_string(),
+            firmware_version: FirmwareVersion {
+                major: 4,
+                minor: 19,
+                revision: 0,
+            },
+            release_firmware: true,
+            protocol_version: 2,
+            architecture: DeviceArchitecture::Hailo8L,
+            extended: None,
+        };
+        let health = DeviceHealth {
+            device_id: "0000:01:00.0".to_string(),
+            temperature: ChipTemperature {
+                ts0_celsius: 48.5,
+                ts1_celsius: 50.0,
+                sample_count: 4,
+            },
+            power: None,
+        };
+
+        let info_json: serde_json::Value = serde_json::to_value(&info).unwrap();
+        assert_eq!(info_json["firmware_version"], "4.19.0");
+        assert_eq!(info_json["architecture"], "HAILO8L");
+        assert!(info_json["extended"].is_null());
+
+        let health_json: serde_json::Value = serde_json::to_value(&health).unwrap();
+        assert_eq!(health_json["temperature"]["ts1_celsius"], 50.0);
+        assert!(health_json["power"].is_null());
+        assert_eq!(health.temperature.max_celsius(), 50.0);
+    }
+
+    #[test]
+    fn test_mock_has_no_device_health() {
+        let mock = MockBackend::new()
+            .with_input("input", VStreamShape::new(1, 1, 1))
+            .with_output("output", VStreamShape::new(1, 1, 1));
+        let device = HailoDevice::with_backend(mock);
+
+        assert!(matches!(device.health(1), Err(HailorsError::NoDevice)));
+        assert!(matches!(device.device_info(), Err(HailorsError::NoDevice)));
+    }
+}
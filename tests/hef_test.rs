@@ -0,0 +1,213 @@
+#[cfg(test)]
+mod tests {
+    use hailors::hef::HEF_MAGIC;
+    use hailors::{FormatType, Hef, HefArchitecture, HailorsError, VStreamShape};
+
+    /// Encodes protobuf messages for building synthetic HEFs.
+    #[derive(Default)]
+    struct Message(Vec<u8>);
+
+    impl Message {
+        fn varint(mut self, field: u32, value: u64) -> Self {
+            self.key(field, 0);
+            self.raw_varint(value);
+            self
+        }
+
+        fn float(mut self, field: u32, value: f32) -> Self {
+            self.key(field, 5);
+            self.0.extend_from_slice(&value.to_bits().to_le_bytes());
+            self
+        }
+
+        fn bytes(mut self, field: u32, value: &[u8]) -> Self {
+            self.key(field, 2);
+            self.raw_varint(value.len() as u64);
+            self.0.extend_from_slice(value);
+            self
+        }
+
+        fn string(self, field: u32, value: &str) -> Self {
+            self.bytes(field, value.as_bytes())
+        }
+
+        fn message(self, field: u32, value: Message) -> Self {
+            self.bytes(field, &value.0)
+        }
+
+        fn key(&mut self, field: u32, wire_type: u8) {
+            self.raw_varint(u64::from(field) << 3 | u64::from(wire_type));
+        }
+
+        fn raw_varint(&mut self, mut value: u64) {
+            while value >= 0x80 {
+                self.0.push(value as u8 | 0x80);
+                value >>= 7;
+            }
+            self.0.push(value as u8);
+        }
+    }
+
+    fn edge_layer(output: bool, name: &str, shape: (u64, u64, u64), data_bytes: u64, boundary: bool) -> Message {
+        let base = Message::default()
+            .varint(1, shape.0)
+            .varint(3, shape.1)
+            .varint(5, shape.2)
+            .varint(10, data_bytes);
+        let numeric = Message::default()
+            .float(1, 3.0)
+            .float(2, 0.5)
+            .float(3, -1.5)
+            .float(4, 126.0);
+        let info = Message::default().string(1, name).message(3, base).message(5, numeric);
+        Message::default()
+            .varint(1, output as u64)
+            .message(3, info)
+            .message(5, Message::default().varint(1, if boundary { 0 } else { 1 }))
+    }
+
+    fn yolo_network_group() -> Message {
+        let first = Message::default()
+            .message(1, edge_layer(false, "yolov8s/input_layer1", (640, 640, 3), 1, true))
+            .message(1, edge_layer(true, "yolov8s/inter_context", (20, 20, 64), 1, false))
+            .message(1, edge_layer(true, "yolov8s/conv41", (80, 80, 64), 2, true));
+        let second = Message::default()
+            .message(1, edge_layer(false, "yolov8s/input_layer1", (640, 640, 3), 1, true))
+            .message(1, edge_layer(true, "yolov8s/conv42", (80, 80, 80), 1, true));
+        let nms = Message::default()
+            .float(1, 0.25)
+            .float(2, 0.7)
+            .varint(3, 100)
+            .varint(4, 80);
+        Message::default()
+            .message(1, Message::default().string(1, "yolov8s"))
+            .message(3, Message::default().message(3, first))
+            .message(3, Message::default().message(3, second))
+            .string(6, "yolov8s/yolov8s")
+            .message(8, Message::default().string(1, "yolov8s/yolov8_nms_postprocess").message(5, nms))
+    }
+
+    /// Wraps a `ProtoHEFHef` in a version 3 HEF header, followed by some compiled code.
+    fn hef_file(proto: Message) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&HEF_MAGIC.to_be_bytes());
+        data.extend_from_slice(&3u32.to_be_bytes());
+        data.extend_from_slice(&(proto.0.len() as u32).to_be_bytes());
+        data.extend_from_slice(&[0u8; 44]);
+        data.extend_from_slice(&proto.0);
+        data.extend_from_slice(&[0xffu8; 64]);
+        data
+    }
+
+    fn yolo_hef() -> Vec<u8> {
+        let header = Message::default().varint(1, 3).string(3, "3.29.0");
+        hef_file(Message::default().message(1, header).message(2, yolo_network_group()))
+    }
+
+    #[test]
+    fn test_parse_header() {
+        let hef = Hef::parse(&yolo_hef()).unwrap();
+        assert_eq!(hef.version, 3);
+        assert_eq!(hef.architecture, HefArchitecture::Hailo8L);
+        assert_eq!(hef.sdk_version.as_deref(), Some("3.29.0"));
+        assert_eq!(hef.network_groups.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_streams() {
+        let hef = Hef::parse(&yolo_hef()).unwrap();
+        let group = hef.network_group("yolov8s").unwrap();
+        assert_eq!(group.networks, vec!["yolov8s/yolov8s".to_string()]);
+
+        // The input is shared by both contexts and listed once; the inter-context layer is skipped
+        assert_eq!(group.inputs.len(), 1);
+        assert_eq!(group.inputs[0].name, "yolov8s/input_layer1");
+        assert_eq!(group.inputs[0].shape, VStreamShape::new(640, 640, 3));
+        assert_eq!(group.inputs[0].format_type, FormatType::Uint8);
+
+        let names: Vec<_> = group.outputs.iter().map(|output| output.name.as_str()).collect();
+        assert_eq!(names, vec!["yolov8s/conv41", "yolov8s/conv42"]);
+        assert_eq!(group.outputs[0].format_type, FormatType::Uint16);
+        assert_eq!(group.outputs[0].quant.zero_point, 3.0);
+        assert_eq!(group.outputs[0].quant.scale, 0.5);
+        assert_eq!(group.outputs[0].quant.limvals_min, -1.5);
+        assert_eq!(group.outputs[0].quant.limvals_max, 126.0);
+    }
+
+    #[test]
+    fn test_parse_nms() {
+        let hef = Hef::parse(&yolo_hef()).unwrap();
+        let nms = hef.network_groups[0].nms.as_ref().unwrap();
+        assert_eq!(nms.name, "yolov8s/yolov8_nms_postprocess");
+        assert_eq!(nms.number_of_classes, 80);
+        assert_eq!(nms.max_bboxes_per_class, 100);
+        assert_eq!(nms.score_threshold, 0.25);
+        assert_eq!(nms.iou_threshold, 0.7);
+    }
+
+    #[test]
+    fn test_unknown_fields_are_skipped() {
+        let header = Message::default().varint(1, 103).varint(2, 7).float(9, 1.0);
+        let proto = Message::default()
+            .message(1, header)
+            .bytes(15, b"padding")
+            .message(2, yolo_network_group());
+        let hef = Hef::parse(&hef_file(proto)).unwrap();
+        assert_eq!(hef.architecture, HefArchitecture::Hailo15H);
+        assert_eq!(hef.sdk_version, None);
+        assert_eq!(hef.network_groups[0].outputs.len(), 2);
+    }
+
+    #[test]
+    fn test_open_reads_only_the_model_description() {
+        let path = std::env::temp_dir().join(format!("hailors_hef_test_{}.hef", std::process::id()));
+        std::fs::write(&path, yolo_hef()).unwrap();
+        let hef = Hef::open(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(hef.unwrap(), Hef::parse(&yolo_hef()).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_files() {
+        assert!(matches!(Hef::parse(b"\x01HE"), Err(HailorsError::InvalidHef(_))));
+        assert!(matches!(Hef::parse(&[0u8; 64]), Err(HailorsError::InvalidHef(_))));
+
+        let mut unsupported = yolo_hef();
+        unsupported[7] = 9;
+        let err = Hef::parse(&unsupported).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid HEF: Unsupported HEF version 9");
+
+        let truncated = yolo_hef();
+        assert!(matches!(
+            Hef::parse(&truncated[..100]),
+            Err(HailorsError::InvalidHef(_))
+        ));
+
+        // A header claiming the largest model description must not overflow the end offset
+        let mut oversized = yolo_hef();
+        oversized[8..12].copy_from_slice(&u32::MAX.to_be_bytes());
+        let err = Hef::parse(&oversized).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid HEF: File ends before the end of the model description"
+        );
+
+        let mut corrupt = hef_file(Message::default().bytes(2, &[0x0a, 0x7f]));
+        corrupt.truncate(corrupt.len() - 64);
+        assert!(matches!(Hef::parse(&corrupt), Err(HailorsError::InvalidHef(_))));
+    }
+
+    #[test]
+    fn test_display_and_json() {
+        let hef = Hef::parse(&yolo_hef()).unwrap();
+        let text = hef.to_string();
+        assert!(text.starts_with("HEF version 3, architecture HAILO8L, SDK 3.29.0"));
+        assert!(text.contains("Input  yolov8s/input_layer1: UINT8 640x640x3"));
+        assert!(text.contains("NMS    yolov8s/yolov8_nms_postprocess: 80 classes"));
+
+        let json: serde_json::Value = serde_json::to_value(&hef).unwrap();
+        assert_eq!(json["architecture"], "HAILO8L");
+        assert_eq!(json["network_groups"][0]["outputs"][0]["format_type"], "UINT16");
+        assert_eq!(json["network_groups"][0]["inputs"][0]["shape"]["width"], 640);
+    }
+}
@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    use hailors::labels::COCO_LABELS;
+    use hailors::network::{Network, YoloDetection};
+    use hailors::{HailorsError, Labels};
+
+    #[test]
+    fn test_coco_labels() {
+        let labels = Labels::coco();
+        assert_eq!(labels.len(), 80);
+        assert_eq!(labels.get(0).as_deref(), Some("person"));
+        assert_eq!(labels.get(16).as_deref(), Some("dog"));
+        assert_eq!(labels.get(79).as_deref(), Some("toothbrush"));
+        assert_eq!(labels.get(80), None);
+        assert_eq!(labels.class_id("dog"), Some(16));
+        assert_eq!(labels.class_id("unicorn"), None);
+        assert_eq!(COCO_LABELS[16], "dog");
+    }
+
+    #[test]
+    fn test_text_labels() {
+        let labels = Labels::from_text("cat\n\n  dog  \r\nbird\n");
+        assert_eq!(labels, Labels::new(["cat", "dog", "bird"]));
+    }
+
+    #[test]
+    fn test_json_labels() {
+        let list = Labels::from_json(r#"["cat", "dog"]"#).unwrap();
+        assert_eq!(list, Labels::new(["cat", "dog"]));
+
+        let map = Labels::from_json(r#"{"0": "cat", "2": "bird"}"#).unwrap();
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.get(1), None);
+        assert_eq!(map.get(2).as_deref(), Some("bird"));
+
+        // Sparse maps do not allocate up to the highest class ID
+        let sparse = Labels::from_json(r#"{"4294967295": "x"}"#).unwrap();
+        assert_eq!(sparse.get(u32::MAX).as_deref(), Some("x"));
+        assert_eq!(sparse.class_id("x"), Some(u32::MAX));
+        let sparse = Labels::from_yaml("4294967295: x
+7: y
+").unwrap();
+        assert_eq!(sparse.get(7).as_deref(), Some("y"));
+
+        let names = Labels::from_json(r#"{"names": ["cat", "dog"]}"#).unwrap();
+        assert_eq!(names, list);
+
+        let err = Labels::from_json(r#"{"cat": "dog"}"#).unwrap_err();
+        assert!(matches!(err, HailorsError::InvalidLabels(_)));
+
+        let err = Labels::from_json(r#"{"classes": 2}"#).unwrap_err();
+        assert!(matches!(err, HailorsError::InvalidLabels(_)));
+    }
+
+    #[test]
+    fn test_yaml_labels() {
+        let list = Labels::from_yaml("- cat\n- dog\n").unwrap();
+        assert_eq!(list, Labels::new(["cat", "dog"]));
+
+        // Ultralytics dataset files name the classes under `names`, next to other keys
+        let dataset = Labels::from_yaml("path: ../datasets/pets\nnames:\n  0: cat\n  1: dog\n").unwrap();
+        assert_eq!(dataset, list);
+
+        assert!(matches!(Labels::from_yaml("42"), Err(HailorsError::InvalidLabels(_))));
+    }
+
+    #[test]
+    fn test_load_by_extension() {
+        let dir = std::env::temp_dir().join(format!("hailors_labels_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let files = [
+            ("labels.txt", "cat\ndog\n"),
+            ("labels.json", r#"["cat", "dog"]"#),
+            ("labels.yml", "names: [cat, dog]\n"),
+        ];
+        for (name, contents) in files {
+            std::fs::write(dir.join(name), contents).unwrap();
+        }
+        let loaded: Vec<_> = files.iter().map(|(name, _)| Labels::load(dir.join(name))).collect();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        for labels in loaded {
+            assert_eq!(labels.unwrap(), Labels::new(["cat", "dog"]));
+        }
+    }
+
+    #[test]
+    fn test_detections_carry_labels() {
+        let network = YoloDetection {
+            num_classes: 17,
+            max_bboxes_per_class: 1,
+            threshold: 0.5,
+            labels: None,
+        };
+        // One dog box, every other class empty
+        let mut output = vec![0.0; 16];
+        output.extend_from_slice(&[1.0, 0.1, 0.2, 0.6, 0.9, 0.91]);
+
+        let detections = network.parse_output(&output);
+        assert_eq!(detections[0].label, None);
+        assert_eq!(detections[0].to_string(), "class 16 0.91 (0.10, 0.20, 0.60, 0.90)");
+
+        let detections = network.with_labels(Labels::coco()).parse_output(&output);
+        assert_eq!(detections[0].class_id, 16);
+        assert_eq!(detections[0].label.as_deref(), Some("dog"));
+        assert_eq!(detections[0].to_string(), "dog 0.91 (0.10, 0.20, 0.60, 0.90)");
+    }
+}
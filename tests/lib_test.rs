@@ -1,86 +1,122 @@
-use std::sync::{Arc, Mutex, MutexGuard};
-use lazy_static::lazy_static; // Add `lazy_static` dependency
-
-lazy_static! {
-    static ref DEVICE_MUTEX: Mutex<()> = Mutex::new(()); // Global Mutex for device access
-}
-
 #[cfg(test)]
 mod tests {
-    use super::*;
-    use hailors::{HailoDevice, network::YoloDetection};
-    use std::ptr;
+    use std::sync::OnceLock;
+
+    use hailors::{DeviceConfig, HailoDevice, Labels, VDevice, network::YoloDetection};
+
+    /// One virtual device shared by every test. Each test configures its own network group on it
+    /// and HailoRT's model scheduler runs them side by side, so tests need no global lock.
+    fn vdevice() -> &'static VDevice {
+        static VDEVICE: OnceLock<VDevice> = OnceLock::new();
+        VDEVICE.get_or_init(|| VDevice::new().expect("Failed to create VDevice"))
+    }
 
-    fn get_device_lock() -> MutexGuard<'static, ()> {
-        DEVICE_MUTEX.lock().unwrap() // Acquire the global lock
+    /// Configures a HEF on the shared virtual device.
+    fn configure(hef_path: &str) -> HailoDevice {
+        vdevice()
+            .configure(hef_path, &DeviceConfig::new())
+            .expect("Failed to configure HailoDevice")
     }
 
     #[test]
     fn test_device_creation_and_release() {
-        let _device_lock = get_device_lock(); // Lock the device for this test
-
-        // Create a new HailoDevice
+        // Configure a HailoDevice on the shared virtual device
         let hef_path = "./hef/yolov8s_h8.hef";
-        let device = Arc::new(Mutex::new(
-            HailoDevice::new(hef_path).expect("Failed to create HailoDevice"),
-        ));
-
-        // Verify release of device
-        {
-            let device_lock = device.lock().unwrap();
-            assert!(device_lock.device_handle != ptr::null_mut(), "Device handle is null");
-        }
+        let device = configure(hef_path);
+        assert!(device.input_frame_size > 0, "Device has no input stream");
+
+        // Verify release of device; the network group can be configured again
+        drop(device);
+        let device = configure(hef_path);
+        assert!(device.input_frame_size > 0, "Device has no input stream");
     }
 
     #[test]
     fn test_hef_configuration() {
-        let _device_lock = get_device_lock(); // Lock the device for this test
-
-        // Create a HailoDevice
+        // Configure a HailoDevice on the shared virtual device
         let hef_path = "./hef/yolov8s_h8.hef";
-        let device = Arc::new(Mutex::new(
-            HailoDevice::new(hef_path).expect("Failed to create HailoDevice"),
-        ));
-
-        let device_lock = device.lock().unwrap();
+        let device = configure(hef_path);
 
         // Verify vstreams setup
         assert!(
-            device_lock.input_vstream != ptr::null_mut(),
-            "Input vstream is null"
+            device.input_frame_size > 0,
+            "Input frame size should be greater than zero"
+        );
+        assert!(
+            device.output_frame_size > 0,
+            "Output frame size should be greater than zero"
         );
         assert!(
-            device_lock.output_vstream != ptr::null_mut(),
-            "Output vstream is null"
+            device.inputs().iter().all(|info| !info.name.is_empty() && info.frame_size > 0),
+            "Every input vstream should have a name and frame size"
         );
         assert!(
-            device_lock.input_frame_size > 0,
-            "Input frame size should be greater than zero"
+            device.outputs().iter().all(|info| !info.name.is_empty() && info.frame_size > 0),
+            "Every output vstream should have a name and frame size"
         );
+        assert_eq!(device.inputs()[0].shape, hailors::VStreamShape::new(640, 640, 3));
         assert!(
-            device_lock.output_frame_size > 0,
-            "Output frame size should be greater than zero"
+            device.outputs()[0].format.order.is_nms(),
+            "YOLOv8 output vstream should be an NMS stream"
         );
     }
 
     #[test]
-    fn test_yolo_inference() {
-        let _device_lock = get_device_lock(); // Lock the device for this test
+    fn test_network_group_selection() {
+        let hef_path = "./hef/yolov8s_h8.hef";
+        let names = hailors::network_group_names(hef_path).expect("Failed to list network groups");
+        assert!(!names.is_empty(), "HEF should contain at least one network group");
+
+        // Configure the first network group by name on a shared virtual device
+        let config = DeviceConfig::new().with_network_group(&names[0]);
+        let device = vdevice().configure(hef_path, &config).expect("Failed to configure network group");
+        assert_eq!(device.network_group(), Some(names[0].as_str()));
+
+        // Unknown network groups are reported instead of silently falling back to the first one
+        let config = DeviceConfig::new().with_network_group("no_such_network_group");
+        let err = vdevice().configure(hef_path, &config).err().expect("Unknown network group should fail");
+        assert_eq!(err.status(), Some(hailors::HailoStatus::NotFound));
+    }
 
-        // Create a HailoDevice
+    #[test]
+    fn test_reconfigure() {
         let hef_path = "./hef/yolov8s_h8.hef";
-        let device = Arc::new(Mutex::new(
-            HailoDevice::new(hef_path).expect("Failed to create HailoDevice"),
-        ));
+        let mut device = configure(hef_path);
+        let device_ids: Vec<_> = device
+            .device_info()
+            .expect("Failed to read device info")
+            .into_iter()
+            .map(|info| info.device_id)
+            .collect();
+
+        // Loading a model in place keeps the same physical devices
+        device.reconfigure(hef_path).expect("Failed to reconfigure HailoDevice");
+        assert!(device.input_frame_size > 0, "Reconfigured device has no input stream");
+        let reconfigured_ids: Vec<_> = device
+            .device_info()
+            .expect("Failed to read device info")
+            .into_iter()
+            .map(|info| info.device_id)
+            .collect();
+        assert_eq!(reconfigured_ids, device_ids);
+
+        // A failed reconfigure leaves the previous model configured
+        assert!(device.reconfigure("./hef/missing.hef").is_err());
+        assert!(device.output_frame_size > 0, "Previous model should stay configured");
+    }
 
-        let device_lock = device.lock().unwrap();
+    #[test]
+    fn test_yolo_inference() {
+        // Configure a HailoDevice on the shared virtual device
+        let hef_path = "./hef/yolov8s_h8.hef";
+        let device = configure(hef_path);
 
-        // Define a YOLO network type
-        let yolo_network = YoloDetection {
-            num_classes: 80,
-            max_bboxes_per_class: 100,
-            threshold: 0.85,
-        };
+        // Configure the YOLO network from the NMS output stream
+        let yolo_network = YoloDetection::from_vstream_info(&device.outputs()[0], 0.85)
+            .expect("Output vstream should be an NMS stream")
+            .with_labels(Labels::coco());
+        assert_eq!(yolo_network.num_classes, 80);
+        assert_eq!(yolo_network.max_bboxes_per_class, 100);
 
         // Read the input RGB file
         let input_file_path = "./images/dog.rgb";
@@ -89,16 +125,16 @@ mod tests {
         // Verify that the input file size matches the expected input frame size
         assert_eq!(
             input_data.len(),
-            device_lock.input_frame_size,
+            device.input_frame_size,
             "Input file size does not match the expected frame size"
         );
 
-        device_lock
+        device
             .write_input(&input_data)
             .expect("Failed to write input frame");
 
         // Perform inference and parse output
-        let detections = device_lock
+        let detections = device
             .read_output(&yolo_network)
             .expect("Failed to read and parse output");
 
@@ -108,8 +144,8 @@ mod tests {
             "No detections found; check input or inference pipeline"
         );
 
-        // Check for the presence of the "dog" class (assuming class ID for "dog" is 16)
-        let dog_detected = detections.iter().any(|d| d.class_id == 16 && d.confidence >= 0.5);
+        // Check for the presence of the "dog" class
+        let dog_detected = detections.iter().any(|d| d.label.as_deref() == Some("dog") && d.confidence >= 0.5);
 
         assert!(
             dog_detected,
@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    use hailors::network::{Network, YoloDetection};
+
+    /// Collects formatted log output in memory.
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Runs `f` with a subscriber that records every event at `WARN` and above.
+    fn capture_warnings<F: FnOnce()>(f: F) -> String {
+        let capture = Capture::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::WARN)
+            .with_ansi(false)
+            .with_writer({
+                let capture = capture.clone();
+                move || capture.clone()
+            })
+            .finish();
+        tracing::subscriber::with_default(subscriber, f);
+        let output = capture.0.lock().unwrap().clone();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_bbox_truncation_is_logged() {
+        let network = YoloDetection {
+            num_classes: 1,
+            max_bboxes_per_class: 1,
+            threshold: 0.5,
+            labels: None,
+        };
+        // Two boxes are reported for a class that holds at most one
+        let output = [2.0, 0.1, 0.1, 0.5, 0.5, 0.9, 0.2, 0.2, 0.6, 0.6, 0.8];
+
+        let mut detections = Vec::new();
+        let logs = capture_warnings(|| detections = network.parse_output(&output));
+
+        assert_eq!(detections.len(), 1);
+        assert!(logs.contains("WARN"), "{}", logs);
+        assert!(logs.contains("bbox_count=2"), "{}", logs);
+        assert!(logs.contains("max_bboxes_per_class=1"), "{}", logs);
+    }
+}
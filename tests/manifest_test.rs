@@ -0,0 +1,184 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use hailors::manifest::{InputOrder, NetworkSpec};
+    use hailors::network::NetworkType;
+    use hailors::{
+        FormatOrder, FormatType, HailorsError, Labels, ModelManifest, NmsShape, VStreamFormat, VStreamInfo,
+        VStreamShape,
+    };
+
+    const YOLO_MANIFEST: &str = r#"
+        hef = "yolov8s_h8.hef"
+        network_group = "yolov8s"
+        labels = "coco.txt"
+
+        [network]
+        type = "yolo-detection"
+        threshold = 0.4
+
+        [input]
+        width = 2
+        height = 1
+        channels = 3
+        order = "nchw"
+
+        [preprocess]
+        swap_rb = true
+    "#;
+
+    fn input_info(height: u32, width: u32, features: u32) -> VStreamInfo {
+        let format = VStreamFormat {
+            format_type: FormatType::Uint8,
+            order: FormatOrder::Nhwc,
+        };
+        VStreamInfo::new("model/input_layer1", VStreamShape::new(height, width, features), format)
+    }
+
+    fn nms_info() -> VStreamInfo {
+        VStreamInfo::nms(
+            "model/nms",
+            NmsShape {
+                number_of_classes: 80,
+                max_bboxes_per_class: 100,
+            },
+        )
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = ModelManifest::from_toml(YOLO_MANIFEST).unwrap();
+        assert_eq!(manifest.hef, PathBuf::from("yolov8s_h8.hef"));
+        assert_eq!(manifest.network_group.as_deref(), Some("yolov8s"));
+        assert_eq!(manifest.input.unwrap().order, InputOrder::Nchw);
+        assert!(manifest.preprocess.swap_rb);
+
+        let NetworkSpec::YoloDetection(spec) = &manifest.network else {
+            panic!("Expected a detection network");
+        };
+        let network = spec.network(&nms_info()).unwrap();
+        assert_eq!(network.threshold, 0.4);
+        assert_eq!(network.num_classes, 80);
+        assert_eq!(network.max_bboxes_per_class, 100);
+    }
+
+    #[test]
+    fn test_detection_parameters_without_nms() {
+        let spec = NetworkSpec::from(NetworkType::YoloDetection);
+        let NetworkSpec::YoloDetection(spec) = spec else {
+            panic!("Expected a detection network");
+        };
+        let err = spec.network(&input_info(1, 1, 4)).err().unwrap();
+        assert!(matches!(err, HailorsError::InvalidManifest(_)));
+
+        let manifest = ModelManifest::from_toml(
+            r#"
+            hef = "model.hef"
+            [network]
+            type = "yolo-detection"
+            num_classes = 2
+            max_bboxes_per_class = 10
+            "#,
+        )
+        .unwrap();
+        let NetworkSpec::YoloDetection(spec) = manifest.network else {
+            panic!("Expected a detection network");
+        };
+        let network = spec.network(&input_info(1, 1, 4)).unwrap();
+        assert_eq!((network.num_classes, network.max_bboxes_per_class), (2, 10));
+        assert_eq!(network.threshold, 0.5);
+    }
+
+    #[test]
+    fn test_pose_defaults_and_threshold_override() {
+        let manifest = ModelManifest::from_toml("hef = \"pose.hef\"\n[network]\ntype = \"yolo-pose\"").unwrap();
+        let NetworkSpec::YoloPose(spec) = manifest.network.with_threshold(0.7) else {
+            panic!("Expected a pose network");
+        };
+        let network = spec.network();
+        assert_eq!(network.num_keypoints, 17);
+        assert_eq!(network.threshold, 0.7);
+    }
+
+    #[test]
+    fn test_invalid_manifests() {
+        for toml in [
+            "hef = \"model.hef\"",
+            "hef = \"model.hef\"\n[network]\ntype = \"resnet\"",
+            "hef = \"model.hef\"\nbatch = 4\n[network]\ntype = \"yolo-pose\"",
+            "hef = \"model.hef\"\n[network]\ntype = \"yolo-pose\"\n[input]\nwidth = 2\nheight = 2\nchannels = 3\n\
+             [preprocess]\nmean = [0.0, 0.0]",
+            "hef = \"model.hef\"\n[network]\ntype = \"yolo-pose\"\n[preprocess]\nstd = [1.0, 0.0, 1.0]",
+        ] {
+            let err = ModelManifest::from_toml(toml).unwrap_err();
+            assert!(matches!(err, HailorsError::InvalidManifest(_)), "{}", toml);
+        }
+    }
+
+    #[test]
+    fn test_load_resolves_paths() {
+        let dir = std::env::temp_dir().join(format!("hailors_manifest_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("model.toml"), YOLO_MANIFEST).unwrap();
+        std::fs::write(dir.join("coco.txt"), "person\nbicycle\n\ncar\n").unwrap();
+
+        let manifest = ModelManifest::load(dir.join("model.toml")).unwrap();
+        let labels = manifest.load_labels().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(manifest.hef, dir.join("yolov8s_h8.hef"));
+        assert_eq!(labels.unwrap(), Labels::new(["person", "bicycle", "car"]));
+    }
+
+    #[test]
+    fn test_check_input() {
+        let manifest = ModelManifest::from_toml(YOLO_MANIFEST).unwrap();
+        assert!(manifest.check_input(&input_info(1, 2, 3)).is_ok());
+        let err = manifest.check_input(&input_info(2, 2, 3)).unwrap_err();
+        assert!(matches!(err, HailorsError::InvalidManifest(_)));
+    }
+
+    #[test]
+    fn test_preprocess_planar_bgr() {
+        let manifest = ModelManifest::from_toml(YOLO_MANIFEST).unwrap();
+        // Two pixels stored as B, G and R planes
+        let planar = [10, 11, 20, 21, 30, 31];
+        let frame = manifest.preprocess(&planar, &input_info(1, 2, 3)).unwrap();
+        assert_eq!(frame, vec![30, 20, 10, 31, 21, 11]);
+
+        let err = manifest.preprocess(&planar[..5], &input_info(1, 2, 3)).unwrap_err();
+        assert!(matches!(err, HailorsError::FrameSizeMismatch { expected: 6, actual: 5, .. }));
+    }
+
+    #[test]
+    fn test_preprocess_normalizes_to_float32() {
+        let manifest = ModelManifest::from_toml(
+            r#"
+            hef = "model.hef"
+            [network]
+            type = "yolo-pose"
+            [preprocess]
+            mean = [0.0, 100.0]
+            std = [2.0, 50.0]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(manifest.device_config().input.format_type, FormatType::Float32);
+
+        let frame = manifest.preprocess(&[4, 200], &input_info(1, 1, 2)).unwrap();
+        let values: Vec<f32> = frame
+            .chunks_exact(4)
+            .map(|bytes| f32::from_ne_bytes(bytes.try_into().unwrap()))
+            .collect();
+        assert_eq!(values, vec![2.0, 2.0]);
+
+        // Without an [input] table, the lengths are checked against the stream
+        let err = manifest.preprocess(&[4, 200, 0], &input_info(1, 1, 3)).unwrap_err();
+        assert!(matches!(err, HailorsError::InvalidManifest(_)));
+        assert_eq!(
+            err.to_string(),
+            "Invalid model manifest: preprocess.mean has 2 values for 3 channels of model/input_layer1"
+        );
+    }
+}
@@ -0,0 +1,280 @@
+#[cfg(test)]
+mod tests {
+    use hailors::{
+        FormatOrder, FormatType, HailoDevice, HailorsError, MockBackend, NmsShape, QuantInfo, VStreamFormat,
+        VStreamInfo, VStreamShape,
+        network::YoloDetection,
+    };
+
+    const NUM_CLASSES: usize = 80;
+    const MAX_BBOXES_PER_CLASS: usize = 100;
+
+    /// Creates a mock with a 640x640 RGB input and a YOLOv8 NMS-shaped output.
+    fn yolo_mock() -> MockBackend {
+        MockBackend::new()
+            .with_input("yolov8s/input_layer1", VStreamShape::new(640, 640, 3))
+            .with_output_info(VStreamInfo::nms(
+                "yolov8s/yolov8_nms_postprocess",
+                NmsShape {
+                    number_of_classes: NUM_CLASSES as u32,
+                    max_bboxes_per_class: MAX_BBOXES_PER_CLASS as u32,
+                },
+            ))
+    }
+
+    /// Builds an NMS output where only `class_id` has a single bounding box.
+    fn nms_output(class_id: usize, bbox: [f32; 4], confidence: f32) -> Vec<f32> {
+        let mut values = Vec::new();
+        for class in 0..NUM_CLASSES {
+            if class == class_id {
+                values.push(1.0);
+                values.extend_from_slice(&bbox);
+                values.push(confidence);
+            } else {
+                values.push(0.0);
+            }
+        }
+        values
+    }
+
+    #[test]
+    fn test_mock_frame_sizes() {
+        let device = HailoDevice::with_backend(yolo_mock());
+
+        assert_eq!(device.input_frame_size, 640 * 640 * 3);
+        assert_eq!(device.output_frame_size, NUM_CLASSES * (1 + MAX_BBOXES_PER_CLASS * 5) * 4);
+    }
+
+    #[test]
+    fn test_mock_yolo_inference() {
+        let mock = yolo_mock();
+        mock.push_output_f32(0, &nms_output(16, [0.1, 0.2, 0.6, 0.9], 0.91))
+            .expect("Failed to script output");
+        let device = HailoDevice::with_backend(mock.clone());
+
+        let yolo_network = YoloDetection::from_vstream_info(&device.outputs()[0], 0.85)
+            .expect("Output stream is not an NMS stream");
+
+        let input_data = std::fs::read("./images/dog.rgb").expect("Failed to read input file");
+        device
+            .write_input(&input_data)
+            .expect("Failed to write input frame");
+        let detections = device
+            .read_output(&yolo_network)
+            .expect("Failed to read and parse output");
+
+        assert_eq!(mock.written_inputs(0), vec![input_data]);
+        assert_eq!(detections.len(), 1);
+        assert_eq!(detections[0].class_id, 16);
+        assert_eq!(detections[0].bbox, (0.1, 0.2, 0.6, 0.9));
+
+        // Without scripted output the mock returns an empty NMS frame.
+        let detections = device
+            .read_output(&yolo_network)
+            .expect("Failed to read and parse output");
+        assert!(detections.is_empty());
+    }
+
+    #[test]
+    fn test_mock_vstream_metadata() {
+        let device = HailoDevice::with_backend(yolo_mock());
+
+        let input = &device.inputs()[0];
+        assert_eq!(input.shape, VStreamShape::new(640, 640, 3));
+        assert_eq!(input.format.format_type, FormatType::Uint8);
+        assert_eq!(input.format.order, FormatOrder::Nhwc);
+        assert!(input.nms.is_none());
+
+        let output = &device.outputs()[0];
+        assert_eq!(output.format.format_type, FormatType::Float32);
+        assert!(output.format.order.is_nms());
+        assert_eq!(output.nms.map(|nms| nms.number_of_classes), Some(NUM_CLASSES as u32));
+
+        let network = YoloDetection::from_vstream_info(output, 0.5).unwrap();
+        assert_eq!(network.num_classes, NUM_CLASSES);
+        assert_eq!(network.max_bboxes_per_class, MAX_BBOXES_PER_CLASS);
+        assert!(YoloDetection::from_vstream_info(input, 0.5).is_none());
+    }
+
+    #[test]
+    fn test_mock_dequantizes_uint8_output() {
+        let format = VStreamFormat {
+            format_type: FormatType::Uint8,
+            order: FormatOrder::Nhwc,
+        };
+        let mut output = VStreamInfo::new("net/scores", VStreamShape::new(1, 1, 3), format);
+        output.quant = QuantInfo {
+            zero_point: 128.0,
+            scale: 0.5,
+            ..QuantInfo::default()
+        };
+        let mock = MockBackend::new()
+            .with_input("net/input", VStreamShape::new(1, 1, 1))
+            .with_output_info(output);
+        mock.push_output(0, vec![128, 130, 0]).unwrap();
+
+        /// Passes the dequantized values through unchanged.
+        struct Identity;
+        impl hailors::Network for Identity {
+            type Output = f32;
+            fn parse_output(&self, output_data: &[f32]) -> Vec<f32> {
+                output_data.to_vec()
+            }
+        }
+
+        let device = HailoDevice::with_backend(mock);
+        assert_eq!(device.output_frame_size, 3);
+        assert_eq!(device.read_output(&Identity).unwrap(), vec![0.0, 1.0, -64.0]);
+    }
+
+    #[test]
+    fn test_mock_infer_batch() {
+        let mock = yolo_mock();
+        for class_id in [3, 16, 42] {
+            mock.push_output_f32(0, &nms_output(class_id, [0.1, 0.2, 0.3, 0.4], 0.9)).unwrap();
+        }
+        let device = HailoDevice::with_backend(mock.clone());
+        let network = YoloDetection::from_vstream_info(&device.outputs()[0], 0.5).unwrap();
+
+        let frames = [vec![1u8; 640 * 640 * 3], vec![2u8; 640 * 640 * 3], vec![3u8; 640 * 640 * 3]];
+        let frames: Vec<&[u8]> = frames.iter().map(Vec::as_slice).collect();
+        let results = device.infer_batch(&frames, &network).expect("Failed to run batch");
+
+        let classes: Vec<u32> = results.iter().map(|detections| detections[0].class_id).collect();
+        assert_eq!(classes, vec![3, 16, 42]);
+        assert_eq!(mock.written_inputs(0).len(), 3);
+        assert_eq!(mock.written_inputs(0)[2][0], 3);
+        // The last frames are flushed, so a partial batch is processed
+        assert_eq!(mock.flush_count(0), 1);
+
+        // A wrongly sized frame is rejected before anything is written
+        assert!(device.infer_batch(&[&[0u8; 4]], &network).is_err());
+        assert_eq!(mock.written_inputs(0).len(), 3);
+        assert_eq!(mock.flush_count(0), 1);
+    }
+
+    #[test]
+    fn test_infer_batch_requires_one_input() {
+        let mock = MockBackend::new()
+            .with_input("stereo/left", VStreamShape::new(1, 1, 1))
+            .with_input("stereo/right", VStreamShape::new(1, 1, 1))
+            .with_output("stereo/nms", VStreamShape::new(1, 1, 1));
+        let device = HailoDevice::with_backend(mock.clone());
+        let network = YoloDetection {
+            num_classes: 1,
+            max_bboxes_per_class: 1,
+            threshold: 0.5,
+            labels: None,
+        };
+
+        let err = device.infer_batch(&[&[0u8]], &network).err().unwrap();
+        assert!(matches!(
+            err,
+            HailorsError::StreamCountMismatch {
+                expected: 1,
+                actual: 2,
+                ..
+            }
+        ));
+        assert_eq!(err.to_string(), "input count mismatch: expected 1, got 2");
+        assert!(mock.written_inputs(0).is_empty());
+    }
+
+    #[test]
+    fn test_mock_cannot_reconfigure() {
+        let mut device = HailoDevice::with_backend(yolo_mock());
+
+        assert!(matches!(device.reconfigure("./hef/yolov8s_h8.hef"), Err(HailorsError::NoDevice)));
+        assert_eq!(device.input_frame_size, 640 * 640 * 3);
+    }
+
+    #[test]
+    fn test_mock_rejects_wrong_input_size() {
+        let device = HailoDevice::with_backend(yolo_mock());
+
+        assert!(device.write_input(&[0u8; 16]).is_err());
+    }
+
+    #[test]
+    fn test_mock_replays_recorded_outputs() {
+        let mock = MockBackend::new()
+            .with_input("input", VStreamShape::new(1, 1, 1))
+            .with_output("output", VStreamShape::new(1, 1, 2));
+        let path = std::env::temp_dir().join("hailors_mock_recording.bin");
+        let recording: Vec<u8> = [1.0_f32, 2.0, 3.0, 4.0]
+            .iter()
+            .flat_map(|value| value.to_ne_bytes())
+            .collect();
+        std::fs::write(&path, recording).expect("Failed to write recording");
+
+        mock.push_output_file(0, &path).expect("Failed to load recording");
+        std::fs::remove_file(&path).ok();
+
+        let device = HailoDevice::with_backend(mock);
+        let frame = device.read_output_frame(0).unwrap();
+        assert_eq!(frame, [1.0_f32, 2.0].iter().flat_map(|v| v.to_ne_bytes()).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn test_mock_quantizes_scripted_values() {
+        let format = VStreamFormat {
+            format_type: FormatType::Uint16,
+            order: FormatOrder::Nhwc,
+        };
+        let mut scores = VStreamInfo::new("net/scores", VStreamShape::new(1, 1, 3), format);
+        scores.quant = QuantInfo {
+            zero_point: 10.0,
+            scale: 0.5,
+            ..QuantInfo::default()
+        };
+        let mock = MockBackend::new()
+            .with_input("net/input", VStreamShape::new(1, 1, 1))
+            .with_output_info(scores);
+        mock.push_output_f32(0, &[0.0, 2.0]).unwrap();
+        let device = HailoDevice::with_backend(mock);
+
+        let frame = device.read_output_frame(0).unwrap();
+        let expected: Vec<u8> = [10u16, 14, 10].iter().flat_map(|value| value.to_ne_bytes()).collect();
+        assert_eq!(frame, expected);
+    }
+
+    #[test]
+    fn test_mock_multiple_streams() {
+        let mock = MockBackend::new()
+            .with_input("stereo/left", VStreamShape::new(2, 2, 1))
+            .with_input("stereo/right", VStreamShape::new(2, 2, 1))
+            .with_output("stereo/disparity", VStreamShape::new(2, 2, 1))
+            .with_output("stereo/confidence", VStreamShape::new(1, 1, 1));
+        mock.push_output_f32(1, &[0.5]).unwrap();
+        let device = HailoDevice::with_backend(mock.clone());
+
+        assert_eq!(device.inputs().len(), 2);
+        assert_eq!(device.outputs()[0].frame_size, 16);
+        assert_eq!(device.outputs()[1].frame_size, 4);
+        assert_eq!(device.input_index("stereo/right").unwrap(), 1);
+        assert_eq!(device.output_index("stereo/confidence").unwrap(), 1);
+        assert!(device.input_index("stereo/center").is_err());
+
+        let outputs = device
+            .infer(&[&[1, 2, 3, 4], &[5, 6, 7, 8]])
+            .expect("Failed to run inference");
+
+        assert_eq!(mock.written_inputs(0), vec![vec![1, 2, 3, 4]]);
+        assert_eq!(mock.written_inputs(1), vec![vec![5, 6, 7, 8]]);
+        assert_eq!(outputs, vec![vec![0u8; 16], 0.5_f32.to_ne_bytes().to_vec()]);
+    }
+
+    #[test]
+    fn test_mock_streams_by_name() {
+        let mock = MockBackend::new()
+            .with_input("net/input", VStreamShape::new(1, 1, 2))
+            .with_output("net/boxes", VStreamShape::new(1, 1, 1))
+            .with_output("net/scores", VStreamShape::new(1, 1, 1));
+        let device = HailoDevice::with_backend(mock.clone());
+
+        device.write_input_by_name("net/input", &[7, 8]).unwrap();
+        assert!(device.write_input_by_name("net/other", &[7, 8]).is_err());
+        assert!(device.write_inputs(&[]).is_err());
+        assert_eq!(mock.written_inputs(0), vec![vec![7, 8]]);
+    }
+}
@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod tests {
+    use hailors::{HailoDevice, HailorsError, MockBackend, VStreamShape};
+
+    fn mock() -> MockBackend {
+        MockBackend::new()
+            .with_input("net/input", VStreamShape::new(1, 1, 2))
+            .with_output("net/output", VStreamShape::new(1, 1, 1))
+    }
+
+    fn f32_frame(value: f32) -> Vec<u8> {
+        value.to_ne_bytes().to_vec()
+    }
+
+    #[test]
+    fn test_pipeline_returns_outputs_in_order() {
+        let mock = mock();
+        for value in 0..5 {
+            mock.push_output_f32(0, &[value as f32]).unwrap();
+        }
+        let mut pipeline = HailoDevice::with_backend(mock.clone()).into_pipeline(2);
+
+        for value in 0..5 {
+            pipeline.send(vec![vec![value, value]]).unwrap();
+        }
+        let first = pipeline.recv().unwrap().unwrap();
+        assert_eq!(first, vec![f32_frame(0.0)]);
+
+        let rest = pipeline.flush().unwrap();
+        assert_eq!(rest, (1..5).map(|value| vec![f32_frame(value as f32)]).collect::<Vec<_>>());
+        assert!(pipeline.recv().is_none());
+
+        pipeline.shutdown();
+        assert_eq!(mock.written_inputs(0).len(), 5);
+        assert_eq!(mock.written_inputs(0)[4], vec![4, 4]);
+    }
+
+    #[test]
+    fn test_pipeline_rejects_wrong_frames() {
+        let mut pipeline = HailoDevice::with_backend(mock()).into_pipeline(2);
+
+        assert!(matches!(
+            pipeline.send(vec![vec![0; 3]]),
+            Err(HailorsError::FrameSizeMismatch { expected: 2, actual: 3, .. })
+        ));
+        assert!(matches!(
+            pipeline.send(Vec::new()),
+            Err(HailorsError::StreamCountMismatch { expected: 1, actual: 0, .. })
+        ));
+        assert!(pipeline.recv().is_none());
+    }
+
+    #[test]
+    fn test_split_pipeline_drains_on_close() {
+        let mock = mock();
+        let (writer, reader) = HailoDevice::with_backend(mock.clone()).into_pipeline(1).split();
+
+        let feeder = std::thread::spawn(move || {
+            for value in 0..10 {
+                writer.send(vec![vec![value, 0]]).unwrap();
+            }
+            // Dropping the writer closes the pipeline once the queued frames are written
+        });
+
+        let mut reader = reader;
+        let outputs: Vec<_> = reader.by_ref().collect::<Result<_, _>>().unwrap();
+        assert_eq!(outputs.len(), 10);
+        feeder.join().unwrap();
+
+        let device = reader.shutdown();
+        assert_eq!(device.inputs().len(), 1);
+        assert_eq!(mock.written_inputs(0).len(), 10);
+    }
+}
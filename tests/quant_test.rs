@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use hailors::{
+        quant, FormatOrder, FormatType, HailoDevice, MockBackend, QuantInfo, VStreamFormat, VStreamInfo, VStreamShape,
+    };
+
+    fn quant_info(zero_point: f32, scale: f32) -> QuantInfo {
+        QuantInfo {
+            zero_point,
+            scale,
+            ..QuantInfo::default()
+        }
+    }
+
+    #[test]
+    fn test_dequantize_u8() {
+        // Longer than any SIMD width, with a remainder
+        let input: Vec<u8> = (0..=255).collect();
+        let mut output = vec![0.0; input.len()];
+        quant::dequantize_u8(&input, quant_info(10.0, 0.25), &mut output);
+        for (&quantized, &value) in input.iter().zip(&output) {
+            assert_eq!(value, (quantized as f32 - 10.0) * 0.25);
+        }
+    }
+
+    #[test]
+    fn test_dequantize_u16() {
+        let input = [0u16, 1000, 65535];
+        let mut output = [0.0; 3];
+        quant::dequantize_u16(&input, quant_info(1000.0, 0.5), &mut output);
+        assert_eq!(output, [-500.0, 0.0, 32267.5]);
+    }
+
+    #[test]
+    fn test_quantize_rounds_and_saturates() {
+        let quant_info = quant_info(128.0, 0.5);
+        let mut output = [0u8; 6];
+        quant::quantize_u8(&[0.0, 0.74, 0.76, -1000.0, 1000.0, f32::NAN], quant_info, &mut output);
+        assert_eq!(output, [128, 129, 130, 0, 255, 0]);
+
+        let mut output = [0u16; 3];
+        quant::quantize_u16(&[-1.0, 2.0, 1e9], quant_info, &mut output);
+        assert_eq!(output, [126, 132, 65535]);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let quant_info = quant_info(3.0, 0.1);
+        let input: Vec<u8> = (0..=255).collect();
+        let mut values = vec![0.0; input.len()];
+        quant::dequantize_u8(&input, quant_info, &mut values);
+        let mut output = vec![0u8; input.len()];
+        quant::quantize_u8(&values, quant_info, &mut output);
+        // Values below the zero point dequantize to negative numbers and quantize back unchanged
+        assert_eq!(output, input);
+    }
+
+    #[test]
+    #[should_panic(expected = "Input and output lengths differ")]
+    fn test_length_mismatch_panics() {
+        quant::dequantize_u8(&[1, 2, 3], QuantInfo::default(), &mut [0.0; 2]);
+    }
+
+    #[test]
+    fn test_frames() {
+        let quant_info = quant_info(0.0, 2.0);
+        let mut frame = Vec::new();
+        let mut values = Vec::new();
+        for format_type in [FormatType::Uint8, FormatType::Uint16, FormatType::Float32, FormatType::Auto] {
+            quant::quantize(format_type, quant_info, &[2.0, 4.0, 6.0], &mut frame);
+            assert_eq!(frame.len(), 3 * format_type.element_size());
+            quant::dequantize(format_type, quant_info, &frame, &mut values);
+            assert_eq!(values, vec![2.0, 4.0, 6.0], "{}", format_type);
+        }
+
+        // Both functions replace the previous contents
+        quant::quantize(FormatType::Uint8, quant_info, &[8.0], &mut frame);
+        assert_eq!(frame, vec![4]);
+        quant::dequantize(FormatType::Uint8, quant_info, &frame, &mut values);
+        assert_eq!(values, vec![8.0]);
+    }
+
+    #[test]
+    fn test_write_input_f32_quantizes() {
+        let format = VStreamFormat {
+            format_type: FormatType::Uint8,
+            order: FormatOrder::Nhwc,
+        };
+        let mut info = VStreamInfo::new("net/input", VStreamShape::new(1, 1, 3), format);
+        info.quant = quant_info(0.0, 1.0 / 255.0);
+        let mock = MockBackend::new()
+            .with_input_info(info)
+            .with_output("net/output", VStreamShape::new(1, 1, 1));
+        let device = HailoDevice::with_backend(mock.clone());
+
+        device.write_input_f32(0, &[0.0, 0.2, 1.0]).unwrap();
+        assert_eq!(mock.written_inputs(0), vec![vec![0, 51, 255]]);
+        assert!(device.write_input_f32(0, &[0.0, 0.2]).is_err());
+        assert!(device.write_input_f32(1, &[0.0]).is_err());
+    }
+}
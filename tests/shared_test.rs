@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::sync::{Condvar, Mutex};
+
+    use hailors::{
+        FormatOrder, FormatType, HailoDevice, InferenceBackend, InputWriter, OutputReader, SharedDevice,
+        VStreamFormat, VStreamInfo, VStreamShape,
+    };
+
+    /// Returns every written frame as the output of the matching read, in order. Reads block
+    /// until a frame has been written, like vstream reads.
+    struct Echo {
+        inputs: Vec<VStreamInfo>,
+        outputs: Vec<VStreamInfo>,
+        queue: Mutex<VecDeque<Vec<u8>>>,
+        written: Condvar,
+    }
+
+    impl Echo {
+        fn new() -> Self {
+            let format = VStreamFormat {
+                format_type: FormatType::Uint8,
+                order: FormatOrder::Nhwc,
+            };
+            Self {
+                inputs: vec![VStreamInfo::new("echo/input", VStreamShape::new(1, 1, 8), format)],
+                outputs: vec![VStreamInfo::new("echo/output", VStreamShape::new(1, 1, 8), format)],
+                queue: Mutex::new(VecDeque::new()),
+                written: Condvar::new(),
+            }
+        }
+    }
+
+    impl InferenceBackend for Echo {
+        fn inputs(&self) -> &[VStreamInfo] {
+            &self.inputs
+        }
+
+        fn outputs(&self) -> &[VStreamInfo] {
+            &self.outputs
+        }
+
+        fn write_input(&self, _index: usize, frame: &[u8]) -> hailors::Result<()> {
+            self.queue.lock().unwrap().push_back(frame.to_vec());
+            self.written.notify_one();
+            Ok(())
+        }
+
+        fn read_output(&self, _index: usize, buffer: &mut [u8]) -> hailors::Result<()> {
+            let queue = self.queue.lock().unwrap();
+            let mut queue = self.written.wait_while(queue, |queue| queue.is_empty()).unwrap();
+            buffer.copy_from_slice(&queue.pop_front().unwrap());
+            Ok(())
+        }
+    }
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_handles_are_thread_safe() {
+        assert_send_sync::<HailoDevice>();
+        assert_send_sync::<InputWriter>();
+        assert_send_sync::<OutputReader>();
+        assert_send_sync::<SharedDevice>();
+    }
+
+    #[test]
+    fn test_split_device_across_threads() {
+        let (writer, reader) = HailoDevice::with_backend(Echo::new()).split();
+
+        let feeder = std::thread::spawn(move || {
+            for value in 0..20u8 {
+                writer.write_inputs(&[&[value; 8]]).unwrap();
+            }
+            writer
+        });
+        for value in 0..20u8 {
+            assert_eq!(reader.read_output_frame(0).unwrap(), vec![value; 8]);
+        }
+
+        let writer = feeder.join().unwrap();
+        let device = writer.reunite(reader).ok().expect("Halves of the same device should reunite");
+        assert_eq!(device.inputs().len(), 1);
+    }
+
+    #[test]
+    fn test_reunite_rejects_foreign_halves() {
+        let (writer, _) = HailoDevice::with_backend(Echo::new()).split();
+        let (_, reader) = HailoDevice::with_backend(Echo::new()).split();
+
+        assert!(writer.reunite(reader).is_err());
+    }
+
+    #[test]
+    fn test_shared_device_pairs_outputs_with_inputs() {
+        let device = HailoDevice::with_backend(Echo::new()).into_shared();
+
+        let workers: Vec<_> = (0..8u8)
+            .map(|worker| {
+                let device = device.clone();
+                std::thread::spawn(move || {
+                    for frame in 0..25u8 {
+                        let input = [worker, frame, 0, 0, 0, 0, 0, worker ^ frame];
+                        let outputs = device.infer(&[&input]).unwrap();
+                        assert_eq!(outputs, vec![input.to_vec()]);
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        assert!(device.into_inner().is_some());
+    }
+}
@@ -0,0 +1,202 @@
+#[cfg(test)]
+mod tests {
+    use hailors::network::{Network, YoloPose};
+    use hailors::{
+        FormatOrder, FormatType, HailoDevice, HailorsError, MockBackend, QuantInfo, Tensor, VStreamFormat, VStreamInfo,
+        VStreamShape,
+    };
+
+    /// Creates a mock with a quantized UINT8 output and a FLOAT32 output.
+    fn mock() -> MockBackend {
+        let format = VStreamFormat {
+            format_type: FormatType::Uint8,
+            order: FormatOrder::Nhwc,
+        };
+        let mut scores = VStreamInfo::new("net/scores", VStreamShape::new(1, 1, 3), format);
+        scores.quant = QuantInfo {
+            zero_point: 2.0,
+            scale: 0.5,
+            ..QuantInfo::default()
+        };
+        MockBackend::new()
+            .with_input("net/input", VStreamShape::new(1, 1, 1))
+            .with_output_info(scores)
+            .with_output("net/boxes", VStreamShape::new(1, 1, 2))
+    }
+
+    #[test]
+    fn test_read_raw_outputs() {
+        let mock = mock();
+        mock.push_output(0, vec![2, 4, 0]).unwrap();
+        mock.push_output_f32(1, &[1.5, -1.0]).unwrap();
+        let device = HailoDevice::with_backend(mock);
+
+        let tensors = device.read_raw_outputs().unwrap();
+        assert_eq!(tensors.len(), 2);
+
+        let scores = &tensors[0];
+        assert_eq!(scores.name, "net/scores");
+        assert_eq!(scores.dtype, FormatType::Uint8);
+        assert_eq!(scores.order, FormatOrder::Nhwc);
+        assert_eq!(scores.shape, VStreamShape::new(1, 1, 3));
+        assert_eq!(scores.as_bytes(), &[2, 4, 0]);
+        assert_eq!(scores.len(), 3);
+        assert_eq!(scores.to_f32(), vec![0.0, 1.0, -1.0]);
+        assert_eq!(
+            scores.to_string(),
+            "net/scores: UINT8 NHWC 1x1x3 (zero point 2, scale 0.5), 3 bytes"
+        );
+
+        let boxes = &tensors[1];
+        assert_eq!(boxes.dtype, FormatType::Float32);
+        assert_eq!(boxes.len(), 2);
+        assert_eq!(boxes.to_f32(), vec![1.5, -1.0]);
+    }
+
+    #[test]
+    fn test_raw_tensor_dump_replays_in_mock() {
+        let mock = mock();
+        mock.push_output_f32(1, &[3.0, 4.0]).unwrap();
+        let device = HailoDevice::with_backend(mock);
+        let tensor = device.read_raw_output(1).unwrap();
+
+        let path = std::env::temp_dir().join("hailors_raw_tensor.bin");
+        tensor.save(&path).unwrap();
+        let replay = self::mock();
+        replay.push_output_file(1, &path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let replayed = HailoDevice::with_backend(replay).read_raw_output(1).unwrap();
+        assert_eq!(replayed, tensor);
+    }
+
+    #[test]
+    fn test_index_by_position() {
+        // Two rows of two pixels with three features, stored as NHWC, NHCW and NCHW
+        let shape = VStreamShape::new(2, 2, 3);
+        let nhwc: Vec<f32> = (0..12).map(|value| value as f32).collect();
+        let nhcw = [0.0, 3.0, 1.0, 4.0, 2.0, 5.0, 6.0, 9.0, 7.0, 10.0, 8.0, 11.0];
+        let nchw = [0.0, 3.0, 6.0, 9.0, 1.0, 4.0, 7.0, 10.0, 2.0, 5.0, 8.0, 11.0];
+        for (order, values) in [
+            (FormatOrder::Nhwc, &nhwc[..]),
+            (FormatOrder::Nhcw, &nhcw[..]),
+            (FormatOrder::Nchw, &nchw[..]),
+        ] {
+            let tensor = Tensor::from_values(shape, values).with_order(order);
+            assert_eq!(tensor.get(1, 0, 2), Some(8.0), "{}", order);
+            assert_eq!(tensor.get(0, 1, 1), Some(4.0), "{}", order);
+            assert_eq!(tensor.get(2, 0, 0), None, "{}", order);
+        }
+
+        let quantized = Tensor::from_values(VStreamShape::new(1, 1, 2), &[4u16, 8]).with_quant(QuantInfo {
+            zero_point: 4.0,
+            scale: 0.25,
+            ..QuantInfo::default()
+        });
+        assert_eq!(quantized.get(0, 0, 1), Some(1.0));
+
+        let nms = Tensor::from_values(shape, &nhwc).with_order(FormatOrder::HailoNms);
+        assert_eq!(nms.strides, None);
+        assert_eq!(nms.get(0, 0, 0), None);
+    }
+
+    #[test]
+    fn test_write_input_tensor_converts_layout() {
+        let format = VStreamFormat {
+            format_type: FormatType::Uint8,
+            order: FormatOrder::Nhwc,
+        };
+        let mut info = VStreamInfo::new("net/input", VStreamShape::new(1, 2, 3), format);
+        info.quant = QuantInfo {
+            zero_point: 0.0,
+            scale: 0.5,
+            ..QuantInfo::default()
+        };
+        let mock = MockBackend::new()
+            .with_input_info(info.clone())
+            .with_output("net/output", VStreamShape::new(1, 1, 1));
+        let device = HailoDevice::with_backend(mock.clone());
+
+        // A matching tensor is written as is
+        let frame = [1u8, 2, 3, 4, 5, 6];
+        let tensor = Tensor::new(&info, &frame[..]);
+        assert!(matches!(tensor.to_frame(&info).unwrap(), std::borrow::Cow::Borrowed(_)));
+        device.write_input_tensor(0, &tensor).unwrap();
+
+        // A planar FLOAT32 image is reordered and quantized
+        let planar = Tensor::from_values(VStreamShape::new(1, 2, 3), &[0.5f32, 2.0, 1.0, 2.5, 1.5, 3.0])
+            .with_order(FormatOrder::Nchw);
+        device.write_input_tensor(0, &planar).unwrap();
+        assert_eq!(mock.written_inputs(0), vec![frame.to_vec(), frame.to_vec()]);
+
+        let wrong_shape = Tensor::from_values(VStreamShape::new(2, 1, 3), &[0u8; 6]);
+        let err = device.write_input_tensor(0, &wrong_shape).unwrap_err();
+        assert!(matches!(err, HailorsError::ShapeMismatch { .. }));
+        assert_eq!(err.to_string(), "Shape mismatch for net/input: expected 1x2x3, got 2x1x3");
+
+        let yuv = Tensor::from_values(VStreamShape::new(1, 2, 3), &[0u8; 6]).with_order(FormatOrder::Nv12);
+        let err = device.write_input_tensor(0, &yuv).unwrap_err();
+        assert!(matches!(err, HailorsError::UnsupportedLayout(FormatOrder::Nv12)));
+    }
+
+    #[test]
+    fn test_auto_format_reads_as_uint8() {
+        let format = VStreamFormat {
+            format_type: FormatType::Auto,
+            order: FormatOrder::Nhwc,
+        };
+        let info = VStreamInfo::new("net/input", VStreamShape::new(1, 2, 2), format);
+        assert_eq!(info.frame_size, 4);
+
+        let frame = [1u8, 2, 3, 4];
+        let tensor = Tensor::new(&info, &frame[..]);
+        assert_eq!(tensor.len(), 4);
+        assert_eq!(tensor.get(0, 1, 1), Some(4.0));
+        assert_eq!(tensor.to_frame(&info).unwrap().as_ref(), &frame);
+        assert_eq!(tensor.to_f32(), vec![1.0, 2.0, 3.0, 4.0]);
+
+        let values = Tensor::from_values(VStreamShape::new(1, 2, 2), &[1.0f32, 2.0, 3.0, 4.0]);
+        assert_eq!(values.to_frame(&info).unwrap().as_ref(), &frame);
+    }
+
+    #[test]
+    fn test_pose_parses_tensor_positions() {
+        let network = YoloPose {
+            num_keypoints: 1,
+            threshold: 0.5,
+        };
+        // Two candidates in NCHW: x, y and confidence planes
+        let tensor = Tensor::from_values(VStreamShape::new(1, 2, 3), &[1.0f32, 4.0, 2.0, 5.0, 0.9, 0.1])
+            .with_order(FormatOrder::Nchw);
+        let poses = network.parse_tensor(&tensor);
+        assert_eq!(poses.len(), 1);
+        assert_eq!(poses[0].keypoints, vec![(1.0, 2.0)]);
+        assert_eq!(poses[0].confidence, 0.9);
+
+        // Read through the device, a FLOAT32 NHWC output parses as the flat list did
+        let mock = mock().with_output("net/poses", VStreamShape::new(1, 2, 3));
+        mock.push_output_f32(2, &[1.0, 2.0, 0.9, 4.0, 5.0, 0.7]).unwrap();
+        let device = HailoDevice::with_backend(mock);
+        let poses = device.read_output_at(2, &network).unwrap();
+        assert_eq!(poses.len(), 2);
+        assert_eq!(poses[1].keypoints, vec![(4.0, 5.0)]);
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn test_ndarray_conversions() {
+        let array = ndarray::Array3::from_shape_fn((2, 3, 4), |(h, w, c)| (h * 100 + w * 10 + c) as f32);
+        let tensor = Tensor::from_array(&array);
+        assert_eq!(tensor.shape, VStreamShape::new(2, 3, 4));
+        assert_eq!(tensor.get(1, 2, 3), Some(123.0));
+        assert_eq!(tensor.to_array().unwrap(), array);
+
+        // Arrays come back indexed by [h, w, c] whatever the order of the frame
+        let planes: Vec<f32> = array.view().permuted_axes([2, 0, 1]).iter().copied().collect();
+        let planar = Tensor::from_values(tensor.shape, &planes).with_order(FormatOrder::Nchw);
+        assert_eq!(planar.to_array().unwrap(), array);
+
+        let nms = tensor.with_order(FormatOrder::HailoNms);
+        assert!(matches!(nms.to_array(), Err(HailorsError::UnsupportedLayout(FormatOrder::HailoNms))));
+    }
+}